@@ -0,0 +1,242 @@
+use lazy_static::lazy_static;
+
+use super::{SchemaDocumentExtension, TypeDefinitionExtension};
+use crate::static_graphql::schema::{Definition, Document, Field, TypeDefinition};
+
+/// The definitions specified by the GraphQL specification, which a schema does not have to
+/// declare: the built-in scalars, the `@skip`, `@include`, `@deprecated`, `@specifiedBy` and
+/// `@oneOf` directives, and the introspection types.
+///
+/// See https://spec.graphql.org/draft/#sec-Schema-Introspection
+pub static BUILTIN_DEFINITIONS: &str = "
+scalar Int
+scalar Float
+scalar String
+scalar Boolean
+scalar ID
+
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @deprecated(reason: String = \"No longer supported\") on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+directive @specifiedBy(url: String!) on SCALAR
+directive @oneOf on INPUT_OBJECT
+
+type __Schema {
+  description: String
+  types: [__Type!]!
+  queryType: __Type!
+  mutationType: __Type
+  subscriptionType: __Type
+  directives: [__Directive!]!
+}
+
+type __Type {
+  kind: __TypeKind!
+  name: String
+  description: String
+  specifiedByURL: String
+  fields(includeDeprecated: Boolean = false): [__Field!]
+  interfaces: [__Type!]
+  possibleTypes: [__Type!]
+  enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+  inputFields(includeDeprecated: Boolean = false): [__InputValue!]
+  ofType: __Type
+  isOneOf: Boolean
+}
+
+enum __TypeKind {
+  SCALAR
+  OBJECT
+  INTERFACE
+  UNION
+  ENUM
+  INPUT_OBJECT
+  LIST
+  NON_NULL
+}
+
+type __Field {
+  name: String!
+  description: String
+  args(includeDeprecated: Boolean = false): [__InputValue!]!
+  type: __Type!
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __InputValue {
+  name: String!
+  description: String
+  type: __Type!
+  defaultValue: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __EnumValue {
+  name: String!
+  description: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __Directive {
+  name: String!
+  description: String
+  isRepeatable: Boolean!
+  locations: [__DirectiveLocation!]!
+  args(includeDeprecated: Boolean = false): [__InputValue!]!
+}
+
+enum __DirectiveLocation {
+  QUERY
+  MUTATION
+  SUBSCRIPTION
+  FIELD
+  FRAGMENT_DEFINITION
+  FRAGMENT_SPREAD
+  INLINE_FRAGMENT
+  VARIABLE_DEFINITION
+  SCHEMA
+  SCALAR
+  OBJECT
+  FIELD_DEFINITION
+  ARGUMENT_DEFINITION
+  INTERFACE
+  UNION
+  ENUM
+  ENUM_VALUE
+  INPUT_OBJECT
+  INPUT_FIELD_DEFINITION
+}
+
+type Query {
+  __schema: __Schema!
+  __type(name: String!): __Type
+}
+";
+
+lazy_static! {
+    static ref BUILTIN_DOCUMENT: Document = graphql_parser::parse_schema(BUILTIN_DEFINITIONS)
+        .expect("Failed to parse builtin definitions")
+        .into_static();
+    static ref INTROSPECTION_FIELDS: Vec<Field> = BUILTIN_DOCUMENT
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.name == "Query" =>
+            {
+                Some(object.fields.clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+}
+
+/// Returns the schema with the definitions of `BUILTIN_DEFINITIONS` it does not declare, so
+/// operations can be validated like with graphql-js, without pasting these definitions into every
+/// schema. The `__schema` and `__type` introspection fields are added to the query type, so the
+/// introspection queries are validated too.
+///
+/// The definitions declared by the schema are kept as is.
+pub fn with_builtin_definitions(schema: &Document) -> Document {
+    let mut augmented = schema.clone();
+
+    for definition in &BUILTIN_DOCUMENT.definitions {
+        let is_defined = match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.name == "Query" =>
+            {
+                true
+            }
+            Definition::TypeDefinition(type_definition) => {
+                schema.type_by_name(type_definition.name()).is_some()
+            }
+            Definition::DirectiveDefinition(directive_definition) => schema
+                .directive_by_name(&directive_definition.name)
+                .is_some(),
+            _ => true,
+        };
+
+        if !is_defined {
+            augmented.definitions.push(definition.clone());
+        }
+    }
+
+    let query_type_name = schema
+        .schema_definition()
+        .query
+        .clone()
+        .unwrap_or_else(|| "Query".to_string());
+
+    for definition in augmented.definitions.iter_mut() {
+        if let Definition::TypeDefinition(TypeDefinition::Object(query_type)) = definition {
+            if query_type.name == query_type_name {
+                for field in INTROSPECTION_FIELDS.iter() {
+                    if !query_type.fields.iter().any(|f| f.name == field.name) {
+                        query_type.fields.push(field.clone());
+                    }
+                }
+
+                break;
+            }
+        }
+    }
+
+    augmented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::FieldByNameExtension;
+
+    #[test]
+    fn add_missing_builtin_definitions() {
+        let schema = graphql_parser::parse_schema(
+            "
+scalar Int
+directive @deprecated(reason: String) on FIELD_DEFINITION
+
+type Query {
+  count: Int @deprecated
+}
+",
+        )
+        .expect("Failed to parse schema")
+        .into_static();
+
+        let augmented = with_builtin_definitions(&schema);
+
+        assert_eq!(
+            augmented
+                .definitions
+                .iter()
+                .filter(|definition| matches!(
+                    definition,
+                    Definition::TypeDefinition(TypeDefinition::Scalar(scalar)) if scalar.name == "Int"
+                ))
+                .count(),
+            1
+        );
+        assert_eq!(
+            augmented
+                .directive_by_name("deprecated")
+                .unwrap()
+                .locations
+                .len(),
+            1
+        );
+        assert!(augmented.directive_by_name("oneOf").is_some());
+        assert!(augmented.type_by_name("String").is_some());
+        assert!(augmented.type_by_name("__DirectiveLocation").is_some());
+
+        let query_type = augmented.type_by_name("Query").unwrap();
+        assert!(query_type.field_by_name(&"count".to_string()).is_some());
+        assert!(query_type.field_by_name(&"__schema".to_string()).is_some());
+        assert!(query_type.field_by_name(&"__type".to_string()).is_some());
+
+        assert_eq!(with_builtin_definitions(&augmented), augmented);
+    }
+}
@@ -1,11 +1,22 @@
 use std::collections::HashMap;
 
-use super::{AbstractTypeDefinitionExtension, OperationVisitorContext, SchemaDocumentExtension};
+use graphql_parser::Pos;
+use serde_json::Value as JsonValue;
+
+use super::{
+    evaluate_skip_include, AbstractTypeDefinitionExtension, OperationVisitorContext,
+    SchemaDocumentExtension,
+};
 use crate::ast::ext::{SubTypeExtension, TypeDefinitionExtension};
 use crate::static_graphql::{
-    query::{self, Selection, TypeCondition},
+    query::{self, Selection, TypeCondition, Value},
     schema::{self, TypeDefinition},
 };
+
+/// Collects the fields of a selection set grouped by field name, following the fragments whose
+/// type condition applies to `parent_type`.
+///
+/// See `collect_grouped_fields` for the field collection of the specification.
 pub fn collect_fields<'a>(
     selection_set: &query::SelectionSet,
     parent_type: &schema::TypeDefinition,
@@ -27,6 +38,323 @@ pub fn collect_fields<'a>(
     map
 }
 
+/// The fields of a selection set grouped by response key (the alias of the field, or its name),
+/// in the order of their first occurrence in the selection set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupedFields<'a> {
+    groups: Vec<(&'a str, Vec<&'a query::Field>)>,
+    /// The conditions of each field of `groups`, in the same order.
+    conditions: Vec<Vec<Vec<FragmentCondition<'a>>>>,
+}
+
+/// The `@skip` and `@include` directives of a fragment, when they cannot be evaluated (they depend
+/// on a variable without a value).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FragmentCondition<'a> {
+    /// The position of the inline fragment or of the fragment spread.
+    pub position: Pos,
+    pub directives: Vec<&'a query::Directive>,
+}
+
+impl<'a> GroupedFields<'a> {
+    pub fn get(&self, response_key: &str) -> Option<&[&'a query::Field]> {
+        self.groups
+            .iter()
+            .find(|(key, _)| *key == response_key)
+            .map(|(_, fields)| fields.as_slice())
+    }
+
+    pub fn contains_key(&self, response_key: &str) -> bool {
+        self.get(response_key).is_some()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.groups.iter().map(|(key, _)| *key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &[&'a query::Field])> {
+        self.groups
+            .iter()
+            .map(|(key, fields)| (*key, fields.as_slice()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Iterates over the fields, group after group, with the conditions of the fragments each
+    /// field was collected through, from the outermost one. The conditions are only tracked with
+    /// `CollectFieldsOptions::conditions`.
+    pub fn iter_with_conditions(
+        &self,
+    ) -> impl Iterator<Item = (&'a query::Field, &[FragmentCondition<'a>])> {
+        self.groups
+            .iter()
+            .zip(&self.conditions)
+            .flat_map(|((_, fields), conditions)| {
+                fields
+                    .iter()
+                    .zip(conditions)
+                    .map(|(field, conditions)| (*field, conditions.as_slice()))
+            })
+    }
+
+    fn push(&mut self, field: &'a query::Field, conditions: &[FragmentCondition<'a>]) {
+        let response_key = field.alias.as_deref().unwrap_or(field.name.as_str());
+
+        match self.groups.iter().position(|(key, _)| *key == response_key) {
+            Some(index) => {
+                self.groups[index].1.push(field);
+                self.conditions[index].push(conditions.to_vec());
+            }
+            None => {
+                self.groups.push((response_key, vec![field]));
+                self.conditions.push(vec![conditions.to_vec()]);
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for GroupedFields<'a> {
+    type Item = (&'a str, Vec<&'a query::Field>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.groups.into_iter()
+    }
+}
+
+/// The fields of a fragment deferred with `@defer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredFields<'a> {
+    /// The `label` argument of the `@defer` directive.
+    pub label: Option<&'a str>,
+    pub fields: GroupedFields<'a>,
+}
+
+/// The result of `collect_grouped_fields`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectedFields<'a> {
+    pub fields: GroupedFields<'a>,
+    /// The deferred fragments, in the order of the selection set (a fragment deferred within a
+    /// deferred fragment comes after it).
+    pub deferred: Vec<DeferredFields<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollectFieldsOptions<'v> {
+    /// The variable values used to evaluate `@skip`, `@include` and `@defer`. The selections
+    /// depending on a variable without a value are included, and the fragments are deferred.
+    pub variables: Option<&'v HashMap<String, JsonValue>>,
+    /// When `true`, the fragments with `@defer` are collected into `CollectedFields::deferred`,
+    /// otherwise `@defer` is ignored.
+    pub defer: bool,
+    /// When `true`, the `@skip` and `@include` directives of the fragments that cannot be
+    /// evaluated are kept with the fields collected through these fragments (see
+    /// `GroupedFields::iter_with_conditions`), and a named fragment is collected again when it was
+    /// only collected under such conditions.
+    pub conditions: bool,
+}
+
+/// Collects the fields of a selection set, as `CollectFields()` of the specification: the fields
+/// are grouped by response key in order, the selections excluded by `@skip` or `@include` are not
+/// collected, the fragments are collected when their type condition applies to `parent_type`, and
+/// each named fragment is collected once.
+///
+/// Unlike `collect_fields`, the fields are not cloned, and fields with different aliases are not
+/// grouped together.
+///
+/// See https://spec.graphql.org/draft/#CollectFields()
+pub fn collect_grouped_fields<'a>(
+    selection_set: &'a query::SelectionSet,
+    parent_type: &schema::TypeDefinition,
+    context: &OperationVisitorContext<'a>,
+    options: CollectFieldsOptions<'_>,
+) -> CollectedFields<'a> {
+    let no_variables = HashMap::new();
+    let mut collector = GroupedFieldsCollector {
+        context,
+        variables: options.variables.unwrap_or(&no_variables),
+        defer: options.defer,
+        track_conditions: options.conditions,
+        deferred: vec![],
+        conditions: vec![],
+        visited_fragments_names: vec![],
+        spread_fragments_names: vec![],
+    };
+
+    let mut fields = GroupedFields::default();
+    collector.collect(selection_set, parent_type, &mut fields);
+
+    CollectedFields {
+        fields,
+        deferred: collector.deferred,
+    }
+}
+
+struct GroupedFieldsCollector<'a, 'c> {
+    context: &'c OperationVisitorContext<'a>,
+    variables: &'c HashMap<String, JsonValue>,
+    defer: bool,
+    track_conditions: bool,
+    deferred: Vec<DeferredFields<'a>>,
+    /// The conditions of the fragments being collected.
+    conditions: Vec<FragmentCondition<'a>>,
+    visited_fragments_names: Vec<&'a str>,
+    /// The fragments being collected, to stop on fragment cycles.
+    spread_fragments_names: Vec<&'a str>,
+}
+
+impl<'a, 'c> GroupedFieldsCollector<'a, 'c> {
+    fn collect(
+        &mut self,
+        selection_set: &'a query::SelectionSet,
+        parent_type: &schema::TypeDefinition,
+        fields: &mut GroupedFields<'a>,
+    ) {
+        for item in &selection_set.items {
+            match item {
+                Selection::Field(f) => {
+                    if evaluate_skip_include(&f.directives, self.variables) != Some(false) {
+                        fields.push(f, &self.conditions);
+                    }
+                }
+                Selection::InlineFragment(f) => {
+                    if evaluate_skip_include(&f.directives, self.variables) != Some(false)
+                        && does_fragment_condition_match(
+                            &f.type_condition,
+                            parent_type,
+                            self.context,
+                        )
+                    {
+                        self.collect_conditional_fragment(
+                            f.position,
+                            &f.directives,
+                            &f.selection_set,
+                            parent_type,
+                            fields,
+                        );
+                    }
+                }
+                Selection::FragmentSpread(f) => {
+                    let name = f.fragment_name.as_str();
+                    let included = evaluate_skip_include(&f.directives, self.variables);
+
+                    if included == Some(false)
+                        || self.visited_fragments_names.contains(&name)
+                        || self.spread_fragments_names.contains(&name)
+                    {
+                        continue;
+                    }
+
+                    // A fragment collected under conditions is collected again elsewhere.
+                    if !self.track_conditions || (self.conditions.is_empty() && included.is_some())
+                    {
+                        self.visited_fragments_names.push(name);
+                    }
+
+                    if let Some(fragment) = self.context.known_fragments.get(name) {
+                        if does_fragment_condition_match(
+                            &Some(fragment.type_condition.clone()),
+                            parent_type,
+                            self.context,
+                        ) {
+                            self.spread_fragments_names.push(name);
+                            self.collect_conditional_fragment(
+                                f.position,
+                                &f.directives,
+                                &fragment.selection_set,
+                                parent_type,
+                                fields,
+                            );
+                            self.spread_fragments_names.pop();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect_conditional_fragment(
+        &mut self,
+        position: Pos,
+        directives: &'a [query::Directive],
+        selection_set: &'a query::SelectionSet,
+        parent_type: &schema::TypeDefinition,
+        fields: &mut GroupedFields<'a>,
+    ) {
+        if !self.track_conditions || evaluate_skip_include(directives, self.variables).is_some() {
+            return self.collect_fragment(directives, selection_set, parent_type, fields);
+        }
+
+        self.conditions.push(FragmentCondition {
+            position,
+            directives: directives
+                .iter()
+                .filter(|directive| directive.name == "skip" || directive.name == "include")
+                .collect(),
+        });
+        self.collect_fragment(directives, selection_set, parent_type, fields);
+        self.conditions.pop();
+    }
+
+    fn collect_fragment(
+        &mut self,
+        directives: &'a [query::Directive],
+        selection_set: &'a query::SelectionSet,
+        parent_type: &schema::TypeDefinition,
+        fields: &mut GroupedFields<'a>,
+    ) {
+        let defer = match directives.iter().find(|d| d.name == "defer") {
+            Some(defer) if self.defer => defer,
+            _ => return self.collect(selection_set, parent_type, fields),
+        };
+
+        let argument = |name: &str| {
+            defer
+                .arguments
+                .iter()
+                .find(|(argument_name, _)| argument_name == name)
+                .map(|(_, value)| value)
+        };
+
+        let deferred = match argument("if") {
+            Some(Value::Boolean(value)) => *value,
+            Some(Value::Variable(name)) => self
+                .variables
+                .get(name)
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(true),
+            _ => true,
+        };
+
+        if !deferred {
+            return self.collect(selection_set, parent_type, fields);
+        }
+
+        let label = match argument("label") {
+            Some(Value::String(label)) => Some(label.as_str()),
+            _ => None,
+        };
+
+        // The deferred fragment comes before the fragments deferred within it.
+        let index = self.deferred.len();
+        let mut deferred_fields = GroupedFields::default();
+        self.collect(selection_set, parent_type, &mut deferred_fields);
+        self.deferred.insert(
+            index,
+            DeferredFields {
+                label,
+                fields: deferred_fields,
+            },
+        );
+    }
+}
+
 fn does_fragment_condition_match<'a>(
     fragment_condition: &'a Option<TypeCondition>,
     current_selection_set_type: &'a TypeDefinition,
@@ -113,3 +441,208 @@ fn collect_fields_inner<'a>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::static_graphql::query::Definition;
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    fn collect(
+        operation: &str,
+        options: CollectFieldsOptions<'_>,
+        check: impl Fn(&CollectedFields<'_>),
+    ) {
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let document = graphql_parser::parse_query(operation)
+            .expect("Failed to parse query")
+            .into_static();
+        let context = OperationVisitorContext::new(&document, &schema);
+
+        let selection_set = match &document.definitions[0] {
+            Definition::Operation(query::OperationDefinition::SelectionSet(selection_set)) => {
+                selection_set
+            }
+            _ => unreachable!(),
+        };
+
+        check(&collect_grouped_fields(
+            selection_set,
+            schema.type_by_name("Dog").unwrap(),
+            &context,
+            options,
+        ));
+    }
+
+    fn keys<'a>(fields: &'a GroupedFields<'_>) -> Vec<&'a str> {
+        fields.keys().collect()
+    }
+
+    #[test]
+    fn group_fields_by_response_key_in_order() {
+        collect(
+            "
+{
+  nickname
+  name: nickname
+  ... on Cat { meowVolume }
+  ...DogFields
+  barkVolume @skip(if: $skip)
+  doesKnowCommand(dogCommand: SIT) @include(if: $unknown)
+  ...DogFields
+}
+
+fragment DogFields on Dog {
+  name
+  nickname
+}
+",
+            CollectFieldsOptions {
+                variables: Some(&HashMap::from([(
+                    "skip".to_string(),
+                    JsonValue::Bool(true),
+                )])),
+                defer: true,
+                ..Default::default()
+            },
+            |collected| {
+                assert_eq!(
+                    keys(&collected.fields),
+                    vec!["nickname", "name", "doesKnowCommand"]
+                );
+                assert_eq!(
+                    collected
+                        .fields
+                        .get("name")
+                        .unwrap()
+                        .iter()
+                        .map(|field| field.name.as_str())
+                        .collect::<Vec<_>>(),
+                    vec!["nickname", "name"]
+                );
+                assert_eq!(collected.fields.get("nickname").unwrap().len(), 2);
+                assert!(collected.deferred.is_empty());
+            },
+        );
+    }
+
+    #[test]
+    fn collect_fields_with_skip_include() {
+        collect(
+            "
+{
+  name @skip(if: $skip)
+  nickname @include(if: $skip)
+  ...DogFields @include(if: false)
+  ...DogFields
+}
+
+fragment DogFields on Dog {
+  barkVolume
+}
+",
+            CollectFieldsOptions {
+                variables: Some(&HashMap::from([(
+                    "skip".to_string(),
+                    JsonValue::Bool(true),
+                )])),
+                ..Default::default()
+            },
+            |collected| {
+                assert_eq!(keys(&collected.fields), vec!["nickname", "barkVolume"]);
+            },
+        );
+    }
+
+    #[test]
+    fn track_skip_include_conditions() {
+        collect(
+            "
+{
+  name
+  ... @include(if: $details) {
+    ...DogFields @skip(if: $short)
+  }
+  ...DogFields @include(if: true)
+}
+
+fragment DogFields on Dog {
+  barkVolume
+}
+",
+            CollectFieldsOptions {
+                conditions: true,
+                ..Default::default()
+            },
+            |collected| {
+                assert_eq!(
+                    collected
+                        .fields
+                        .iter_with_conditions()
+                        .map(|(field, conditions)| (
+                            field.name.as_str(),
+                            conditions
+                                .iter()
+                                .map(|condition| (
+                                    condition.position.line,
+                                    condition.directives[0].name.as_str()
+                                ))
+                                .collect::<Vec<_>>()
+                        ))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        ("name", vec![]),
+                        ("barkVolume", vec![(4, "include"), (5, "skip")]),
+                        ("barkVolume", vec![]),
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn group_deferred_fragments() {
+        collect(
+            "
+{
+  name
+  ... @defer(label: \"details\") {
+    barkVolume
+    ...Mother @defer
+  }
+  ... @defer(if: $noDefer) {
+    nickname
+  }
+}
+
+fragment Mother on Dog {
+  mother { name }
+}
+",
+            CollectFieldsOptions {
+                variables: Some(&HashMap::from([(
+                    "noDefer".to_string(),
+                    JsonValue::Bool(false),
+                )])),
+                defer: true,
+                ..Default::default()
+            },
+            |collected| {
+                assert_eq!(keys(&collected.fields), vec!["name", "nickname"]);
+                assert_eq!(
+                    collected
+                        .deferred
+                        .iter()
+                        .map(|deferred| (deferred.label, keys(&deferred.fields)))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        (Some("details"), vec!["barkVolume"]),
+                        (None, vec!["mother"]),
+                    ]
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,771 @@
+use graphql_parser::schema::DirectiveLocation;
+use graphql_parser::Pos;
+
+use super::{
+    visit_document, DeprecationExtension, FieldByNameExtension, OperationVisitor,
+    OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+};
+use crate::static_graphql::query::{self, Field, SelectionSet};
+use crate::static_graphql::schema::{self, InputValue, TypeDefinition};
+
+/// The kind of a completion candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Field,
+    Argument,
+    InputField,
+    EnumValue,
+    /// A literal value, like `true`.
+    Value,
+    Directive,
+}
+
+/// A candidate returned by `completions_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// The type of a field, an argument or an input field (like `[String!]!`), the enum of an
+    /// enum value, or the signature of a directive (like `@include(if: Boolean!)`).
+    pub detail: Option<String>,
+    pub description: Option<String>,
+    pub deprecation_reason: Option<String>,
+}
+
+/// Returns the completion candidates at a position of an operation document (as reported by the
+/// parser: 1-based, with a tab counting as 8 columns):
+///
+/// - in a selection set, the fields of its type
+/// - after `@`, the directives allowed at this location
+/// - in the arguments of a field or a directive, the arguments
+/// - in an input object value, the fields of the input object type
+/// - in the value of an argument, of an input field or of a list item, the values of its enum
+///   type, or `true` and `false` for a `Boolean`
+///
+/// The document does not have to be complete: when it cannot be parsed, it is completed with a
+/// placeholder at the cursor and the missing closing brackets, and the text after the cursor is
+/// dropped if needed.
+pub fn completions_at(schema: &schema::Document, document: &str, position: Pos) -> Vec<Completion> {
+    let offset = offset_of(document, position);
+    let word_start = document[..offset]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_name_char(*c))
+        .map(|(index, c)| index + c.len_utf8())
+        .unwrap_or(0);
+    let at_word_start = word_start == offset;
+
+    match context_at(document, word_start) {
+        Some(Context::SelectionSet) => {
+            let placeholder = if at_word_start { "__typename" } else { "" };
+
+            parse_completed(document, offset, placeholder)
+                .and_then(|parsed| selection_set_type(schema, &parsed, position))
+                .and_then(|type_name| schema.type_by_name(&type_name))
+                .map(field_completions)
+                .unwrap_or_default()
+        }
+        Some(Context::Directive { locations }) => directive_completions(schema, locations),
+        Some(Context::Input {
+            owner,
+            path,
+            target,
+        }) => {
+            let placeholder = match (&target, at_word_start) {
+                (InputTarget::Name, true) => "placeholder: null",
+                (InputTarget::Name, false) => ": null",
+                (_, true) => "null",
+                (_, false) => "",
+            };
+            let arguments = match &owner {
+                ArgumentsOwner::Directive(name) => schema
+                    .directive_by_name(name)
+                    .map(|directive| directive.arguments.as_slice()),
+                ArgumentsOwner::Field { name, start } => {
+                    parse_completed(document, offset, placeholder).and_then(|parsed| {
+                        field_arguments(schema, &parsed, name, pos_of_offset(document, *start))
+                    })
+                }
+            };
+
+            arguments
+                .and_then(|arguments| input_completions(schema, arguments, &path, &target))
+                .unwrap_or_default()
+        }
+        None => vec![],
+    }
+}
+
+/// The syntactic context of the cursor.
+enum Context {
+    SelectionSet,
+    Directive {
+        locations: &'static [DirectiveLocation],
+    },
+    /// In the arguments of a field or a directive, possibly nested in input values.
+    Input {
+        owner: ArgumentsOwner,
+        path: Vec<PathSegment>,
+        target: InputTarget,
+    },
+}
+
+enum ArgumentsOwner {
+    /// A field, with the offset of its name.
+    Field {
+        name: String,
+        start: usize,
+    },
+    Directive(String),
+}
+
+/// A step from the arguments to the input value at the cursor.
+enum PathSegment {
+    /// The value of an argument or of an input field.
+    Key(String),
+    Object,
+    List,
+}
+
+enum InputTarget {
+    /// The name of an argument or of an input field.
+    Name,
+    /// The value of an argument or of an input field.
+    Value(String),
+    ListItem,
+}
+
+/// Determines the context of the cursor, from the text before it.
+fn context_at(source: &str, word_start: usize) -> Option<Context> {
+    let before = &source[..word_start];
+    let trimmed = before.trim_end();
+    let brackets = open_brackets(before);
+
+    if before.ends_with('@') {
+        let locations: &'static [DirectiveLocation] = match brackets.last() {
+            // The current definition starts after the last selection set of the document
+            None => match before[before.rfind('}').map_or(0, |index| index + 1)..]
+                .split(|c: char| !is_name_char(c))
+                .find(|word| !word.is_empty())
+            {
+                Some("query") => &[DirectiveLocation::Query],
+                Some("mutation") => &[DirectiveLocation::Mutation],
+                Some("subscription") => &[DirectiveLocation::Subscription],
+                Some("fragment") => &[DirectiveLocation::FragmentDefinition],
+                _ => return None,
+            },
+            Some(('{', _)) if brackets.iter().all(|(bracket, _)| *bracket == '{') => &[
+                DirectiveLocation::Field,
+                DirectiveLocation::FragmentSpread,
+                DirectiveLocation::InlineFragment,
+            ],
+            _ => return None,
+        };
+
+        return Some(Context::Directive { locations });
+    }
+
+    if before.ends_with('$') || trimmed.ends_with("...") || trimmed.ends_with("... on") {
+        return None;
+    }
+
+    let arguments_start = match brackets.iter().position(|(bracket, _)| *bracket == '(') {
+        Some(index) => index,
+        None => {
+            return match brackets.last() {
+                Some(('{', _)) => Some(Context::SelectionSet),
+                _ => None,
+            };
+        }
+    };
+
+    let parenthesis = brackets[arguments_start].1;
+    let owner_end = source[..parenthesis].trim_end().len();
+    let owner_start = name_start(&source[..owner_end]);
+    let owner_name = source[owner_start..owner_end].to_string();
+
+    if owner_name.is_empty() {
+        return None;
+    }
+
+    let owner = if source[..owner_start].ends_with('@') {
+        ArgumentsOwner::Directive(owner_name)
+    } else {
+        ArgumentsOwner::Field {
+            name: owner_name,
+            start: owner_start,
+        }
+    };
+
+    let mut path = vec![];
+    for (bracket, offset) in &brackets[arguments_start + 1..] {
+        if let Some(key) = key_before(&source[..*offset]) {
+            path.push(PathSegment::Key(key));
+        }
+
+        path.push(match bracket {
+            '{' => PathSegment::Object,
+            _ => PathSegment::List,
+        });
+    }
+
+    let target = match (brackets.last(), key_before(trimmed)) {
+        (_, Some(key)) => InputTarget::Value(key),
+        (Some(('[', _)), None) => InputTarget::ListItem,
+        (_, None) => InputTarget::Name,
+    };
+
+    Some(Context::Input {
+        owner,
+        path,
+        target,
+    })
+}
+
+/// Returns the name before a colon ending the text (like `name:`).
+fn key_before(text: &str) -> Option<String> {
+    let text = text.trim_end().strip_suffix(':')?.trim_end();
+    let key = &text[name_start(text)..];
+
+    if key.is_empty() {
+        None
+    } else {
+        Some(key.to_string())
+    }
+}
+
+fn name_start(text: &str) -> usize {
+    text.char_indices()
+        .rev()
+        .find(|(_, c)| !is_name_char(*c))
+        .map(|(index, c)| index + c.len_utf8())
+        .unwrap_or(0)
+}
+
+/// Returns `true` for the characters of a GraphQL name (letters, digits and underscores).
+pub fn is_name_char(c: char) -> bool {
+    c == '_' || c.is_ascii_alphanumeric()
+}
+
+/// Returns the brackets (with their offsets) opened in the text and not closed, skipping the
+/// strings and the comments.
+fn open_brackets(text: &str) -> Vec<(char, usize)> {
+    let mut brackets = vec![];
+    let mut chars = text.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '#' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' if text[index..].starts_with("\"\"\"") => {
+                chars.nth(1);
+
+                while let Some((index, c)) = chars.next() {
+                    if c == '\\' {
+                        chars.next();
+                    } else if text[index..].starts_with("\"\"\"") {
+                        chars.nth(1);
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' | '\n' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '(' | '[' => brackets.push((c, index)),
+            '}' | ')' | ']' => {
+                brackets.pop();
+            }
+            _ => {}
+        }
+    }
+
+    brackets
+}
+
+/// Parses the document, completing it when it cannot be parsed as is: with the placeholder at the
+/// cursor, then with the missing closing brackets, then without the text after the cursor. The
+/// text before the cursor is kept as is, so the positions before the cursor do not change.
+fn parse_completed(source: &str, offset: usize, placeholder: &str) -> Option<query::Document> {
+    let with_placeholder = format!("{}{}{}", &source[..offset], placeholder, &source[offset..]);
+    let mut truncated = format!("{}{}", &source[..offset], placeholder);
+    let mut closed = with_placeholder.clone();
+    close_brackets(&mut closed);
+    close_brackets(&mut truncated);
+
+    [source, &with_placeholder, &closed, &truncated]
+        .iter()
+        .find_map(|candidate| {
+            graphql_parser::parse_query::<String>(candidate)
+                .ok()
+                .map(|document| document.into_static())
+        })
+}
+
+fn close_brackets(source: &mut String) {
+    for (bracket, _) in open_brackets(source).into_iter().rev() {
+        source.push(match bracket {
+            '{' => '}',
+            '(' => ')',
+            _ => ']',
+        });
+    }
+}
+
+/// Returns the byte offset of a position in the source.
+fn offset_of(source: &str, position: Pos) -> usize {
+    let mut offset = 0;
+
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        if index + 1 == position.line {
+            let mut column = 1;
+
+            for (byte, c) in line.char_indices() {
+                if column >= position.column || c == '\n' {
+                    return offset + byte;
+                }
+
+                column += column_width(c);
+            }
+
+            return offset + line.len();
+        }
+
+        offset += line.len();
+    }
+
+    source.len()
+}
+
+/// Returns the position of a byte offset in the source.
+fn pos_of_offset(source: &str, offset: usize) -> Pos {
+    let line_start = source[..offset]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    Pos {
+        line: source[..line_start].matches('\n').count() + 1,
+        column: 1 + source[line_start..offset]
+            .chars()
+            .map(column_width)
+            .sum::<usize>(),
+    }
+}
+
+/// Returns the number of columns the parser counts for a character in its positions: 8 for a tab,
+/// none for a carriage return or a byte order mark.
+pub fn column_width(c: char) -> usize {
+    match c {
+        '\t' => 8,
+        '\r' | '\u{feff}' => 0,
+        _ => 1,
+    }
+}
+
+/// Returns the name of the type of the innermost selection set containing the position.
+fn selection_set_type(
+    schema: &schema::Document,
+    document: &query::Document,
+    position: Pos,
+) -> Option<String> {
+    struct SelectionSetTypeFinder {
+        position: Pos,
+    }
+
+    impl<'a> OperationVisitor<'a, Option<String>> for SelectionSetTypeFinder {
+        fn enter_selection_set(
+            &mut self,
+            visitor_context: &mut OperationVisitorContext<'a>,
+            type_name: &mut Option<String>,
+            selection_set: &'a SelectionSet,
+        ) {
+            if selection_set.span.0 < self.position && self.position <= selection_set.span.1 {
+                *type_name = visitor_context
+                    .current_parent_type()
+                    .map(|parent_type| parent_type.name().to_string());
+            }
+        }
+    }
+
+    let mut type_name = None;
+
+    visit_document(
+        &mut SelectionSetTypeFinder { position },
+        document,
+        &mut OperationVisitorContext::new(document, schema),
+        &mut type_name,
+    );
+
+    type_name
+}
+
+/// Returns the arguments of the field named `name`, selected at `start`.
+fn field_arguments<'s>(
+    schema: &'s schema::Document,
+    document: &query::Document,
+    name: &str,
+    start: Pos,
+) -> Option<&'s [InputValue]> {
+    struct FieldFinder<'n> {
+        name: &'n str,
+        start: Pos,
+    }
+
+    impl<'a, 'n> OperationVisitor<'a, Option<String>> for FieldFinder<'n> {
+        fn enter_field(
+            &mut self,
+            visitor_context: &mut OperationVisitorContext<'a>,
+            parent_type_name: &mut Option<String>,
+            field: &Field,
+        ) {
+            // The field starts at its alias, if any, on the line of its name
+            if field.name == self.name
+                && field.position.line == self.start.line
+                && field.position.column <= self.start.column
+            {
+                *parent_type_name = visitor_context
+                    .current_parent_type()
+                    .map(|parent_type| parent_type.name().to_string());
+            }
+        }
+    }
+
+    let mut parent_type_name = None;
+
+    visit_document(
+        &mut FieldFinder { name, start },
+        document,
+        &mut OperationVisitorContext::new(document, schema),
+        &mut parent_type_name,
+    );
+
+    schema
+        .type_by_name(&parent_type_name?)?
+        .field_by_name(&name.to_string())
+        .map(|field| field.arguments.as_slice())
+}
+
+fn field_completions(parent_type: &TypeDefinition) -> Vec<Completion> {
+    let fields = match parent_type {
+        TypeDefinition::Object(object) => object.fields.as_slice(),
+        TypeDefinition::Interface(interface) => interface.fields.as_slice(),
+        _ => &[],
+    };
+
+    fields
+        .iter()
+        .map(|field| Completion {
+            label: field.name.clone(),
+            kind: CompletionKind::Field,
+            detail: Some(field.field_type.to_string()),
+            description: field.description.clone(),
+            deprecation_reason: field.deprecation_reason().map(String::from),
+        })
+        .chain(std::iter::once(Completion {
+            label: "__typename".to_string(),
+            kind: CompletionKind::Field,
+            detail: Some("String!".to_string()),
+            description: None,
+            deprecation_reason: None,
+        }))
+        .collect()
+}
+
+fn directive_completions(
+    schema: &schema::Document,
+    locations: &[DirectiveLocation],
+) -> Vec<Completion> {
+    schema
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            schema::Definition::DirectiveDefinition(directive) => Some(directive),
+            _ => None,
+        })
+        .filter(|directive| {
+            directive
+                .locations
+                .iter()
+                .any(|location| locations.contains(location))
+        })
+        .map(|directive| {
+            let arguments = directive
+                .arguments
+                .iter()
+                .map(|argument| format!("{}: {}", argument.name, argument.value_type))
+                .collect::<Vec<_>>();
+
+            Completion {
+                label: directive.name.clone(),
+                kind: CompletionKind::Directive,
+                detail: Some(if arguments.is_empty() {
+                    format!("@{}", directive.name)
+                } else {
+                    format!("@{}({})", directive.name, arguments.join(", "))
+                }),
+                description: directive.description.clone(),
+                deprecation_reason: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the candidates for the input value at the end of the path, from the arguments.
+fn input_completions(
+    schema: &schema::Document,
+    arguments: &[InputValue],
+    path: &[PathSegment],
+    target: &InputTarget,
+) -> Option<Vec<Completion>> {
+    let mut inputs = arguments;
+    let mut value_type = None;
+
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                value_type = Some(
+                    inputs
+                        .iter()
+                        .find(|input| &input.name == key)?
+                        .value_type
+                        .inner_type(),
+                );
+            }
+            PathSegment::Object => match schema.type_by_name(value_type?)? {
+                TypeDefinition::InputObject(input_object) => inputs = &input_object.fields,
+                _ => return None,
+            },
+            PathSegment::List => {}
+        }
+    }
+
+    let value_type = match target {
+        InputTarget::Name => {
+            let kind = if path.is_empty() {
+                CompletionKind::Argument
+            } else {
+                CompletionKind::InputField
+            };
+
+            return Some(
+                inputs
+                    .iter()
+                    .map(|input| Completion {
+                        label: input.name.clone(),
+                        kind,
+                        detail: Some(input.value_type.to_string()),
+                        description: input.description.clone(),
+                        deprecation_reason: input.deprecation_reason().map(String::from),
+                    })
+                    .collect(),
+            );
+        }
+        InputTarget::Value(key) => inputs
+            .iter()
+            .find(|input| &input.name == key)?
+            .value_type
+            .inner_type(),
+        InputTarget::ListItem => value_type?,
+    };
+
+    match schema.type_by_name(value_type)? {
+        TypeDefinition::Enum(enum_type) => Some(
+            enum_type
+                .values
+                .iter()
+                .map(|value| Completion {
+                    label: value.name.clone(),
+                    kind: CompletionKind::EnumValue,
+                    detail: Some(enum_type.name.clone()),
+                    description: value.description.clone(),
+                    deprecation_reason: value.deprecation_reason().map(String::from),
+                })
+                .collect(),
+        ),
+        TypeDefinition::Scalar(scalar) if scalar.name == "Boolean" => Some(
+            ["true", "false"]
+                .iter()
+                .map(|value| Completion {
+                    label: value.to_string(),
+                    kind: CompletionKind::Value,
+                    detail: Some("Boolean".to_string()),
+                    description: None,
+                    deprecation_reason: None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::with_builtin_definitions;
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    /// Returns the labels of the candidates at the `|` of the document.
+    fn labels(document: &str) -> Vec<String> {
+        let schema = with_builtin_definitions(
+            &graphql_parser::parse_schema(TEST_SCHEMA)
+                .expect("Failed to parse schema")
+                .into_static(),
+        );
+        let offset = document.find('|').unwrap();
+        let document = document.replace('|', "");
+
+        completions_at(&schema, &document, pos_of_offset(&document, offset))
+            .into_iter()
+            .map(|completion| completion.label)
+            .collect()
+    }
+
+    #[test]
+    fn complete_fields_and_directives() {
+        let dog_fields = vec![
+            "name",
+            "nickname",
+            "barkVolume",
+            "barks",
+            "doesKnowCommand",
+            "isHouseTrained",
+            "isAtLocation",
+            "mother",
+            "father",
+            "__typename",
+        ];
+
+        assert_eq!(labels("{ dog { | } }"), dog_fields);
+        assert_eq!(labels("{ dog { ba| } }"), dog_fields);
+        assert_eq!(labels("query {\n  dog {\n    ba|"), dog_fields);
+        assert_eq!(labels("{ dog { | }\n  cat { meows(( }"), dog_fields);
+        assert_eq!(
+            labels("{ catOrDog { ... on Cat { | } } }")[..2],
+            ["name", "nickname"]
+        );
+        assert_eq!(labels("{ catOrDog { | } }"), vec!["__typename"]);
+        assert_eq!(labels("{ catOrDog { ... on | } }"), Vec::<String>::new());
+        assert_eq!(
+            labels("{ dog @| { name } }"),
+            vec![
+                "onField",
+                "onFragmentSpread",
+                "onInlineFragment",
+                "testDirective",
+                "repeatable",
+                "skip",
+                "include"
+            ]
+        );
+        assert_eq!(labels("query @| { dog { name } }"), vec!["onQuery"]);
+        assert_eq!(
+            labels("{ dog { name } }\nfragment F on Dog @|"),
+            vec!["onFragmentDefinition", "testDirective", "repeatable"]
+        );
+    }
+
+    #[test]
+    fn complete_arguments_and_values() {
+        assert_eq!(
+            labels("{ dog { isAtLocation(|) } }"),
+            vec!["x".to_string(), "y".to_string()]
+        );
+        assert_eq!(
+            labels("{ dog { location: isAtLocation(x: 1, |) } }"),
+            vec!["x", "y"]
+        );
+        assert_eq!(
+            labels("{ dog { doesKnowCommand(dogCommand: |) } }"),
+            vec!["SIT", "HEEL", "DOWN"]
+        );
+        assert_eq!(
+            labels("{ dog { doesKnowCommand(dogCommand: H|"),
+            vec!["SIT", "HEEL", "DOWN"]
+        );
+        assert_eq!(
+            labels("{ complicatedArgs { complexArgField(complexArg: { |"),
+            vec![
+                "requiredField",
+                "nonNullField",
+                "intField",
+                "stringField",
+                "booleanField",
+                "stringListField"
+            ]
+        );
+        assert_eq!(
+            labels(
+                "{ complicatedArgs { complexArgField(complexArg: { intField: 1, booleanField: |"
+            ),
+            vec!["true", "false"]
+        );
+        assert_eq!(
+            labels("{ dog @include(if: |) { name } }"),
+            vec!["true", "false"]
+        );
+    }
+
+    #[test]
+    fn completion_details() {
+        let schema = graphql_parser::parse_schema(
+            "
+type Query {
+  \"The dog\"
+  dog(command: Command): Dog @deprecated(reason: \"Use pets\")
+}
+
+type Dog {
+  name: String
+}
+
+enum Command {
+  SIT
+}
+",
+        )
+        .expect("Failed to parse schema")
+        .into_static();
+
+        assert_eq!(
+            completions_at(&schema, "{  }", Pos { line: 1, column: 3 })[0],
+            Completion {
+                label: "dog".to_string(),
+                kind: CompletionKind::Field,
+                detail: Some("Dog".to_string()),
+                description: Some("The dog".to_string()),
+                deprecation_reason: Some("Use pets".to_string()),
+            }
+        );
+        assert_eq!(
+            completions_at(
+                &schema,
+                "{ dog(command: ) }",
+                Pos {
+                    line: 1,
+                    column: 16
+                }
+            ),
+            vec![Completion {
+                label: "SIT".to_string(),
+                kind: CompletionKind::EnumValue,
+                detail: Some("Command".to_string()),
+                description: None,
+                deprecation_reason: None,
+            }]
+        );
+    }
+}
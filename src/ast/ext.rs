@@ -7,7 +7,8 @@ use crate::static_graphql::query::{
     VariableDefinition,
 };
 use crate::static_graphql::schema::{
-    self, DirectiveDefinition, InputValue, InterfaceType, ObjectType, TypeDefinition, UnionType,
+    self, DirectiveDefinition, InputObjectType, InputValue, InterfaceType, ObjectType,
+    TypeDefinition, UnionType,
 };
 
 pub trait FieldByNameExtension {
@@ -89,6 +90,25 @@ pub trait SchemaDocumentExtension {
         abstract_type: &TypeDefinition,
         possible_type: &TypeDefinition,
     ) -> bool;
+
+    /// The name of the root type of an operation, from the schema definition (or the default
+    /// root type names).
+    fn root_type_name(&self, operation: &OperationDefinition) -> &str {
+        let schema_definition = self.schema_definition();
+
+        match operation {
+            OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_) => {
+                schema_definition.query.as_deref().unwrap_or("Query")
+            }
+            OperationDefinition::Mutation(_) => {
+                schema_definition.mutation.as_deref().unwrap_or("Mutation")
+            }
+            OperationDefinition::Subscription(_) => schema_definition
+                .subscription
+                .as_deref()
+                .unwrap_or("Subscription"),
+        }
+    }
 }
 
 impl SchemaDocumentExtension for schema::Document {
@@ -343,6 +363,24 @@ impl ValueExtension for Value {
     }
 }
 
+/// Compares the arguments of two fields or directives, in any order, with
+/// `ValueExtension::compare`.
+pub(crate) fn arguments_equal(a: &[(String, Value)], b: &[(String, Value)]) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|(name, value)| {
+            b.iter()
+                .any(|(other_name, other_value)| name == other_name && value.compare(other_value))
+        })
+}
+
+/// Compares two lists of directives, in order, with their arguments.
+pub(crate) fn directives_equal(a: &[Directive], b: &[Directive]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.name == b.name && arguments_equal(&a.arguments, &b.arguments))
+}
+
 pub trait InputValueHelpers {
     fn is_required(&self) -> bool;
 }
@@ -359,6 +397,73 @@ impl InputValueHelpers for InputValue {
     }
 }
 
+pub trait DeprecationExtension {
+    /// Returns the deprecation reason when the node is marked with `@deprecated`, falling back to
+    /// the default reason of the directive when none is given.
+    fn deprecation_reason(&self) -> Option<&str>;
+
+    fn is_deprecated(&self) -> bool {
+        self.deprecation_reason().is_some()
+    }
+}
+
+fn deprecation_reason_from_directives<'a>(directives: &'a [Directive]) -> Option<&'a str> {
+    directives
+        .iter()
+        .find(|directive| directive.name == "deprecated")
+        .map(|directive| {
+            directive
+                .arguments
+                .iter()
+                .find_map(|(name, value)| match (name.as_str(), value) {
+                    ("reason", Value::String(reason)) => Some(reason.as_str()),
+                    _ => None,
+                })
+                .unwrap_or("No longer supported")
+        })
+}
+
+impl DeprecationExtension for schema::Field {
+    fn deprecation_reason(&self) -> Option<&str> {
+        deprecation_reason_from_directives(&self.directives)
+    }
+}
+
+impl DeprecationExtension for InputValue {
+    fn deprecation_reason(&self) -> Option<&str> {
+        deprecation_reason_from_directives(&self.directives)
+    }
+}
+
+impl DeprecationExtension for schema::EnumValue {
+    fn deprecation_reason(&self) -> Option<&str> {
+        deprecation_reason_from_directives(&self.directives)
+    }
+}
+
+pub trait OneOfExtension {
+    /// Returns `true` for the input object types marked with `@oneOf`, where exactly one field
+    /// must be provided, with a non-null value.
+    fn is_one_of(&self) -> bool;
+}
+
+impl OneOfExtension for InputObjectType {
+    fn is_one_of(&self) -> bool {
+        self.directives
+            .iter()
+            .any(|directive| directive.name == "oneOf")
+    }
+}
+
+impl OneOfExtension for TypeDefinition {
+    fn is_one_of(&self) -> bool {
+        match self {
+            TypeDefinition::InputObject(input_object) => input_object.is_one_of(),
+            _ => false,
+        }
+    }
+}
+
 pub trait AbstractTypeDefinitionExtension {
     fn is_implemented_by(&self, other_type: &dyn ImplementingInterfaceExtension) -> bool;
 }
@@ -655,6 +760,18 @@ impl TypeDefinitionExtension for schema::TypeDefinition {
     }
 }
 
+/// The name of the type extended by a type extension.
+pub(crate) fn type_extension_name(type_extension: &schema::TypeExtension) -> &str {
+    match type_extension {
+        schema::TypeExtension::Scalar(scalar) => &scalar.name,
+        schema::TypeExtension::Object(object) => &object.name,
+        schema::TypeExtension::Interface(interface) => &interface.name,
+        schema::TypeExtension::Union(union) => &union.name,
+        schema::TypeExtension::Enum(enum_) => &enum_.name,
+        schema::TypeExtension::InputObject(input_object) => &input_object.name,
+    }
+}
+
 pub trait AstNodeWithName {
     fn node_name(&self) -> Option<&str>;
 }
@@ -0,0 +1,472 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    arguments_equal, collect_fragments_in_use, directives_equal, OperationDefinitionExtension,
+    OperationTransformer, Transformed, TransformedValue, TypeAware, TypeAwareTransformer,
+    TypeDefinitionExtension,
+};
+use crate::static_graphql::{
+    query::{
+        Definition, Document, Field, FragmentDefinition, FragmentSpread, Selection, SelectionSet,
+        TypeCondition,
+    },
+    schema,
+};
+
+/// Hoists the selection sets repeated in a corpus of documents into named fragments, and replaces
+/// them with spreads of these fragments. Returns the transformed documents, in the same order.
+///
+/// A selection set is hoisted when it is selected on the same type at least `min_occurrences`
+/// times (across all the documents), and has at least two fields (counting nested fields).
+/// Selection sets are compared structurally: the arguments of fields and directives are compared
+/// with `ValueExtension::compare`.
+///
+/// The fragments are named after their type (`DogFields`, `DogFields2`, ...) without conflicting
+/// with the existing fragments, and each document only gets the fragments it uses.
+pub fn extract_fragments(
+    schema: &schema::Document,
+    documents: &[Document],
+    min_occurrences: usize,
+) -> Vec<Document> {
+    let mut extractor = TypeAware::new(schema, FragmentExtractor::new(documents, min_occurrences));
+
+    for document in documents {
+        extractor.transform_document(document);
+    }
+
+    extractor.inner.collecting = false;
+    let transformed = documents
+        .iter()
+        .map(|document| {
+            extractor
+                .transform_document(document)
+                .replace_or_else(|| document.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let fragments = extractor.fragment_definitions();
+    let mut single_use = SingleUseFragmentsInliner::new(&transformed, &fragments);
+
+    transformed
+        .iter()
+        .map(|document| {
+            let mut document = single_use
+                .transform_document(document)
+                .replace_or_else(|| document.clone());
+            let fragments = single_use.transformed_fragments();
+            append_fragments_in_use(&mut document, &fragments);
+
+            document
+        })
+        .collect()
+}
+
+/// A selection set found in the documents, and the name of its fragment once hoisted.
+struct Subtree {
+    type_name: String,
+    items: Vec<Selection>,
+    span: (graphql_parser::Pos, graphql_parser::Pos),
+    occurrences: usize,
+    fragment_name: Option<String>,
+}
+
+/// Counts the selection sets of the fields (when `collecting`), then replaces the selection sets
+/// found often enough with fragment spreads.
+struct FragmentExtractor {
+    min_occurrences: usize,
+    collecting: bool,
+    subtrees: Vec<Subtree>,
+    fragment_names: HashSet<String>,
+}
+
+impl FragmentExtractor {
+    fn new(documents: &[Document], min_occurrences: usize) -> Self {
+        FragmentExtractor {
+            min_occurrences,
+            collecting: true,
+            subtrees: vec![],
+            fragment_names: documents
+                .iter()
+                .flat_map(|document| &document.definitions)
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some(fragment.name.clone()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    fn find_subtree(&self, type_name: &str, items: &[Selection]) -> Option<usize> {
+        self.subtrees.iter().position(|subtree| {
+            subtree.type_name == type_name && selections_equal(&subtree.items, items)
+        })
+    }
+
+    /// Returns the fragment name of a hoisted subtree, choosing it on first use.
+    fn fragment_name(&mut self, index: usize) -> String {
+        if let Some(name) = &self.subtrees[index].fragment_name {
+            return name.clone();
+        }
+
+        let base_name = format!("{}Fields", self.subtrees[index].type_name);
+        let mut name = base_name.clone();
+        let mut suffix = 1;
+
+        while self.fragment_names.contains(&name) {
+            suffix += 1;
+            name = format!("{}{}", base_name, suffix);
+        }
+
+        self.fragment_names.insert(name.clone());
+        self.subtrees[index].fragment_name = Some(name.clone());
+
+        name
+    }
+}
+
+impl<'a> TypeAware<'a, FragmentExtractor> {
+    /// Builds the definitions of the hoisted fragments. The selections of a fragment can hoist new
+    /// fragments, which are built too.
+    fn fragment_definitions(&mut self) -> Vec<FragmentDefinition> {
+        let mut definitions: Vec<FragmentDefinition> = vec![];
+
+        while let Some(index) = self.inner.subtrees.iter().position(|subtree| {
+            subtree.fragment_name.as_ref().is_some_and(|name| {
+                !definitions
+                    .iter()
+                    .any(|definition| &definition.name == name)
+            })
+        }) {
+            let type_name = self.inner.subtrees[index].type_name.clone();
+            let selection_set = SelectionSet {
+                span: self.inner.subtrees[index].span,
+                items: self.inner.subtrees[index].items.clone(),
+            };
+
+            let items = self.with_type(&type_name, |extractor| {
+                extractor
+                    .transform_selection_set(&selection_set)
+                    .replace_or_else(|| selection_set.items.clone())
+            });
+
+            definitions.push(FragmentDefinition {
+                position: selection_set.span.0,
+                name: self.inner.fragment_name(index),
+                type_condition: TypeCondition::On(type_name),
+                directives: vec![],
+                selection_set: SelectionSet {
+                    span: selection_set.span,
+                    items,
+                },
+            });
+        }
+
+        definitions
+    }
+}
+
+impl<'a> TypeAwareTransformer<'a> for FragmentExtractor {
+    fn transform_field(
+        transformer: &mut TypeAware<'a, Self>,
+        field: &Field,
+    ) -> Transformed<Selection> {
+        if let Some(field_type) = transformer.current_type() {
+            let extractor = &mut transformer.inner;
+            let type_name = field_type.name().to_string();
+            let items = &field.selection_set.items;
+
+            if extractor.collecting && selections_size(items) >= 2 {
+                match extractor.find_subtree(&type_name, items) {
+                    Some(index) => extractor.subtrees[index].occurrences += 1,
+                    None => extractor.subtrees.push(Subtree {
+                        type_name,
+                        items: items.clone(),
+                        span: field.selection_set.span,
+                        occurrences: 1,
+                        fragment_name: None,
+                    }),
+                }
+            } else if !extractor.collecting {
+                if let Some(index) = extractor.find_subtree(&type_name, items).filter(|index| {
+                    extractor.subtrees[*index].occurrences >= extractor.min_occurrences
+                }) {
+                    return Transformed::Replace(Selection::Field(Field {
+                        selection_set: SelectionSet {
+                            span: field.selection_set.span,
+                            items: vec![Selection::FragmentSpread(FragmentSpread {
+                                position: field.selection_set.span.0,
+                                fragment_name: extractor.fragment_name(index),
+                                directives: vec![],
+                            })],
+                        },
+                        ..field.clone()
+                    }));
+                }
+            }
+        }
+
+        transformer.default_transform_field(field)
+    }
+}
+
+/// Inlines back the hoisted fragments that end up spread only once (when a repeated selection set
+/// is only repeated inside another hoisted selection set).
+struct SingleUseFragmentsInliner {
+    single_use: HashMap<String, Vec<Selection>>,
+    fragments: Vec<FragmentDefinition>,
+}
+
+impl SingleUseFragmentsInliner {
+    fn new(documents: &[Document], fragments: &[FragmentDefinition]) -> Self {
+        let mut spreads: HashMap<String, usize> = HashMap::new();
+        let mut count_spreads = |selection_set: &SelectionSet| {
+            let mut fragments_in_use = vec![];
+            count_fragment_spreads(selection_set, &mut fragments_in_use);
+
+            for name in fragments_in_use {
+                *spreads.entry(name.to_string()).or_insert(0) += 1;
+            }
+        };
+
+        for document in documents {
+            for definition in &document.definitions {
+                match definition {
+                    Definition::Operation(operation) => count_spreads(operation.selection_set()),
+                    Definition::Fragment(fragment) => count_spreads(&fragment.selection_set),
+                }
+            }
+        }
+
+        for fragment in fragments {
+            count_spreads(&fragment.selection_set);
+        }
+
+        let single_use = fragments
+            .iter()
+            .filter(|fragment| spreads.get(&fragment.name) == Some(&1))
+            .map(|fragment| (fragment.name.clone(), fragment.selection_set.items.clone()))
+            .collect::<HashMap<_, _>>();
+
+        SingleUseFragmentsInliner {
+            fragments: fragments
+                .iter()
+                .filter(|fragment| !single_use.contains_key(&fragment.name))
+                .cloned()
+                .collect(),
+            single_use,
+        }
+    }
+
+    fn transformed_fragments(&mut self) -> Vec<FragmentDefinition> {
+        self.fragments
+            .clone()
+            .iter()
+            .map(|fragment| match self.transform_fragment(fragment) {
+                Transformed::Keep => fragment.clone(),
+                Transformed::Replace(fragment) => fragment,
+            })
+            .collect()
+    }
+}
+
+impl OperationTransformer<'static, String> for SingleUseFragmentsInliner {
+    fn transform_selection_set(
+        &mut self,
+        selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        if let [Selection::FragmentSpread(spread)] = selections.items.as_slice() {
+            if let Some(items) = self.single_use.get(&spread.fragment_name).cloned() {
+                let selection_set = SelectionSet {
+                    span: selections.span,
+                    items,
+                };
+
+                return TransformedValue::Replace(
+                    self.transform_selection_set(&selection_set)
+                        .replace_or_else(|| selection_set.items),
+                );
+            }
+        }
+
+        self.transform_list(&selections.items, Self::transform_selection)
+    }
+}
+
+fn count_fragment_spreads<'a>(selection_set: &'a SelectionSet, spreads: &mut Vec<&'a str>) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => count_fragment_spreads(&field.selection_set, spreads),
+            Selection::InlineFragment(fragment) => {
+                count_fragment_spreads(&fragment.selection_set, spreads)
+            }
+            Selection::FragmentSpread(spread) => spreads.push(spread.fragment_name.as_str()),
+        }
+    }
+}
+
+/// Appends to a document the fragments (among `fragments`) that it uses, directly or through
+/// other fragments.
+fn append_fragments_in_use(document: &mut Document, fragments: &[FragmentDefinition]) {
+    let mut known_fragments: HashMap<&str, &FragmentDefinition> = fragments
+        .iter()
+        .map(|fragment| (fragment.name.as_str(), fragment))
+        .collect();
+    for definition in &document.definitions {
+        if let Definition::Fragment(fragment) = definition {
+            known_fragments.insert(fragment.name.as_str(), fragment);
+        }
+    }
+
+    let mut fragments_in_use: Vec<&str> = vec![];
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            collect_fragments_in_use(
+                operation.selection_set(),
+                &known_fragments,
+                &mut fragments_in_use,
+            );
+        }
+    }
+
+    let used = fragments
+        .iter()
+        .filter(|fragment| fragments_in_use.contains(&fragment.name.as_str()))
+        .cloned()
+        .map(Definition::Fragment)
+        .collect::<Vec<_>>();
+    document.definitions.extend(used);
+}
+
+/// The number of fields in a list of selections, counting nested fields.
+fn selections_size(items: &[Selection]) -> usize {
+    items
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field(field) => 1 + selections_size(&field.selection_set.items),
+            Selection::InlineFragment(fragment) => selections_size(&fragment.selection_set.items),
+            Selection::FragmentSpread(_) => 1,
+        })
+        .sum()
+}
+
+/// Compares selections structurally, ignoring positions.
+fn selections_equal(a: &[Selection], b: &[Selection]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|pair| match pair {
+            (Selection::Field(a), Selection::Field(b)) => {
+                a.name == b.name
+                    && a.alias == b.alias
+                    && arguments_equal(&a.arguments, &b.arguments)
+                    && directives_equal(&a.directives, &b.directives)
+                    && selections_equal(&a.selection_set.items, &b.selection_set.items)
+            }
+            (Selection::InlineFragment(a), Selection::InlineFragment(b)) => {
+                a.type_condition == b.type_condition
+                    && directives_equal(&a.directives, &b.directives)
+                    && selections_equal(&a.selection_set.items, &b.selection_set.items)
+            }
+            (Selection::FragmentSpread(a), Selection::FragmentSpread(b)) => {
+                a.fragment_name == b.fragment_name && directives_equal(&a.directives, &b.directives)
+            }
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(documents: &[&str]) -> Vec<String> {
+        use crate::validation::test_utils::TEST_SCHEMA;
+
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let documents = documents
+            .iter()
+            .map(|document| {
+                graphql_parser::parse_query(document)
+                    .expect("Failed to parse query")
+                    .into_static()
+            })
+            .collect::<Vec<_>>();
+
+        extract_fragments(&schema, &documents, 2)
+            .iter()
+            .map(|document| document.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn extract_repeated_selection_sets() {
+        let documents = extract(&["
+{
+  dog {
+    name
+    mother {
+      name
+      barkVolume
+    }
+  }
+  pet {
+    ... on Dog {
+      father {
+        barkVolume
+        name
+      }
+    }
+  }
+  human {
+    pets {
+      name
+    }
+  }
+}
+
+fragment DogFields on Dog {
+  mother {
+    name
+    mother {
+      name
+      barkVolume
+    }
+  }
+}
+"]);
+
+        assert_eq!(
+            documents[0],
+            "{\n  dog {\n    ...DogFields2\n  }\n  pet {\n    ... on Dog {\n      father {\n        barkVolume\n        name\n      }\n    }\n  }\n  human {\n    pets {\n      name\n    }\n  }\n}\n\nfragment DogFields on Dog {\n  mother {\n    ...DogFields2\n  }\n}\n\nfragment DogFields2 on Dog {\n  name\n  mother {\n    name\n    barkVolume\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn extract_across_documents() {
+        let documents = extract(&[
+            "query A { dog { name nickname } }",
+            "query B { dog { name nickname } cat { name } }",
+            "query C { cat { name nickname } }",
+        ]);
+
+        assert_eq!(
+            documents,
+            vec![
+                "query A {\n  dog {\n    ...DogFields\n  }\n}\n\nfragment DogFields on Dog {\n  name\n  nickname\n}\n",
+                "query B {\n  dog {\n    ...DogFields\n  }\n  cat {\n    name\n  }\n}\n\nfragment DogFields on Dog {\n  name\n  nickname\n}\n",
+                "query C {\n  cat {\n    name\n    nickname\n  }\n}\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn inline_back_fragments_used_once() {
+        let documents = extract(&[
+            "{ a: dog { name mother { name nickname } } b: dog { name mother { name nickname } } }",
+        ]);
+
+        assert_eq!(
+            documents[0],
+            "{\n  a: dog {\n    ...DogFields\n  }\n  b: dog {\n    ...DogFields\n  }\n}\n\nfragment DogFields on Dog {\n  name\n  mother {\n    name\n    nickname\n  }\n}\n"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use super::{AstNodeWithName, OperationDefinitionExtension, ValueExtension};
+use crate::static_graphql::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet,
+};
+
+/// Returns a standalone document with the operation with the given name (or the only operation of
+/// the document, when `operation_name` is `None`), and the fragments it uses, directly or through
+/// other fragments. The definitions keep their order.
+///
+/// Returns `None` when no such operation exists, or when `operation_name` is `None` and the
+/// document does not have exactly one operation.
+pub fn extract_operation(document: &Document, operation_name: Option<&str>) -> Option<Document> {
+    let mut operations = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        });
+
+    let operation = match operation_name {
+        Some(name) => operations.find(|operation| operation.node_name() == Some(name))?,
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => operation,
+            _ => return None,
+        },
+    };
+
+    Some(operation_document(document, operation))
+}
+
+/// Returns a standalone document for each operation of the document, in order, with the fragments
+/// it uses (see `extract_operation`).
+pub fn extract_operations(document: &Document) -> Vec<Document> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation_document(document, operation)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn operation_document(document: &Document, operation: &OperationDefinition) -> Document {
+    let known_fragments: HashMap<&str, &FragmentDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    let mut fragments_in_use: Vec<&str> = vec![];
+    collect_fragments_in_use(
+        operation.selection_set(),
+        &known_fragments,
+        &mut fragments_in_use,
+    );
+
+    Document {
+        definitions: document
+            .definitions
+            .iter()
+            .filter(|definition| match definition {
+                Definition::Operation(other) => std::ptr::eq(other, operation),
+                Definition::Fragment(fragment) => {
+                    fragments_in_use.contains(&fragment.name.as_str())
+                }
+            })
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Collects the names of the fragments spread in a selection set, following the spreads into the
+/// known fragments. Fragments already in `fragments_in_use` are not followed again, so cycles are
+/// not followed.
+pub fn collect_fragments_in_use<'a>(
+    selection_set: &'a SelectionSet,
+    known_fragments: &HashMap<&str, &'a FragmentDefinition>,
+    fragments_in_use: &mut Vec<&'a str>,
+) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                collect_fragments_in_use(&field.selection_set, known_fragments, fragments_in_use)
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_fragments_in_use(&fragment.selection_set, known_fragments, fragments_in_use)
+            }
+            Selection::FragmentSpread(spread) => {
+                if fragments_in_use.contains(&spread.fragment_name.as_str()) {
+                    continue;
+                }
+
+                if let Some(fragment) = known_fragments.get(spread.fragment_name.as_str()) {
+                    fragments_in_use.push(fragment.name.as_str());
+                    collect_fragments_in_use(
+                        &fragment.selection_set,
+                        known_fragments,
+                        fragments_in_use,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Collects the names of the variables used in a selection set, by the arguments of its fields and
+/// by its directives, following the fragment spreads into the known fragments (each fragment is
+/// only followed once).
+pub fn collect_variables_in_use<'a>(
+    selection_set: &'a SelectionSet,
+    known_fragments: &HashMap<&str, &'a FragmentDefinition>,
+    variables_in_use: &mut Vec<&'a str>,
+) {
+    collect_variables_in_use_inner(
+        selection_set,
+        known_fragments,
+        variables_in_use,
+        &mut vec![],
+    );
+}
+
+/// Collects the names of the variables used in the arguments of directives.
+pub fn collect_directives_variables_in_use<'a>(
+    directives: &'a [Directive],
+    variables_in_use: &mut Vec<&'a str>,
+) {
+    for directive in directives {
+        for (_, value) in &directive.arguments {
+            for variable in value.variables_in_use() {
+                if !variables_in_use.contains(&variable) {
+                    variables_in_use.push(variable);
+                }
+            }
+        }
+    }
+}
+
+fn collect_variables_in_use_inner<'a>(
+    selection_set: &'a SelectionSet,
+    known_fragments: &HashMap<&str, &'a FragmentDefinition>,
+    variables_in_use: &mut Vec<&'a str>,
+    visited_fragments: &mut Vec<&'a str>,
+) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                for (_, value) in &field.arguments {
+                    for variable in value.variables_in_use() {
+                        if !variables_in_use.contains(&variable) {
+                            variables_in_use.push(variable);
+                        }
+                    }
+                }
+
+                collect_directives_variables_in_use(&field.directives, variables_in_use);
+                collect_variables_in_use_inner(
+                    &field.selection_set,
+                    known_fragments,
+                    variables_in_use,
+                    visited_fragments,
+                );
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_directives_variables_in_use(&fragment.directives, variables_in_use);
+                collect_variables_in_use_inner(
+                    &fragment.selection_set,
+                    known_fragments,
+                    variables_in_use,
+                    visited_fragments,
+                );
+            }
+            Selection::FragmentSpread(spread) => {
+                collect_directives_variables_in_use(&spread.directives, variables_in_use);
+
+                if visited_fragments.contains(&spread.fragment_name.as_str()) {
+                    continue;
+                }
+
+                if let Some(fragment) = known_fragments.get(spread.fragment_name.as_str()) {
+                    visited_fragments.push(fragment.name.as_str());
+                    collect_directives_variables_in_use(&fragment.directives, variables_in_use);
+                    collect_variables_in_use_inner(
+                        &fragment.selection_set,
+                        known_fragments,
+                        variables_in_use,
+                        visited_fragments,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static DOCUMENT: &str = "
+query GetDog {
+  dog {
+    ...DogFields
+  }
+}
+
+fragment OwnerFields on Human {
+  name
+  ...OwnerFields
+}
+
+fragment DogFields on Dog {
+  name
+  ... on Dog {
+    owner {
+      ...OwnerFields
+    }
+  }
+}
+
+fragment CatFields on Cat {
+  name
+}
+
+mutation UpdateDog {
+  updateDog {
+    name
+  }
+}
+
+{
+  cat {
+    ...CatFields
+  }
+}
+";
+
+    fn parse(document: &str) -> Document {
+        graphql_parser::parse_query(document)
+            .expect("Failed to parse query")
+            .into_static()
+    }
+
+    #[test]
+    fn extract_operation_with_its_fragments() {
+        let document = parse(DOCUMENT);
+
+        assert_eq!(
+            extract_operation(&document, Some("GetDog"))
+                .unwrap()
+                .to_string(),
+            "query GetDog {\n  dog {\n    ...DogFields\n  }\n}\n\nfragment OwnerFields on Human {\n  name\n  ...OwnerFields\n}\n\nfragment DogFields on Dog {\n  name\n  ... on Dog {\n    owner {\n      ...OwnerFields\n    }\n  }\n}\n"
+        );
+        assert_eq!(
+            extract_operation(&document, Some("UpdateDog"))
+                .unwrap()
+                .to_string(),
+            "mutation UpdateDog {\n  updateDog {\n    name\n  }\n}\n"
+        );
+        assert!(extract_operation(&document, Some("Unknown")).is_none());
+        assert!(extract_operation(&document, None).is_none());
+        assert!(extract_operation(&parse("{ dog { name } }"), None).is_some());
+    }
+
+    #[test]
+    fn extract_all_operations() {
+        let documents = extract_operations(&parse(DOCUMENT));
+
+        assert_eq!(documents.len(), 3);
+        assert_eq!(
+            documents[2].to_string(),
+            "fragment CatFields on Cat {\n  name\n}\n\n{\n  cat {\n    ...CatFields\n  }\n}\n"
+        );
+    }
+}
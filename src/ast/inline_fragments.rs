@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{
+    OperationTransformer, Transformed, TransformedValue, TypeAware, TypeAwareTransformer,
+    TypeDefinitionExtension,
+};
+use crate::static_graphql::{
+    query::{
+        Definition, Document, FragmentDefinition, FragmentSpread, InlineFragment, Selection,
+        SelectionSet, TypeCondition,
+    },
+    schema,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InlineFragmentsError {
+    /// A spread fragment is not defined in the document.
+    UnknownFragment(String),
+    /// A fragment spreads itself, directly or through other fragments. The cycle is listed from
+    /// the first fragment spread in it.
+    FragmentCycle(Vec<String>),
+}
+
+impl fmt::Display for InlineFragmentsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InlineFragmentsError::UnknownFragment(name) => {
+                write!(f, "Unknown fragment \"{}\".", name)
+            }
+            InlineFragmentsError::FragmentCycle(names) => {
+                write!(f, "Cannot spread fragment \"{}\" within itself", names[0])?;
+
+                if names.len() > 1 {
+                    write!(
+                        f,
+                        " via {}",
+                        names[1..]
+                            .iter()
+                            .map(|name| format!("\"{}\"", name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?;
+                }
+
+                write!(f, ".")
+            }
+        }
+    }
+}
+
+/// Replaces the fragment spreads of a document with the equivalent inline fragments, and removes
+/// the fragment definitions (see `FragmentInliner`).
+pub fn inline_fragments(
+    schema: &schema::Document,
+    document: &Document,
+) -> Result<Document, InlineFragmentsError> {
+    let mut inliner = TypeAware::new(schema, FragmentInliner::new(document));
+    let inlined = inliner
+        .transform_document(document)
+        .replace_or_else(|| document.clone());
+
+    match inliner.inner.error {
+        Some(error) => Err(error),
+        None => Ok(inlined),
+    }
+}
+
+/// A `TypeAwareTransformer` replacing each fragment spread with an inline fragment, with the
+/// selections (inlined too) of the fragment, the type condition of the fragment and the directives
+/// of the spread. The type condition is dropped when it is the type of the parent selection set.
+/// The fragment definitions are removed from the transformed document.
+///
+/// Spreads of unknown fragments, and spreads creating a cycle, are kept as is, and the first of
+/// these errors is available with `error()`.
+pub struct FragmentInliner<'a> {
+    known_fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    fragment_stack: Vec<&'a str>,
+    error: Option<InlineFragmentsError>,
+}
+
+impl<'a> FragmentInliner<'a> {
+    pub fn new(document: &'a Document) -> Self {
+        FragmentInliner {
+            known_fragments: document
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                    _ => None,
+                })
+                .collect(),
+            fragment_stack: vec![],
+            error: None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&InlineFragmentsError> {
+        self.error.as_ref()
+    }
+
+    fn report_error(&mut self, error: InlineFragmentsError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<'a, 's> TypeAwareTransformer<'s> for FragmentInliner<'a> {
+    fn transform_document(
+        transformer: &mut TypeAware<'s, Self>,
+        document: &Document,
+    ) -> TransformedValue<Document> {
+        let mut definitions = Vec::with_capacity(document.definitions.len());
+
+        for definition in &document.definitions {
+            if let Definition::Operation(operation) = definition {
+                definitions.push(match transformer.transform_operation(operation) {
+                    Transformed::Keep => definition.clone(),
+                    Transformed::Replace(operation) => Definition::Operation(operation),
+                });
+            }
+        }
+
+        TransformedValue::Replace(Document { definitions })
+    }
+
+    fn transform_fragment_spread(
+        transformer: &mut TypeAware<'s, Self>,
+        spread: &FragmentSpread,
+    ) -> Transformed<Selection> {
+        let inliner = &mut transformer.inner;
+        let fragment = match inliner.known_fragments.get(spread.fragment_name.as_str()) {
+            Some(fragment) => *fragment,
+            None => {
+                inliner.report_error(InlineFragmentsError::UnknownFragment(
+                    spread.fragment_name.clone(),
+                ));
+
+                return Transformed::Keep;
+            }
+        };
+
+        if let Some(index) = inliner
+            .fragment_stack
+            .iter()
+            .position(|name| *name == fragment.name)
+        {
+            let cycle = inliner.fragment_stack[index..]
+                .iter()
+                .map(|name| name.to_string())
+                .collect();
+            inliner.report_error(InlineFragmentsError::FragmentCycle(cycle));
+
+            return Transformed::Keep;
+        }
+
+        let TypeCondition::On(type_name) = &fragment.type_condition;
+        let type_condition = match transformer.current_type() {
+            Some(parent_type) if parent_type.name() == type_name => None,
+            _ => Some(fragment.type_condition.clone()),
+        };
+
+        transformer
+            .inner
+            .fragment_stack
+            .push(fragment.name.as_str());
+        let items = transformer.with_type(type_name, |transformer| {
+            transformer
+                .transform_selection_set(&fragment.selection_set)
+                .replace_or_else(|| fragment.selection_set.items.clone())
+        });
+        transformer.inner.fragment_stack.pop();
+
+        Transformed::Replace(Selection::InlineFragment(InlineFragment {
+            position: spread.position,
+            type_condition,
+            directives: transformer
+                .transform_directives(&spread.directives)
+                .replace_or_else(|| spread.directives.clone()),
+            selection_set: SelectionSet {
+                span: fragment.selection_set.span,
+                items,
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inline(operation: &str) -> Result<Document, InlineFragmentsError> {
+        use crate::validation::test_utils::TEST_SCHEMA;
+
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let document = graphql_parser::parse_query(operation)
+            .expect("Failed to parse query")
+            .into_static();
+
+        inline_fragments(&schema, &document)
+    }
+
+    #[test]
+    fn inline_nested_fragment_spreads() {
+        let inlined = inline(
+            "
+query Dog($withOwner: Boolean!) {
+  dog {
+    ...DogFields
+  }
+  pet {
+    ...DogFields @include(if: $withOwner)
+  }
+}
+
+fragment DogFields on Dog {
+  name
+  mother {
+    ...DogName
+  }
+}
+
+fragment DogName on Dog {
+  name
+}
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            inlined.to_string(),
+            "query Dog($withOwner: Boolean!) {\n  dog {\n    ... {\n      name\n      mother {\n        ... {\n          name\n        }\n      }\n    }\n  }\n  pet {\n    ... on Dog @include(if: $withOwner) {\n      name\n      mother {\n        ... {\n          name\n        }\n      }\n    }\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn reject_unknown_fragments_and_cycles() {
+        assert_eq!(
+            inline("{ dog { ...Unknown } }").unwrap_err(),
+            InlineFragmentsError::UnknownFragment("Unknown".to_string())
+        );
+
+        let error = inline(
+            "
+{ dog { ...A } }
+fragment A on Dog { mother { ...B } }
+fragment B on Dog { father { ...A } }
+",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            InlineFragmentsError::FragmentCycle(vec!["A".to_string(), "B".to_string()])
+        );
+        assert_eq!(
+            error.to_string(),
+            "Cannot spread fragment \"A\" within itself via \"B\"."
+        );
+    }
+}
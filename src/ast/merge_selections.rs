@@ -0,0 +1,215 @@
+use super::{
+    arguments_equal, directives_equal, OperationTransformer, Transformed, TransformedValue,
+    TypeAware, TypeAwareTransformer, TypeDefinitionExtension,
+};
+use crate::static_graphql::{
+    query::{Document, InlineFragment, Selection, SelectionSet, TypeCondition},
+    schema::{self, TypeDefinition},
+};
+
+/// Merges the redundant selections of a document (see `SelectionMerger`).
+pub fn merge_selections(schema: &schema::Document, document: &Document) -> Document {
+    TypeAware::new(schema, SelectionMerger)
+        .transform_document(document)
+        .replace_or_else(|| document.clone())
+}
+
+/// A `TypeAwareTransformer` removing redundant selections, without changing the result of the
+/// operations:
+///
+/// - inline fragments without directives, and without type condition or with the type of the
+///   parent selection set as type condition, are replaced with their selections,
+/// - fields with the same response key, name, arguments and directives are merged into the first
+///   of them (with the selections of all of them),
+/// - inline fragments with the same type condition and directives are merged into the first of
+///   them, and repeated fragment spreads are removed.
+///
+/// Fields with the same response key that cannot be merged (like `OverlappingFieldsCanBeMerged`
+/// would report them) are kept as is.
+pub struct SelectionMerger;
+
+impl SelectionMerger {
+    /// Appends the selections to `merged`, replacing the inline fragments on the parent type with
+    /// their selections, and merging the selections with the previous ones.
+    fn merge_into(
+        parent_type: Option<&TypeDefinition>,
+        merged: &mut Vec<Selection>,
+        selections: &[Selection],
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    let existing = merged.iter_mut().find_map(|merged| match merged {
+                        Selection::Field(other)
+                            if other.alias == field.alias
+                                && other.name == field.name
+                                && arguments_equal(&other.arguments, &field.arguments)
+                                && directives_equal(&other.directives, &field.directives) =>
+                        {
+                            Some(other)
+                        }
+                        _ => None,
+                    });
+
+                    match existing {
+                        Some(existing) => existing
+                            .selection_set
+                            .items
+                            .extend(field.selection_set.items.iter().cloned()),
+                        None => merged.push(selection.clone()),
+                    }
+                }
+                Selection::InlineFragment(fragment)
+                    if Self::is_redundant(parent_type, fragment) =>
+                {
+                    Self::merge_into(parent_type, merged, &fragment.selection_set.items)
+                }
+                Selection::InlineFragment(fragment) => {
+                    let existing = merged.iter_mut().find_map(|merged| match merged {
+                        Selection::InlineFragment(other)
+                            if other.type_condition == fragment.type_condition
+                                && directives_equal(&other.directives, &fragment.directives) =>
+                        {
+                            Some(other)
+                        }
+                        _ => None,
+                    });
+
+                    match existing {
+                        Some(existing) => existing
+                            .selection_set
+                            .items
+                            .extend(fragment.selection_set.items.iter().cloned()),
+                        None => merged.push(selection.clone()),
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let exists = merged.iter().any(|merged| match merged {
+                        Selection::FragmentSpread(other) => {
+                            other.fragment_name == spread.fragment_name
+                                && directives_equal(&other.directives, &spread.directives)
+                        }
+                        _ => false,
+                    });
+
+                    if !exists {
+                        merged.push(selection.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_redundant(parent_type: Option<&TypeDefinition>, fragment: &InlineFragment) -> bool {
+        if !fragment.directives.is_empty() {
+            return false;
+        }
+
+        match (&fragment.type_condition, parent_type) {
+            (None, _) => true,
+            (Some(TypeCondition::On(type_name)), Some(parent_type)) => {
+                parent_type.name() == type_name
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> TypeAwareTransformer<'a> for SelectionMerger {
+    fn transform_selection_set(
+        transformer: &mut TypeAware<'a, Self>,
+        selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        let mut merged = Vec::with_capacity(selections.items.len());
+        Self::merge_into(transformer.current_type(), &mut merged, &selections.items);
+
+        // The selections are merged first, so the selections of merged fields and fragments are
+        // merged together.
+        let items = merged
+            .iter()
+            .map(
+                |selection| match transformer.transform_selection(selection) {
+                    Transformed::Keep => selection.clone(),
+                    Transformed::Replace(selection) => selection,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        if items == selections.items {
+            TransformedValue::Keep
+        } else {
+            TransformedValue::Replace(items)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(operation: &str) -> String {
+        use crate::validation::test_utils::TEST_SCHEMA;
+
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let document = graphql_parser::parse_query(operation)
+            .expect("Failed to parse query")
+            .into_static();
+
+        merge_selections(&schema, &document).to_string()
+    }
+
+    #[test]
+    fn merge_redundant_selections() {
+        assert_eq!(
+            merge(
+                "
+query ($withName: Boolean!) {
+  dog {
+    name
+    ... on Dog {
+      name
+      mother {
+        name
+      }
+    }
+    mother {
+      barkVolume
+      ... {
+        name
+      }
+    }
+    ... on Dog @include(if: $withName) {
+      nickname
+    }
+    ...DogFields
+    ...DogFields
+  }
+  pet {
+    ... on Dog {
+      name
+    }
+    ... on Dog {
+      barkVolume
+    }
+  }
+}
+
+fragment DogFields on Dog {
+  nickname
+  nickname
+}
+"
+            ),
+            "query($withName: Boolean!) {\n  dog {\n    name\n    mother {\n      name\n      barkVolume\n    }\n    ... on Dog @include(if: $withName) {\n      nickname\n    }\n    ...DogFields\n  }\n  pet {\n    ... on Dog {\n      name\n      barkVolume\n    }\n  }\n}\n\nfragment DogFields on Dog {\n  nickname\n}\n"
+        );
+    }
+
+    #[test]
+    fn keep_fields_that_cannot_be_merged() {
+        let operation = "{\n  dog {\n    doesKnowCommand(dogCommand: SIT)\n    doesKnowCommand(dogCommand: HEEL)\n    name: nickname\n    name\n  }\n}\n";
+
+        assert_eq!(merge(operation), operation);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::static_graphql::schema::{
+    self, Definition, Document, EnumType, InputObjectType, InterfaceType, ObjectType, ScalarType,
+    TypeDefinition, UnionType,
+};
+
+use super::{type_extension_name, TypeDefinitionExtension};
+
+/// Merges the type extensions of a schema document (`extend type ...`) into the type definitions
+/// they extend, and returns the resulting document.
+///
+/// The fields, values, members, interfaces and directives of an extension are appended to the
+/// extended type definition. An extension of a type that is not defined in the document (or that
+/// is defined with another kind) becomes a type definition, in place of the extension.
+pub fn merge_type_extensions(document: &Document) -> Document {
+    let mut definitions: Vec<Definition> = Vec::with_capacity(document.definitions.len());
+
+    for definition in &document.definitions {
+        if let Definition::TypeExtension(_) = definition {
+            continue;
+        }
+
+        definitions.push(definition.clone());
+    }
+
+    for definition in &document.definitions {
+        if let Definition::TypeExtension(extension) = definition {
+            let extended = definitions
+                .iter_mut()
+                .find_map(|definition| match definition {
+                    Definition::TypeDefinition(type_definition)
+                        if type_definition.name() == type_extension_name(extension) =>
+                    {
+                        Some(type_definition)
+                    }
+                    _ => None,
+                });
+
+            let merged = match extended {
+                Some(type_definition) => merge_type_extension(type_definition, extension),
+                None => false,
+            };
+
+            if !merged {
+                definitions.push(Definition::TypeDefinition(type_extension_to_definition(
+                    extension.clone(),
+                )));
+            }
+        }
+    }
+
+    Document { definitions }
+}
+
+/// Merges the extension into the type definition, and returns `false` when their kinds do not
+/// match.
+fn merge_type_extension(
+    type_definition: &mut TypeDefinition,
+    extension: &schema::TypeExtension,
+) -> bool {
+    match (type_definition, extension) {
+        (TypeDefinition::Scalar(scalar), schema::TypeExtension::Scalar(extension)) => {
+            scalar.directives.extend(extension.directives.clone());
+        }
+        (TypeDefinition::Object(object), schema::TypeExtension::Object(extension)) => {
+            object
+                .implements_interfaces
+                .extend(extension.implements_interfaces.clone());
+            object.directives.extend(extension.directives.clone());
+            object.fields.extend(extension.fields.clone());
+        }
+        (TypeDefinition::Interface(interface), schema::TypeExtension::Interface(extension)) => {
+            interface
+                .implements_interfaces
+                .extend(extension.implements_interfaces.clone());
+            interface.directives.extend(extension.directives.clone());
+            interface.fields.extend(extension.fields.clone());
+        }
+        (TypeDefinition::Union(union), schema::TypeExtension::Union(extension)) => {
+            union.directives.extend(extension.directives.clone());
+            union.types.extend(extension.types.clone());
+        }
+        (TypeDefinition::Enum(enum_type), schema::TypeExtension::Enum(extension)) => {
+            enum_type.directives.extend(extension.directives.clone());
+            enum_type.values.extend(extension.values.clone());
+        }
+        (TypeDefinition::InputObject(input), schema::TypeExtension::InputObject(extension)) => {
+            input.directives.extend(extension.directives.clone());
+            input.fields.extend(extension.fields.clone());
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+fn type_extension_to_definition(extension: schema::TypeExtension) -> TypeDefinition {
+    match extension {
+        schema::TypeExtension::Scalar(extension) => TypeDefinition::Scalar(ScalarType {
+            position: extension.position,
+            description: None,
+            name: extension.name,
+            directives: extension.directives,
+        }),
+        schema::TypeExtension::Object(extension) => TypeDefinition::Object(ObjectType {
+            position: extension.position,
+            description: None,
+            name: extension.name,
+            implements_interfaces: extension.implements_interfaces,
+            directives: extension.directives,
+            fields: extension.fields,
+        }),
+        schema::TypeExtension::Interface(extension) => TypeDefinition::Interface(InterfaceType {
+            position: extension.position,
+            description: None,
+            name: extension.name,
+            implements_interfaces: extension.implements_interfaces,
+            directives: extension.directives,
+            fields: extension.fields,
+        }),
+        schema::TypeExtension::Union(extension) => TypeDefinition::Union(UnionType {
+            position: extension.position,
+            description: None,
+            name: extension.name,
+            directives: extension.directives,
+            types: extension.types,
+        }),
+        schema::TypeExtension::Enum(extension) => TypeDefinition::Enum(EnumType {
+            position: extension.position,
+            description: None,
+            name: extension.name,
+            directives: extension.directives,
+            values: extension.values,
+        }),
+        schema::TypeExtension::InputObject(extension) => {
+            TypeDefinition::InputObject(InputObjectType {
+                position: extension.position,
+                description: None,
+                name: extension.name,
+                directives: extension.directives,
+                fields: extension.fields,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{FieldByNameExtension, SchemaDocumentExtension};
+
+    #[test]
+    fn merge_extensions_into_definitions() {
+        let document = graphql_parser::parse_schema(
+            "
+extend type Query {
+  products: [Product]
+}
+
+type Query {
+  me: String
+}
+
+enum Category {
+  BOOKS
+}
+
+extend enum Category {
+  MOVIES
+}
+
+extend type Product @key(fields: \"id\") {
+  id: ID!
+}
+",
+        )
+        .expect("Failed to parse schema")
+        .into_static();
+
+        let merged = merge_type_extensions(&document);
+
+        assert_eq!(merged.definitions.len(), 3);
+
+        let query = merged.type_by_name("Query").unwrap();
+        assert!(query.field_by_name(&"me".to_string()).is_some());
+        assert!(query.field_by_name(&"products".to_string()).is_some());
+
+        match merged.type_by_name("Category") {
+            Some(TypeDefinition::Enum(category)) => assert_eq!(category.values.len(), 2),
+            _ => panic!("Category should be an enum"),
+        }
+
+        let product = merged.object_type_by_name("Product").unwrap();
+        assert_eq!(product.directives[0].name, "key");
+        assert_eq!(product.fields.len(), 1);
+    }
+}
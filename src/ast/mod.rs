@@ -1,12 +1,36 @@
+pub mod builtins;
 pub mod collect_fields;
+pub mod completions;
 pub mod ext;
+pub mod extract_fragments;
+pub mod extract_operation;
+pub mod inline_fragments;
+pub mod merge_selections;
+pub mod merge_type_extensions;
 pub mod operation_transformer;
 pub mod operation_visitor;
+pub mod schema_coordinates;
+pub mod schema_transformer;
 /// Utilities visiting GraphQL AST trees
 pub mod schema_visitor;
+pub mod skip_include;
+pub mod type_aware_transformer;
+pub mod visitor_control;
 
+pub use self::builtins::*;
 pub use self::collect_fields::*;
+pub use self::completions::*;
 pub use self::ext::*;
+pub use self::extract_fragments::*;
+pub use self::extract_operation::*;
+pub use self::inline_fragments::*;
+pub use self::merge_selections::*;
+pub use self::merge_type_extensions::*;
 pub use self::operation_transformer::*;
 pub use self::operation_visitor::*;
+pub use self::schema_coordinates::*;
+pub use self::schema_transformer::*;
 pub use self::schema_visitor::*;
+pub use self::skip_include::*;
+pub use self::type_aware_transformer::*;
+pub use self::visitor_control::*;
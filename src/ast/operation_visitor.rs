@@ -9,6 +9,7 @@ use crate::static_graphql::{
 
 use super::{
     FieldByNameExtension, OperationDefinitionExtension, SchemaDocumentExtension, TypeExtension,
+    VisitorControl,
 };
 /// OperationVisitor
 pub struct OperationVisitorContext<'a> {
@@ -16,6 +17,10 @@ pub struct OperationVisitorContext<'a> {
     pub operation: &'a query::Document,
     pub known_fragments: HashMap<&'a str, &'a FragmentDefinition>,
     pub directives: HashMap<String, schema::DirectiveDefinition>,
+    /// When enabled, `visit_document` visits the selections of a fragment at each of its spread
+    /// sites (using the type condition of the fragment), and skips the fragment definitions.
+    /// Fragments that are already being expanded are not expanded again, so cycles are not followed.
+    pub follow_fragment_spreads: bool,
 
     type_stack: Vec<Option<&'a schema::TypeDefinition>>,
     parent_type_stack: Vec<Option<&'a schema::TypeDefinition>>,
@@ -23,9 +28,13 @@ pub struct OperationVisitorContext<'a> {
     type_literal_stack: Vec<Option<Type>>,
     input_type_literal_stack: Vec<Option<&'a Type>>,
     field_stack: Vec<Option<&'a schema::Field>>,
+    fragment_stack: Vec<&'a FragmentDefinition>,
 }
 
 impl<'a> OperationVisitorContext<'a> {
+    /// Creates the context to visit an operation. The schema is used as is: the context borrows
+    /// it, so add the definitions of the specification it does not declare beforehand, with
+    /// `with_builtin_definitions`, and keep the augmented schema to create the next contexts.
     pub fn new(operation: &'a Document, schema: &'a schema::Document) -> Self {
         OperationVisitorContext {
             schema,
@@ -36,6 +45,8 @@ impl<'a> OperationVisitorContext<'a> {
             type_literal_stack: vec![],
             input_type_literal_stack: vec![],
             field_stack: vec![],
+            fragment_stack: vec![],
+            follow_fragment_spreads: false,
             known_fragments: HashMap::from_iter(operation.definitions.iter().filter_map(|def| {
                 match def {
                     Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
@@ -53,9 +64,9 @@ impl<'a> OperationVisitorContext<'a> {
         }
     }
 
-    pub fn with_type<Func>(&mut self, t: Option<&Type>, func: Func)
+    pub fn with_type<R, Func>(&mut self, t: Option<&Type>, func: Func) -> R
     where
-        Func: FnOnce(&mut OperationVisitorContext<'a>) -> (),
+        Func: FnOnce(&mut OperationVisitorContext<'a>) -> R,
     {
         if let Some(t) = t {
             self.type_stack
@@ -65,24 +76,28 @@ impl<'a> OperationVisitorContext<'a> {
         }
 
         self.type_literal_stack.push(t.cloned());
-        func(self);
+        let result = func(self);
         self.type_literal_stack.pop();
         self.type_stack.pop();
+
+        result
     }
 
-    pub fn with_parent_type<Func>(&mut self, func: Func)
+    pub fn with_parent_type<R, Func>(&mut self, func: Func) -> R
     where
-        Func: FnOnce(&mut OperationVisitorContext<'a>) -> (),
+        Func: FnOnce(&mut OperationVisitorContext<'a>) -> R,
     {
         self.parent_type_stack
             .push(self.type_stack.last().unwrap_or(&None).clone());
-        func(self);
+        let result = func(self);
         self.parent_type_stack.pop();
+
+        result
     }
 
-    pub fn with_field<'f, Func>(&mut self, f: Option<&'f schema::Field>, func: Func)
+    pub fn with_field<'f, R, Func>(&mut self, f: Option<&'f schema::Field>, func: Func) -> R
     where
-        Func: FnOnce(&mut OperationVisitorContext<'a>) -> (),
+        Func: FnOnce(&mut OperationVisitorContext<'a>) -> R,
         'f: 'a,
     {
         if let Some(f) = f {
@@ -91,13 +106,26 @@ impl<'a> OperationVisitorContext<'a> {
             self.field_stack.push(None);
         }
 
-        func(self);
+        let result = func(self);
         self.field_stack.pop();
+
+        result
+    }
+
+    pub fn with_fragment<R, Func>(&mut self, fragment: &'a FragmentDefinition, func: Func) -> R
+    where
+        Func: FnOnce(&mut OperationVisitorContext<'a>) -> R,
+    {
+        self.fragment_stack.push(fragment);
+        let result = func(self);
+        self.fragment_stack.pop();
+
+        result
     }
 
-    pub fn with_input_type<Func>(&mut self, t: Option<&'a Type>, func: Func)
+    pub fn with_input_type<R, Func>(&mut self, t: Option<&'a Type>, func: Func) -> R
     where
-        Func: FnOnce(&mut OperationVisitorContext<'a>) -> (),
+        Func: FnOnce(&mut OperationVisitorContext<'a>) -> R,
     {
         if let Some(ref t) = t {
             self.input_type_stack
@@ -107,9 +135,11 @@ impl<'a> OperationVisitorContext<'a> {
         }
 
         self.input_type_literal_stack.push(t);
-        func(self);
+        let result = func(self);
         self.input_type_literal_stack.pop();
         self.input_type_stack.pop();
+
+        result
     }
 
     pub fn current_type(&self) -> Option<&schema::TypeDefinition> {
@@ -135,6 +165,17 @@ impl<'a> OperationVisitorContext<'a> {
     pub fn current_field(&self) -> Option<&schema::Field> {
         self.field_stack.last().unwrap_or(&None).as_deref()
     }
+
+    /// The fragment whose selections are currently visited through a fragment spread, when
+    /// `follow_fragment_spreads` is enabled.
+    pub fn current_fragment(&self) -> Option<&'a FragmentDefinition> {
+        self.fragment_stack.last().copied()
+    }
+
+    /// All fragments that are currently expanded, from the outermost spread to the innermost one.
+    pub fn fragment_path(&self) -> &[&'a FragmentDefinition] {
+        &self.fragment_stack
+    }
 }
 
 pub fn visit_document<'a, Visitor, UserContext>(
@@ -145,8 +186,18 @@ pub fn visit_document<'a, Visitor, UserContext>(
 ) where
     Visitor: OperationVisitor<'a, UserContext>,
 {
-    visitor.enter_document(context, user_context, document);
-    visit_definitions(visitor, &document.definitions, context, user_context);
+    let control = visitor.enter_document_with_control(context, user_context, document);
+
+    if control.is_break() {
+        return;
+    }
+
+    if control.should_visit_children()
+        && visit_definitions(visitor, &document.definitions, context, user_context).is_break()
+    {
+        return;
+    }
+
     visitor.leave_document(context, user_context, document);
 }
 
@@ -155,10 +206,17 @@ fn visit_definitions<'a, Visitor, UserContext>(
     definitions: &'a Vec<Definition>,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
-) where
+) -> VisitorControl
+where
     Visitor: OperationVisitor<'a, UserContext>,
 {
     for definition in definitions {
+        if context.follow_fragment_spreads {
+            if let Definition::Fragment(_) = definition {
+                continue;
+            }
+        }
+
         let schema_type_name = match definition {
             Definition::Fragment(fragment) => {
                 let TypeCondition::On(name) = &fragment.type_condition;
@@ -175,7 +233,7 @@ fn visit_definitions<'a, Visitor, UserContext>(
         };
 
         let schema_type = schema_type_name.map(|v| Type::NamedType(v.clone()));
-        context.with_type(schema_type.as_ref(), |context| match definition {
+        let control = context.with_type(schema_type.as_ref(), |context| match definition {
             Definition::Fragment(fragment) => {
                 visit_fragment_definition(visitor, fragment, context, user_context)
             }
@@ -183,7 +241,13 @@ fn visit_definitions<'a, Visitor, UserContext>(
                 visit_operation_definition(visitor, operation, context, user_context)
             }
         });
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
     }
+
+    VisitorControl::Continue
 }
 
 fn visit_directives<'a, Visitor, UserContext>(
@@ -326,7 +390,8 @@ fn visit_selection<'a, Visitor, UserContext>(
     selection: &'a Selection,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
-) where
+) -> VisitorControl
+where
     Visitor: OperationVisitor<'a, UserContext>,
 {
     match selection {
@@ -339,84 +404,182 @@ fn visit_selection<'a, Visitor, UserContext>(
             let field_args = parent_type_def.map(|f| &f.arguments);
 
             context.with_type(field_type, |context| {
-                visitor.enter_field(context, user_context, field);
-                context.with_field(
-                    context
-                        .current_parent_type()
-                        .and_then(|t| t.field_by_name(&field.name)),
-                    |context| {
-                        visit_arguments(
-                            visitor,
-                            field_args,
-                            &field.arguments,
-                            context,
-                            user_context,
-                        );
-                        visit_directives(visitor, &field.directives, context, user_context);
-                        visit_selection_set(visitor, &field.selection_set, context, user_context);
-                    },
-                );
+                let control = visitor.enter_field_with_control(context, user_context, field);
+
+                if control.is_break() {
+                    return VisitorControl::Break;
+                }
+
+                if control.should_visit_children() {
+                    let control = context.with_field(
+                        context
+                            .current_parent_type()
+                            .and_then(|t| t.field_by_name(&field.name)),
+                        |context| {
+                            visit_arguments(
+                                visitor,
+                                field_args,
+                                &field.arguments,
+                                context,
+                                user_context,
+                            );
+                            visit_directives(visitor, &field.directives, context, user_context);
+                            visit_selection_set(
+                                visitor,
+                                &field.selection_set,
+                                context,
+                                user_context,
+                            )
+                        },
+                    );
+
+                    if control.is_break() {
+                        return VisitorControl::Break;
+                    }
+                }
+
                 visitor.leave_field(context, user_context, field);
-            });
+
+                VisitorControl::Continue
+            })
         }
         Selection::FragmentSpread(fragment_spread) => {
-            visitor.enter_fragment_spread(context, user_context, fragment_spread);
-            visit_directives(visitor, &fragment_spread.directives, context, user_context);
+            let control =
+                visitor.enter_fragment_spread_with_control(context, user_context, fragment_spread);
+
+            if control.is_break() {
+                return VisitorControl::Break;
+            }
+
+            if control.should_visit_children() {
+                visit_directives(visitor, &fragment_spread.directives, context, user_context);
+
+                if context.follow_fragment_spreads
+                    && visit_spread_fragment(visitor, fragment_spread, context, user_context)
+                        .is_break()
+                {
+                    return VisitorControl::Break;
+                }
+            }
+
             visitor.leave_fragment_spread(context, user_context, fragment_spread);
+
+            VisitorControl::Continue
         }
         Selection::InlineFragment(inline_fragment) => {
             if let Some(TypeCondition::On(fragment_condition)) = &inline_fragment.type_condition {
                 context.with_type(
                     Some(&Type::NamedType(fragment_condition.clone())),
                     |context| {
-                        visitor.enter_inline_fragment(context, user_context, inline_fragment);
-                        visit_directives(
-                            visitor,
-                            &inline_fragment.directives,
-                            context,
-                            user_context,
-                        );
-                        visit_selection_set(
-                            visitor,
-                            &inline_fragment.selection_set,
-                            context,
-                            user_context,
-                        );
-                        visitor.leave_inline_fragment(context, user_context, inline_fragment);
+                        visit_inline_fragment(visitor, inline_fragment, context, user_context)
                     },
-                );
+                )
             } else {
-                visitor.enter_inline_fragment(context, user_context, inline_fragment);
-                visit_directives(visitor, &inline_fragment.directives, context, user_context);
-                visit_selection_set(
-                    visitor,
-                    &inline_fragment.selection_set,
-                    context,
-                    user_context,
-                );
-                visitor.leave_inline_fragment(context, user_context, inline_fragment);
+                visit_inline_fragment(visitor, inline_fragment, context, user_context)
             }
         }
     }
 }
 
+fn visit_spread_fragment<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    fragment_spread: &'a FragmentSpread,
+    context: &mut OperationVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: OperationVisitor<'a, UserContext>,
+{
+    let fragment = match context
+        .known_fragments
+        .get(fragment_spread.fragment_name.as_str())
+    {
+        Some(fragment) => *fragment,
+        None => return VisitorControl::Continue,
+    };
+
+    if context
+        .fragment_stack
+        .iter()
+        .any(|f| f.name.eq(&fragment.name))
+    {
+        return VisitorControl::Continue;
+    }
+
+    let TypeCondition::On(type_condition) = &fragment.type_condition;
+
+    context.with_fragment(fragment, |context| {
+        context.with_type(Some(&Type::NamedType(type_condition.clone())), |context| {
+            visit_selection_set(visitor, &fragment.selection_set, context, user_context)
+        })
+    })
+}
+
+fn visit_inline_fragment<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    inline_fragment: &'a InlineFragment,
+    context: &mut OperationVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: OperationVisitor<'a, UserContext>,
+{
+    let control =
+        visitor.enter_inline_fragment_with_control(context, user_context, inline_fragment);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children() {
+        visit_directives(visitor, &inline_fragment.directives, context, user_context);
+
+        if visit_selection_set(
+            visitor,
+            &inline_fragment.selection_set,
+            context,
+            user_context,
+        )
+        .is_break()
+        {
+            return VisitorControl::Break;
+        }
+    }
+
+    visitor.leave_inline_fragment(context, user_context, inline_fragment);
+
+    VisitorControl::Continue
+}
+
 fn visit_selection_set<'a, Visitor, UserContext>(
     visitor: &mut Visitor,
     selection_set: &'a SelectionSet,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
-) where
+) -> VisitorControl
+where
     Visitor: OperationVisitor<'a, UserContext>,
 {
     context.with_parent_type(|context| {
-        visitor.enter_selection_set(context, user_context, selection_set);
+        let control =
+            visitor.enter_selection_set_with_control(context, user_context, selection_set);
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
 
-        for selection in &selection_set.items {
-            visit_selection(visitor, selection, context, user_context);
+        if control.should_visit_children() {
+            for selection in &selection_set.items {
+                if visit_selection(visitor, selection, context, user_context).is_break() {
+                    return VisitorControl::Break;
+                }
+            }
         }
 
         visitor.leave_selection_set(context, user_context, selection_set);
-    });
+
+        VisitorControl::Continue
+    })
 }
 
 fn visit_fragment_definition<'a, Visitor, UserContext>(
@@ -424,13 +587,27 @@ fn visit_fragment_definition<'a, Visitor, UserContext>(
     fragment: &'a FragmentDefinition,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
-) where
+) -> VisitorControl
+where
     Visitor: OperationVisitor<'a, UserContext>,
 {
-    visitor.enter_fragment_definition(context, user_context, fragment);
-    visit_directives(visitor, &fragment.directives, context, user_context);
-    visit_selection_set(visitor, &fragment.selection_set, context, user_context);
+    let control = visitor.enter_fragment_definition_with_control(context, user_context, fragment);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children() {
+        visit_directives(visitor, &fragment.directives, context, user_context);
+
+        if visit_selection_set(visitor, &fragment.selection_set, context, user_context).is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
     visitor.leave_fragment_definition(context, user_context, fragment);
+
+    VisitorControl::Continue
 }
 
 fn visit_operation_definition<'a, Visitor, UserContext>(
@@ -438,23 +615,106 @@ fn visit_operation_definition<'a, Visitor, UserContext>(
     operation: &'a OperationDefinition,
     context: &mut OperationVisitorContext<'a>,
     user_context: &mut UserContext,
-) where
+) -> VisitorControl
+where
     Visitor: OperationVisitor<'a, UserContext>,
 {
-    visitor.enter_operation_definition(context, user_context, operation);
-    visit_directives(visitor, operation.directives(), context, user_context);
-    visit_variable_definitions(
-        visitor,
-        operation.variable_definitions(),
-        context,
-        user_context,
-    );
-    visit_selection_set(visitor, operation.selection_set(), context, user_context);
+    let control = visitor.enter_operation_definition_with_control(context, user_context, operation);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children() {
+        visit_directives(visitor, operation.directives(), context, user_context);
+        visit_variable_definitions(
+            visitor,
+            operation.variable_definitions(),
+            context,
+            user_context,
+        );
+
+        if visit_selection_set(visitor, operation.selection_set(), context, user_context).is_break()
+        {
+            return VisitorControl::Break;
+        }
+    }
+
     visitor.leave_operation_definition(context, user_context, operation);
+
+    VisitorControl::Continue
 }
 
 // Trait
+/// A trait for implementing a visitor for GraphQL operations, use `visit_document` to run it.
+///
+/// The `enter_*_with_control` methods can be overridden to skip the children of a node or to
+/// stop the traversal. By default, they call the matching `enter_*` method and continue.
 pub trait OperationVisitor<'a, UserContext = ()> {
+    fn enter_document_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        document: &'a Document,
+    ) -> VisitorControl {
+        self.enter_document(visitor_context, user_context, document);
+        VisitorControl::Continue
+    }
+    fn enter_operation_definition_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        operation: &'a OperationDefinition,
+    ) -> VisitorControl {
+        self.enter_operation_definition(visitor_context, user_context, operation);
+        VisitorControl::Continue
+    }
+    fn enter_fragment_definition_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        fragment: &'a FragmentDefinition,
+    ) -> VisitorControl {
+        self.enter_fragment_definition(visitor_context, user_context, fragment);
+        VisitorControl::Continue
+    }
+    fn enter_selection_set_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        selection_set: &'a SelectionSet,
+    ) -> VisitorControl {
+        self.enter_selection_set(visitor_context, user_context, selection_set);
+        VisitorControl::Continue
+    }
+    fn enter_field_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        self.enter_field(visitor_context, user_context, field);
+        VisitorControl::Continue
+    }
+    fn enter_fragment_spread_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        fragment_spread: &'a FragmentSpread,
+    ) -> VisitorControl {
+        self.enter_fragment_spread(visitor_context, user_context, fragment_spread);
+        VisitorControl::Continue
+    }
+    fn enter_inline_fragment_with_control(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut UserContext,
+        inline_fragment: &'a InlineFragment,
+    ) -> VisitorControl {
+        self.enter_inline_fragment(visitor_context, user_context, inline_fragment);
+        VisitorControl::Continue
+    }
+
     fn enter_document(
         &mut self,
         _: &mut OperationVisitorContext<'a>,
@@ -700,3 +960,291 @@ pub trait OperationVisitor<'a, UserContext = ()> {
     ) {
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{AstNodeWithName, TypeDefinitionExtension};
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    struct MaxDepthVisitor {
+        max_depth: usize,
+        depth: usize,
+        visited_fields: Vec<String>,
+    }
+
+    impl<'a> OperationVisitor<'a, Option<String>> for MaxDepthVisitor {
+        fn enter_field_with_control(
+            &mut self,
+            _: &mut OperationVisitorContext<'a>,
+            exceeded: &mut Option<String>,
+            field: &'a Field,
+        ) -> VisitorControl {
+            self.depth += 1;
+            self.visited_fields.push(field.name.clone());
+
+            if self.depth > self.max_depth {
+                *exceeded = Some(field.name.clone());
+                return VisitorControl::Break;
+            }
+
+            VisitorControl::Continue
+        }
+
+        fn leave_field(
+            &mut self,
+            _: &mut OperationVisitorContext<'a>,
+            _: &mut Option<String>,
+            _: &Field,
+        ) {
+            self.depth -= 1;
+        }
+
+        fn leave_document(
+            &mut self,
+            _: &mut OperationVisitorContext<'a>,
+            _: &mut Option<String>,
+            _: &Document,
+        ) {
+            panic!("leave_document should not be called after a break");
+        }
+    }
+
+    #[test]
+    fn break_stops_traversal() {
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let operation = graphql_parser::parse_query(
+            "{
+              dog {
+                mother {
+                  father {
+                    name
+                  }
+                }
+              }
+              cat {
+                name
+              }
+            }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let mut visitor = MaxDepthVisitor {
+            max_depth: 2,
+            depth: 0,
+            visited_fields: vec![],
+        };
+        let mut exceeded = None;
+        visit_document(
+            &mut visitor,
+            &operation,
+            &mut OperationVisitorContext::new(&operation, &schema),
+            &mut exceeded,
+        );
+
+        assert_eq!(exceeded, Some("father".to_string()));
+        assert_eq!(visitor.visited_fields, vec!["dog", "mother", "father"]);
+    }
+
+    #[test]
+    fn skip_children_still_calls_leave() {
+        struct SkipDogVisitor {
+            visited: Vec<String>,
+        }
+
+        impl<'a> OperationVisitor<'a> for SkipDogVisitor {
+            fn enter_field_with_control(
+                &mut self,
+                visitor_context: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                field: &'a Field,
+            ) -> VisitorControl {
+                self.visited.push(format!(
+                    "enter {}.{}",
+                    visitor_context.current_parent_type().name(),
+                    field.name
+                ));
+
+                if field.name == "dog" {
+                    VisitorControl::SkipChildren
+                } else {
+                    VisitorControl::Continue
+                }
+            }
+
+            fn leave_field(
+                &mut self,
+                _: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                field: &Field,
+            ) {
+                self.visited.push(format!("leave {}", field.name));
+            }
+
+            fn enter_fragment_spread(
+                &mut self,
+                _: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                fragment_spread: &'a FragmentSpread,
+            ) {
+                self.visited
+                    .push(format!("spread {}", fragment_spread.fragment_name));
+            }
+        }
+
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let operation = graphql_parser::parse_query(
+            "{
+              dog {
+                ...DogFields
+              }
+              cat {
+                name
+              }
+            }
+            
+            fragment DogFields on Dog {
+              name
+            }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let mut visitor = SkipDogVisitor { visited: vec![] };
+        visit_document(
+            &mut visitor,
+            &operation,
+            &mut OperationVisitorContext::new(&operation, &schema),
+            &mut (),
+        );
+
+        assert_eq!(
+            visitor.visited,
+            vec![
+                "enter QueryRoot.dog",
+                "leave dog",
+                "enter QueryRoot.cat",
+                "enter Cat.name",
+                "leave name",
+                "leave cat",
+                "enter Dog.name",
+                "leave name"
+            ]
+        );
+    }
+
+    #[test]
+    fn follow_fragment_spreads() {
+        struct UsageVisitor {
+            current_operation: Option<String>,
+            usages: Vec<String>,
+        }
+
+        impl<'a> OperationVisitor<'a> for UsageVisitor {
+            fn enter_operation_definition(
+                &mut self,
+                _: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                operation: &'a OperationDefinition,
+            ) {
+                self.current_operation = operation.node_name().map(|name| name.to_string());
+            }
+
+            fn enter_fragment_definition(
+                &mut self,
+                _: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                _: &'a FragmentDefinition,
+            ) {
+                panic!("fragment definitions should not be visited when following spreads");
+            }
+
+            fn enter_field(
+                &mut self,
+                visitor_context: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                field: &Field,
+            ) {
+                self.usages.push(format!(
+                    "{}: {}.{} via [{}]",
+                    self.current_operation.as_ref().unwrap(),
+                    visitor_context.current_parent_type().name(),
+                    field.name,
+                    visitor_context
+                        .fragment_path()
+                        .iter()
+                        .map(|f| f.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+
+            fn enter_variable_value(
+                &mut self,
+                visitor_context: &mut OperationVisitorContext<'a>,
+                _: &mut (),
+                name: &'a str,
+            ) {
+                self.usages.push(format!(
+                    "{}: ${} in {}",
+                    self.current_operation.as_ref().unwrap(),
+                    name,
+                    visitor_context.current_fragment().unwrap().name
+                ));
+            }
+        }
+
+        let schema = graphql_parser::parse_schema(TEST_SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let operation = graphql_parser::parse_query(
+            "query A($atOtherHomes: Boolean) {
+              dog {
+                ...DogFields
+              }
+            }
+
+            query B {
+              catOrDog {
+                ...DogFields
+              }
+            }
+
+            fragment DogFields on Dog {
+              isHouseTrained(atOtherHomes: $atOtherHomes)
+              mother {
+                ...DogFields
+              }
+            }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let mut visitor = UsageVisitor {
+            current_operation: None,
+            usages: vec![],
+        };
+        let mut context = OperationVisitorContext::new(&operation, &schema);
+        context.follow_fragment_spreads = true;
+        visit_document(&mut visitor, &operation, &mut context, &mut ());
+
+        assert_eq!(
+            visitor.usages,
+            vec![
+                "A: QueryRoot.dog via []",
+                "A: Dog.isHouseTrained via [DogFields]",
+                "A: $atOtherHomes in DogFields",
+                "A: Dog.mother via [DogFields]",
+                "B: QueryRoot.catOrDog via []",
+                "B: Dog.isHouseTrained via [DogFields]",
+                "B: $atOtherHomes in DogFields",
+                "B: Dog.mother via [DogFields]",
+            ]
+        );
+    }
+}
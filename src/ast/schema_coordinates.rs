@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use super::{
+    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
+    SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+};
+use crate::static_graphql::{
+    query::{Directive, Document, Field, Value},
+    schema,
+};
+
+/// Collects the schema coordinates used by the operations of a document, with the number of
+/// times each one is used.
+///
+/// The collected coordinates are fields (`Type.field`), field arguments (`Type.field(arg:)`),
+/// directives (`@directive`), directive arguments (`@directive(arg:)`), input fields
+/// (`Input.field`) and enum values (`Enum.VALUE`). Fragments are followed from their spread sites,
+/// so a fragment spread twice is counted twice and an unused fragment is not counted.
+///
+/// Values that are passed through variables are not known at this point, so input fields and enum
+/// values are only collected when written in the document (or in variables default values).
+pub fn collect_schema_coordinates(
+    schema: &schema::Document,
+    operation: &Document,
+) -> BTreeMap<String, usize> {
+    let mut context = OperationVisitorContext::new(operation, schema);
+    context.follow_fragment_spreads = true;
+
+    let mut coordinates = BTreeMap::new();
+    visit_document(
+        &mut SchemaCoordinatesCollector::new(),
+        operation,
+        &mut context,
+        &mut coordinates,
+    );
+
+    coordinates
+}
+
+struct SchemaCoordinatesCollector {
+    directives: Vec<Option<String>>,
+    input_objects: Vec<Option<String>>,
+}
+
+impl SchemaCoordinatesCollector {
+    fn new() -> Self {
+        SchemaCoordinatesCollector {
+            directives: vec![],
+            input_objects: vec![],
+        }
+    }
+}
+
+fn increment(coordinates: &mut BTreeMap<String, usize>, coordinate: String) {
+    *coordinates.entry(coordinate).or_insert(0) += 1;
+}
+
+impl<'a> OperationVisitor<'a, BTreeMap<String, usize>> for SchemaCoordinatesCollector {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut BTreeMap<String, usize>,
+        field: &Field,
+    ) {
+        if let Some(parent_type) = visitor_context.current_parent_type() {
+            if parent_type.field_by_name(&field.name).is_some() {
+                increment(
+                    coordinates,
+                    format!("{}.{}", parent_type.name(), field.name),
+                );
+            }
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut BTreeMap<String, usize>,
+        directive: &Directive,
+    ) {
+        if visitor_context.directives.contains_key(&directive.name) {
+            increment(coordinates, format!("@{}", directive.name));
+            self.directives.push(Some(directive.name.clone()));
+        } else {
+            self.directives.push(None);
+        }
+    }
+
+    fn leave_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut BTreeMap<String, usize>,
+        _: &Directive,
+    ) {
+        self.directives.pop();
+    }
+
+    fn enter_argument(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut BTreeMap<String, usize>,
+        (argument_name, _): &'a (String, Value),
+    ) {
+        // Only arguments known by the schema have an input type
+        if visitor_context.current_input_type_literal().is_none() {
+            return;
+        }
+
+        if let Some(directive) = self.directives.last() {
+            if let Some(directive_name) = directive {
+                increment(
+                    coordinates,
+                    format!("@{}({}:)", directive_name, argument_name),
+                );
+            }
+        } else if let (Some(parent_type), Some(field)) = (
+            visitor_context.current_parent_type(),
+            visitor_context.current_field(),
+        ) {
+            increment(
+                coordinates,
+                format!("{}.{}({}:)", parent_type.name(), field.name, argument_name),
+            );
+        }
+    }
+
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut BTreeMap<String, usize>,
+        _: &BTreeMap<String, Value>,
+    ) {
+        let input_object = visitor_context
+            .current_input_type_literal()
+            .and_then(|t| visitor_context.schema.type_by_name(t.inner_type()))
+            .and_then(|t| match t {
+                schema::TypeDefinition::InputObject(input_object) => {
+                    Some(input_object.name.clone())
+                }
+                _ => None,
+            });
+
+        self.input_objects.push(input_object);
+    }
+
+    fn leave_object_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut BTreeMap<String, usize>,
+        _: &BTreeMap<String, Value>,
+    ) {
+        self.input_objects.pop();
+    }
+
+    fn enter_object_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut BTreeMap<String, usize>,
+        (field_name, _): &(String, Value),
+    ) {
+        if let Some(Some(input_object)) = self.input_objects.last() {
+            if visitor_context.current_input_type_literal().is_some() {
+                increment(coordinates, format!("{}.{}", input_object, field_name));
+            }
+        }
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        coordinates: &mut BTreeMap<String, usize>,
+        value: &String,
+    ) {
+        if let Some(schema::TypeDefinition::Enum(enum_type)) = visitor_context.current_input_type()
+        {
+            if enum_type.values.iter().any(|v| v.name.eq(value)) {
+                increment(coordinates, format!("{}.{}", enum_type.name, value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::test_utils::{INTROSPECTION_SCHEMA, TEST_SCHEMA};
+
+    #[test]
+    fn collect_coordinates_through_fragments() {
+        let schema =
+            graphql_parser::parse_schema(&format!("{}{}", TEST_SCHEMA, INTROSPECTION_SCHEMA))
+                .expect("Failed to parse schema")
+                .into_static();
+        let operation = graphql_parser::parse_query(
+            "query A($skip: Boolean!) {
+              dog {
+                ...DogFields
+                doesKnowCommand(dogCommand: SIT)
+              }
+              catOrDog {
+                ...DogFields
+                ... on Cat {
+                  furColor
+                  __typename
+                }
+              }
+              complicatedArgs {
+                complexArgField(complexArg: { requiredField: true, stringListField: [\"a\"] })
+                enumArgField(enumArg: BROWN) @skip(if: $skip)
+              }
+            }
+
+            fragment DogFields on Dog {
+              name(surname: true)
+            }
+
+            fragment UnusedFields on Dog {
+              barkVolume
+            }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let coordinates = collect_schema_coordinates(&schema, &operation);
+
+        assert_eq!(
+            coordinates.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("@skip".to_string(), 1),
+                ("@skip(if:)".to_string(), 1),
+                ("Cat.furColor".to_string(), 1),
+                ("ComplexInput.requiredField".to_string(), 1),
+                ("ComplexInput.stringListField".to_string(), 1),
+                ("ComplicatedArgs.complexArgField".to_string(), 1),
+                (
+                    "ComplicatedArgs.complexArgField(complexArg:)".to_string(),
+                    1
+                ),
+                ("ComplicatedArgs.enumArgField".to_string(), 1),
+                ("ComplicatedArgs.enumArgField(enumArg:)".to_string(), 1),
+                ("Dog.doesKnowCommand".to_string(), 1),
+                ("Dog.doesKnowCommand(dogCommand:)".to_string(), 1),
+                ("Dog.name".to_string(), 2),
+                ("Dog.name(surname:)".to_string(), 2),
+                ("DogCommand.SIT".to_string(), 1),
+                ("FurColor.BROWN".to_string(), 1),
+                ("QueryRoot.catOrDog".to_string(), 1),
+                ("QueryRoot.complicatedArgs".to_string(), 1),
+                ("QueryRoot.dog".to_string(), 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,751 @@
+use graphql_parser::schema::*;
+
+use super::{Transformed, TransformedValue};
+
+/// A trait for implementing a transformer for GraphQL schema definition.
+///
+/// Works like `OperationTransformer`: every `transform_*` method can be overridden, and the
+/// matching `default_transform_*` method can be used to continue the traversal of the children.
+pub trait SchemaTransformer<'a, T: Text<'a> + Clone> {
+    fn transform_document(
+        &mut self,
+        document: &Document<'a, T>,
+    ) -> TransformedValue<Document<'a, T>> {
+        self.default_transform_document(document)
+    }
+
+    fn default_transform_document(
+        &mut self,
+        document: &Document<'a, T>,
+    ) -> TransformedValue<Document<'a, T>> {
+        match self.transform_list(&document.definitions, Self::transform_definition) {
+            TransformedValue::Keep => TransformedValue::Keep,
+            TransformedValue::Replace(definitions) => {
+                TransformedValue::Replace(Document { definitions })
+            }
+        }
+    }
+
+    fn transform_definition(
+        &mut self,
+        definition: &Definition<'a, T>,
+    ) -> Transformed<Definition<'a, T>> {
+        self.default_transform_definition(definition)
+    }
+
+    fn default_transform_definition(
+        &mut self,
+        definition: &Definition<'a, T>,
+    ) -> Transformed<Definition<'a, T>> {
+        match definition {
+            Definition::SchemaDefinition(schema_definition) => {
+                match self.transform_schema_definition(schema_definition) {
+                    Transformed::Keep => Transformed::Keep,
+                    Transformed::Replace(replacement) => {
+                        Transformed::Replace(Definition::SchemaDefinition(replacement))
+                    }
+                }
+            }
+            Definition::TypeDefinition(type_definition) => {
+                match self.transform_type_definition(type_definition) {
+                    Transformed::Keep => Transformed::Keep,
+                    Transformed::Replace(replacement) => {
+                        Transformed::Replace(Definition::TypeDefinition(replacement))
+                    }
+                }
+            }
+            Definition::TypeExtension(type_extension) => {
+                match self.transform_type_extension(type_extension) {
+                    Transformed::Keep => Transformed::Keep,
+                    Transformed::Replace(replacement) => {
+                        Transformed::Replace(Definition::TypeExtension(replacement))
+                    }
+                }
+            }
+            Definition::DirectiveDefinition(directive_definition) => {
+                match self.transform_directive_definition(directive_definition) {
+                    Transformed::Keep => Transformed::Keep,
+                    Transformed::Replace(replacement) => {
+                        Transformed::Replace(Definition::DirectiveDefinition(replacement))
+                    }
+                }
+            }
+        }
+    }
+
+    fn transform_schema_definition(
+        &mut self,
+        schema_definition: &SchemaDefinition<'a, T>,
+    ) -> Transformed<SchemaDefinition<'a, T>> {
+        self.default_transform_schema_definition(schema_definition)
+    }
+
+    fn default_transform_schema_definition(
+        &mut self,
+        schema_definition: &SchemaDefinition<'a, T>,
+    ) -> Transformed<SchemaDefinition<'a, T>> {
+        match self.transform_directives(&schema_definition.directives) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(directives) => Transformed::Replace(SchemaDefinition {
+                position: schema_definition.position,
+                directives,
+                query: schema_definition.query.clone(),
+                mutation: schema_definition.mutation.clone(),
+                subscription: schema_definition.subscription.clone(),
+            }),
+        }
+    }
+
+    fn transform_type_definition(
+        &mut self,
+        type_definition: &TypeDefinition<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_type_definition(type_definition)
+    }
+
+    fn default_transform_type_definition(
+        &mut self,
+        type_definition: &TypeDefinition<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        match type_definition {
+            TypeDefinition::Scalar(scalar) => self.transform_scalar_type(scalar),
+            TypeDefinition::Object(object) => self.transform_object_type(object),
+            TypeDefinition::Interface(interface) => self.transform_interface_type(interface),
+            TypeDefinition::Union(union) => self.transform_union_type(union),
+            TypeDefinition::Enum(enum_) => self.transform_enum_type(enum_),
+            TypeDefinition::InputObject(input_object) => {
+                self.transform_input_object_type(input_object)
+            }
+        }
+    }
+
+    fn transform_type_extension(
+        &mut self,
+        type_extension: &TypeExtension<'a, T>,
+    ) -> Transformed<TypeExtension<'a, T>> {
+        self.default_transform_type_extension(type_extension)
+    }
+
+    fn default_transform_type_extension(
+        &mut self,
+        type_extension: &TypeExtension<'a, T>,
+    ) -> Transformed<TypeExtension<'a, T>> {
+        match type_extension {
+            TypeExtension::Scalar(scalar) => match self.transform_directives(&scalar.directives) {
+                TransformedValue::Keep => Transformed::Keep,
+                TransformedValue::Replace(directives) => {
+                    Transformed::Replace(TypeExtension::Scalar(ScalarTypeExtension {
+                        position: scalar.position,
+                        name: scalar.name.clone(),
+                        directives,
+                    }))
+                }
+            },
+            TypeExtension::Object(object) => {
+                let directives = self.transform_directives(&object.directives);
+                let fields = self.transform_fields(&object.fields);
+
+                if directives.should_keep() && fields.should_keep() {
+                    return Transformed::Keep;
+                }
+
+                Transformed::Replace(TypeExtension::Object(ObjectTypeExtension {
+                    position: object.position,
+                    name: object.name.clone(),
+                    implements_interfaces: object.implements_interfaces.clone(),
+                    directives: directives.replace_or_else(|| object.directives.clone()),
+                    fields: fields.replace_or_else(|| object.fields.clone()),
+                }))
+            }
+            TypeExtension::Interface(interface) => {
+                let directives = self.transform_directives(&interface.directives);
+                let fields = self.transform_fields(&interface.fields);
+
+                if directives.should_keep() && fields.should_keep() {
+                    return Transformed::Keep;
+                }
+
+                Transformed::Replace(TypeExtension::Interface(InterfaceTypeExtension {
+                    position: interface.position,
+                    name: interface.name.clone(),
+                    implements_interfaces: interface.implements_interfaces.clone(),
+                    directives: directives.replace_or_else(|| interface.directives.clone()),
+                    fields: fields.replace_or_else(|| interface.fields.clone()),
+                }))
+            }
+            TypeExtension::Union(union) => match self.transform_directives(&union.directives) {
+                TransformedValue::Keep => Transformed::Keep,
+                TransformedValue::Replace(directives) => {
+                    Transformed::Replace(TypeExtension::Union(UnionTypeExtension {
+                        position: union.position,
+                        name: union.name.clone(),
+                        directives,
+                        types: union.types.clone(),
+                    }))
+                }
+            },
+            TypeExtension::Enum(enum_) => {
+                let directives = self.transform_directives(&enum_.directives);
+                let values = self.transform_enum_values(&enum_.values);
+
+                if directives.should_keep() && values.should_keep() {
+                    return Transformed::Keep;
+                }
+
+                Transformed::Replace(TypeExtension::Enum(EnumTypeExtension {
+                    position: enum_.position,
+                    name: enum_.name.clone(),
+                    directives: directives.replace_or_else(|| enum_.directives.clone()),
+                    values: values.replace_or_else(|| enum_.values.clone()),
+                }))
+            }
+            TypeExtension::InputObject(input_object) => {
+                let directives = self.transform_directives(&input_object.directives);
+                let fields = self.transform_input_values(&input_object.fields);
+
+                if directives.should_keep() && fields.should_keep() {
+                    return Transformed::Keep;
+                }
+
+                Transformed::Replace(TypeExtension::InputObject(InputObjectTypeExtension {
+                    position: input_object.position,
+                    name: input_object.name.clone(),
+                    directives: directives.replace_or_else(|| input_object.directives.clone()),
+                    fields: fields.replace_or_else(|| input_object.fields.clone()),
+                }))
+            }
+        }
+    }
+
+    fn transform_scalar_type(
+        &mut self,
+        scalar: &ScalarType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_scalar_type(scalar)
+    }
+
+    fn default_transform_scalar_type(
+        &mut self,
+        scalar: &ScalarType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        match self.transform_directives(&scalar.directives) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(directives) => {
+                Transformed::Replace(TypeDefinition::Scalar(ScalarType {
+                    position: scalar.position,
+                    description: scalar.description.clone(),
+                    name: scalar.name.clone(),
+                    directives,
+                }))
+            }
+        }
+    }
+
+    fn transform_object_type(
+        &mut self,
+        object: &ObjectType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_object_type(object)
+    }
+
+    fn default_transform_object_type(
+        &mut self,
+        object: &ObjectType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        let directives = self.transform_directives(&object.directives);
+        let fields = self.transform_fields(&object.fields);
+
+        if directives.should_keep() && fields.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(TypeDefinition::Object(ObjectType {
+            position: object.position,
+            description: object.description.clone(),
+            name: object.name.clone(),
+            implements_interfaces: object.implements_interfaces.clone(),
+            directives: directives.replace_or_else(|| object.directives.clone()),
+            fields: fields.replace_or_else(|| object.fields.clone()),
+        }))
+    }
+
+    fn transform_interface_type(
+        &mut self,
+        interface: &InterfaceType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_interface_type(interface)
+    }
+
+    fn default_transform_interface_type(
+        &mut self,
+        interface: &InterfaceType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        let directives = self.transform_directives(&interface.directives);
+        let fields = self.transform_fields(&interface.fields);
+
+        if directives.should_keep() && fields.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(TypeDefinition::Interface(InterfaceType {
+            position: interface.position,
+            description: interface.description.clone(),
+            name: interface.name.clone(),
+            implements_interfaces: interface.implements_interfaces.clone(),
+            directives: directives.replace_or_else(|| interface.directives.clone()),
+            fields: fields.replace_or_else(|| interface.fields.clone()),
+        }))
+    }
+
+    fn transform_union_type(
+        &mut self,
+        union: &UnionType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_union_type(union)
+    }
+
+    fn default_transform_union_type(
+        &mut self,
+        union: &UnionType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        match self.transform_directives(&union.directives) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(directives) => {
+                Transformed::Replace(TypeDefinition::Union(UnionType {
+                    position: union.position,
+                    description: union.description.clone(),
+                    name: union.name.clone(),
+                    directives,
+                    types: union.types.clone(),
+                }))
+            }
+        }
+    }
+
+    fn transform_enum_type(
+        &mut self,
+        enum_: &EnumType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_enum_type(enum_)
+    }
+
+    fn default_transform_enum_type(
+        &mut self,
+        enum_: &EnumType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        let directives = self.transform_directives(&enum_.directives);
+        let values = self.transform_enum_values(&enum_.values);
+
+        if directives.should_keep() && values.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(TypeDefinition::Enum(EnumType {
+            position: enum_.position,
+            description: enum_.description.clone(),
+            name: enum_.name.clone(),
+            directives: directives.replace_or_else(|| enum_.directives.clone()),
+            values: values.replace_or_else(|| enum_.values.clone()),
+        }))
+    }
+
+    fn transform_input_object_type(
+        &mut self,
+        input_object: &InputObjectType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        self.default_transform_input_object_type(input_object)
+    }
+
+    fn default_transform_input_object_type(
+        &mut self,
+        input_object: &InputObjectType<'a, T>,
+    ) -> Transformed<TypeDefinition<'a, T>> {
+        let directives = self.transform_directives(&input_object.directives);
+        let fields = self.transform_input_values(&input_object.fields);
+
+        if directives.should_keep() && fields.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(TypeDefinition::InputObject(InputObjectType {
+            position: input_object.position,
+            description: input_object.description.clone(),
+            name: input_object.name.clone(),
+            directives: directives.replace_or_else(|| input_object.directives.clone()),
+            fields: fields.replace_or_else(|| input_object.fields.clone()),
+        }))
+    }
+
+    fn transform_directive_definition(
+        &mut self,
+        directive_definition: &DirectiveDefinition<'a, T>,
+    ) -> Transformed<DirectiveDefinition<'a, T>> {
+        self.default_transform_directive_definition(directive_definition)
+    }
+
+    fn default_transform_directive_definition(
+        &mut self,
+        directive_definition: &DirectiveDefinition<'a, T>,
+    ) -> Transformed<DirectiveDefinition<'a, T>> {
+        match self.transform_input_values(&directive_definition.arguments) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(arguments) => Transformed::Replace(DirectiveDefinition {
+                position: directive_definition.position,
+                description: directive_definition.description.clone(),
+                name: directive_definition.name.clone(),
+                arguments,
+                repeatable: directive_definition.repeatable,
+                locations: directive_definition.locations.clone(),
+            }),
+        }
+    }
+
+    fn transform_fields(&mut self, fields: &[Field<'a, T>]) -> TransformedValue<Vec<Field<'a, T>>> {
+        self.transform_list(fields, Self::transform_field)
+    }
+
+    fn transform_field(&mut self, field: &Field<'a, T>) -> Transformed<Field<'a, T>> {
+        self.default_transform_field(field)
+    }
+
+    fn default_transform_field(&mut self, field: &Field<'a, T>) -> Transformed<Field<'a, T>> {
+        let arguments = self.transform_input_values(&field.arguments);
+        let directives = self.transform_directives(&field.directives);
+
+        if arguments.should_keep() && directives.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(Field {
+            position: field.position,
+            description: field.description.clone(),
+            name: field.name.clone(),
+            arguments: arguments.replace_or_else(|| field.arguments.clone()),
+            field_type: field.field_type.clone(),
+            directives: directives.replace_or_else(|| field.directives.clone()),
+        })
+    }
+
+    /// Transforms a list of input values: field arguments, directive arguments and input object fields.
+    fn transform_input_values(
+        &mut self,
+        input_values: &[InputValue<'a, T>],
+    ) -> TransformedValue<Vec<InputValue<'a, T>>> {
+        self.transform_list(input_values, Self::transform_input_value)
+    }
+
+    fn transform_input_value(
+        &mut self,
+        input_value: &InputValue<'a, T>,
+    ) -> Transformed<InputValue<'a, T>> {
+        self.default_transform_input_value(input_value)
+    }
+
+    fn default_transform_input_value(
+        &mut self,
+        input_value: &InputValue<'a, T>,
+    ) -> Transformed<InputValue<'a, T>> {
+        let default_value = match &input_value.default_value {
+            Some(value) => match self.transform_value(value) {
+                TransformedValue::Keep => TransformedValue::Keep,
+                TransformedValue::Replace(replacement) => {
+                    TransformedValue::Replace(Some(replacement))
+                }
+            },
+            None => TransformedValue::Keep,
+        };
+        let directives = self.transform_directives(&input_value.directives);
+
+        if default_value.should_keep() && directives.should_keep() {
+            return Transformed::Keep;
+        }
+
+        Transformed::Replace(InputValue {
+            position: input_value.position,
+            description: input_value.description.clone(),
+            name: input_value.name.clone(),
+            value_type: input_value.value_type.clone(),
+            default_value: default_value.replace_or_else(|| input_value.default_value.clone()),
+            directives: directives.replace_or_else(|| input_value.directives.clone()),
+        })
+    }
+
+    fn transform_enum_values(
+        &mut self,
+        values: &[EnumValue<'a, T>],
+    ) -> TransformedValue<Vec<EnumValue<'a, T>>> {
+        self.transform_list(values, Self::transform_enum_value)
+    }
+
+    fn transform_enum_value(&mut self, value: &EnumValue<'a, T>) -> Transformed<EnumValue<'a, T>> {
+        self.default_transform_enum_value(value)
+    }
+
+    fn default_transform_enum_value(
+        &mut self,
+        value: &EnumValue<'a, T>,
+    ) -> Transformed<EnumValue<'a, T>> {
+        match self.transform_directives(&value.directives) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(directives) => Transformed::Replace(EnumValue {
+                position: value.position,
+                description: value.description.clone(),
+                name: value.name.clone(),
+                directives,
+            }),
+        }
+    }
+
+    fn transform_directives(
+        &mut self,
+        directives: &[Directive<'a, T>],
+    ) -> TransformedValue<Vec<Directive<'a, T>>> {
+        self.transform_list(directives, Self::transform_directive)
+    }
+
+    fn transform_directive(
+        &mut self,
+        directive: &Directive<'a, T>,
+    ) -> Transformed<Directive<'a, T>> {
+        self.default_transform_directive(directive)
+    }
+
+    fn default_transform_directive(
+        &mut self,
+        directive: &Directive<'a, T>,
+    ) -> Transformed<Directive<'a, T>> {
+        match self.transform_arguments(&directive.arguments) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(arguments) => Transformed::Replace(Directive {
+                position: directive.position,
+                name: directive.name.clone(),
+                arguments,
+            }),
+        }
+    }
+
+    fn transform_arguments(
+        &mut self,
+        arguments: &[(T::Value, Value<'a, T>)],
+    ) -> TransformedValue<Vec<(T::Value, Value<'a, T>)>> {
+        self.transform_list(arguments, Self::transform_argument)
+    }
+
+    fn transform_argument(
+        &mut self,
+        argument: &(T::Value, Value<'a, T>),
+    ) -> Transformed<(T::Value, Value<'a, T>)> {
+        let (name, value) = argument;
+
+        match self.transform_value(value) {
+            TransformedValue::Keep => Transformed::Keep,
+            TransformedValue::Replace(replacement) => {
+                Transformed::Replace((name.clone(), replacement))
+            }
+        }
+    }
+
+    fn transform_value(&mut self, value: &Value<'a, T>) -> TransformedValue<Value<'a, T>> {
+        self.default_transform_value(value)
+    }
+
+    /// Transforms the items of a list value and the fields of an object value.
+    fn default_transform_value(&mut self, value: &Value<'a, T>) -> TransformedValue<Value<'a, T>> {
+        match value {
+            Value::List(items) => match self.transform_list(items, Self::transform_value) {
+                TransformedValue::Keep => TransformedValue::Keep,
+                TransformedValue::Replace(items) => TransformedValue::Replace(Value::List(items)),
+            },
+            Value::Object(fields) => {
+                let mut has_changes = false;
+                let fields = fields
+                    .iter()
+                    .map(|(name, field_value)| {
+                        let field_value = match self.transform_value(field_value) {
+                            TransformedValue::Keep => field_value.clone(),
+                            TransformedValue::Replace(replacement) => {
+                                has_changes = true;
+                                replacement
+                            }
+                        };
+
+                        (name.clone(), field_value)
+                    })
+                    .collect();
+
+                if has_changes {
+                    TransformedValue::Replace(Value::Object(fields))
+                } else {
+                    TransformedValue::Keep
+                }
+            }
+            _ => TransformedValue::Keep,
+        }
+    }
+
+    fn transform_list<I, F, R>(&mut self, list: &[I], f: F) -> TransformedValue<Vec<I>>
+    where
+        I: Clone,
+        F: Fn(&mut Self, &I) -> R,
+        R: Into<Transformed<I>>,
+    {
+        let mut result = Vec::new();
+        let mut has_changes = false;
+        for (index, prev_item) in list.iter().enumerate() {
+            let next_item: Transformed<_> = f(self, prev_item).into();
+            match next_item {
+                Transformed::Keep => {
+                    if has_changes {
+                        result.push(prev_item.clone());
+                    }
+                }
+                Transformed::Replace(next_item) => {
+                    if !has_changes {
+                        debug_assert!(result.capacity() == 0);
+                        result.reserve(list.len());
+                        result.extend(list.iter().take(index).cloned());
+                    }
+                    result.push(next_item);
+                    has_changes = true;
+                }
+            }
+        }
+        if has_changes {
+            TransformedValue::Replace(result)
+        } else {
+            TransformedValue::Keep
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_deprecated_fields_and_strip_directives() {
+        let raw = parse_schema::<String>(
+            r#"
+            type Query {
+              user: User
+            }
+
+            type User @key(fields: "id") {
+              id: ID!
+              name: String @deprecated(reason: "Use fullName")
+              fullName: String
+            }
+
+            enum Role {
+              ADMIN
+              USER @deprecated
+            }
+        "#,
+        )
+        .expect("Failed to parse schema");
+
+        struct RemoveDeprecatedTransformer {}
+
+        impl<'a, T: Text<'a> + Clone> SchemaTransformer<'a, T> for RemoveDeprecatedTransformer {
+            fn transform_fields(
+                &mut self,
+                fields: &[Field<'a, T>],
+            ) -> TransformedValue<Vec<Field<'a, T>>> {
+                let fields: Vec<Field<'a, T>> = fields
+                    .iter()
+                    .filter(|f| !f.directives.iter().any(|d| d.name.as_ref() == "deprecated"))
+                    .cloned()
+                    .collect();
+
+                TransformedValue::Replace(fields)
+            }
+
+            fn transform_enum_values(
+                &mut self,
+                values: &[EnumValue<'a, T>],
+            ) -> TransformedValue<Vec<EnumValue<'a, T>>> {
+                let values: Vec<EnumValue<'a, T>> = values
+                    .iter()
+                    .filter(|v| !v.directives.iter().any(|d| d.name.as_ref() == "deprecated"))
+                    .cloned()
+                    .collect();
+
+                TransformedValue::Replace(values)
+            }
+
+            fn transform_directives(
+                &mut self,
+                _directives: &[Directive<'a, T>],
+            ) -> TransformedValue<Vec<Directive<'a, T>>> {
+                TransformedValue::Replace(vec![])
+            }
+        }
+
+        let mut transformer = RemoveDeprecatedTransformer {};
+        let transformed = transformer
+            .transform_document(&raw)
+            .replace_or_else(|| raw.clone());
+
+        assert_eq!(
+            format!("{}", transformed),
+            "type Query {\n  user: User\n}\n\ntype User {\n  id: ID!\n  fullName: String\n}\n\nenum Role {\n  ADMIN\n}\n"
+        );
+    }
+
+    #[test]
+    fn transform_type_extensions_and_nested_values() {
+        let raw = parse_schema::<String>(
+            r#"
+            type Query {
+              user: String
+            }
+
+            extend type Query @tags(names: ["a", { name: "b" }]) {
+              users(filter: [String] = ["c"]): [String]
+            }
+        "#,
+        )
+        .expect("Failed to parse schema");
+
+        struct UppercaseStringsTransformer {}
+
+        impl<'a, T: Text<'a> + Clone> SchemaTransformer<'a, T> for UppercaseStringsTransformer {
+            fn transform_value(&mut self, value: &Value<'a, T>) -> TransformedValue<Value<'a, T>> {
+                match value {
+                    Value::String(string) => {
+                        TransformedValue::Replace(Value::String(string.to_uppercase()))
+                    }
+                    _ => self.default_transform_value(value),
+                }
+            }
+        }
+
+        let mut transformer = UppercaseStringsTransformer {};
+        let transformed = transformer
+            .transform_document(&raw)
+            .replace_or_else(|| raw.clone());
+
+        assert_eq!(
+            format!("{}", transformed),
+            "type Query {\n  user: String\n}\n\nextend type Query @tags(names: [\"A\", {name: \"B\"}]) {\n  users(filter: [String] = [\"C\"]): [String]\n}\n"
+        );
+    }
+
+    #[test]
+    fn keep_when_nothing_changes() {
+        let raw = parse_schema::<String>(
+            r#"
+            type Query {
+              user(id: ID! = 1): String @deprecated
+            }
+        "#,
+        )
+        .expect("Failed to parse schema");
+
+        struct NoopTransformer {}
+        impl<'a, T: Text<'a> + Clone> SchemaTransformer<'a, T> for NoopTransformer {}
+
+        let mut transformer = NoopTransformer {};
+        assert!(transformer.transform_document(&raw).should_keep());
+    }
+}
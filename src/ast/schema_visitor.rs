@@ -1,187 +1,1060 @@
 use crate::static_graphql::schema::{
-    Definition, DirectiveDefinition, Document, EnumType, EnumValue, Field, InputObjectType,
-    InputValue, InterfaceType, ObjectType, ScalarType, SchemaDefinition, TypeDefinition, UnionType,
+    self, Definition, Directive, DirectiveDefinition, Document, EnumType, EnumValue, Field,
+    InputObjectType, InputValue, InterfaceType, ObjectType, ScalarType, SchemaDefinition, Type,
+    TypeDefinition, UnionType, Value,
 };
 
-/// A trait for implenenting a visitor for GraphQL schema definition.
-pub trait SchemaVisitor<T = ()> {
-    fn visit_schema_document(&self, document: &Document, _visitor_context: &mut T) {
-        self.enter_document(document, _visitor_context);
-
-        for definition in &document.definitions {
-            match definition {
-                Definition::SchemaDefinition(schema_definition) => {
-                    self.enter_schema_definition(schema_definition, _visitor_context);
-                    self.leave_schema_definition(schema_definition, _visitor_context);
+use super::{
+    type_extension_name, SchemaDocumentExtension, TypeDefinitionExtension, VisitorControl,
+};
+
+/// SchemaVisitor
+///
+/// Holds the type information of the node that is currently visited: the type (or type extension)
+/// that owns it, the field, the directive definition and the argument / input field.
+pub struct SchemaVisitorContext<'a> {
+    pub schema: &'a Document,
+
+    type_stack: Vec<&'a str>,
+    field_stack: Vec<&'a Field>,
+    directive_definition_stack: Vec<&'a DirectiveDefinition>,
+    input_value_stack: Vec<&'a InputValue>,
+}
+
+impl<'a> SchemaVisitorContext<'a> {
+    pub fn new(schema: &'a Document) -> Self {
+        SchemaVisitorContext {
+            schema,
+            type_stack: vec![],
+            field_stack: vec![],
+            directive_definition_stack: vec![],
+            input_value_stack: vec![],
+        }
+    }
+
+    pub fn with_type<R, Func>(&mut self, type_name: &'a str, func: Func) -> R
+    where
+        Func: FnOnce(&mut SchemaVisitorContext<'a>) -> R,
+    {
+        self.type_stack.push(type_name);
+        let result = func(self);
+        self.type_stack.pop();
+
+        result
+    }
+
+    pub fn with_field<R, Func>(&mut self, field: &'a Field, func: Func) -> R
+    where
+        Func: FnOnce(&mut SchemaVisitorContext<'a>) -> R,
+    {
+        self.field_stack.push(field);
+        let result = func(self);
+        self.field_stack.pop();
+
+        result
+    }
+
+    pub fn with_directive_definition<R, Func>(
+        &mut self,
+        directive_definition: &'a DirectiveDefinition,
+        func: Func,
+    ) -> R
+    where
+        Func: FnOnce(&mut SchemaVisitorContext<'a>) -> R,
+    {
+        self.directive_definition_stack.push(directive_definition);
+        let result = func(self);
+        self.directive_definition_stack.pop();
+
+        result
+    }
+
+    pub fn with_input_value<R, Func>(&mut self, input_value: &'a InputValue, func: Func) -> R
+    where
+        Func: FnOnce(&mut SchemaVisitorContext<'a>) -> R,
+    {
+        self.input_value_stack.push(input_value);
+        let result = func(self);
+        self.input_value_stack.pop();
+
+        result
+    }
+
+    /// The name of the type (or type extension) that is currently visited.
+    pub fn current_type_name(&self) -> Option<&'a str> {
+        self.type_stack.last().copied()
+    }
+
+    /// The definition of the type that is currently visited. For type extensions, this is the
+    /// extended type definition (if it exists in the schema).
+    pub fn current_type(&self) -> Option<&'a TypeDefinition> {
+        self.current_type_name()
+            .and_then(|name| self.schema.type_by_name(name))
+    }
+
+    pub fn current_field(&self) -> Option<&'a Field> {
+        self.field_stack.last().copied()
+    }
+
+    pub fn current_directive_definition(&self) -> Option<&'a DirectiveDefinition> {
+        self.directive_definition_stack.last().copied()
+    }
+
+    /// The argument (of a field or a directive definition) or input field that is currently visited.
+    pub fn current_input_value(&self) -> Option<&'a InputValue> {
+        self.input_value_stack.last().copied()
+    }
+}
+
+pub fn visit_schema_document<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    document: &'a Document,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_document(context, user_context, document);
+
+    if control.is_break() {
+        return;
+    }
+
+    if control.should_visit_children()
+        && visit_definitions(visitor, &document.definitions, context, user_context).is_break()
+    {
+        return;
+    }
+
+    visitor.leave_document(context, user_context, document);
+}
+
+fn visit_definitions<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    definitions: &'a [Definition],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for definition in definitions {
+        let control = match definition {
+            Definition::SchemaDefinition(schema_definition) => {
+                visit_schema_definition(visitor, schema_definition, context, user_context)
+            }
+            Definition::TypeDefinition(type_definition) => context
+                .with_type(type_definition.name(), |context| {
+                    visit_type_definition(visitor, type_definition, context, user_context)
+                }),
+            Definition::TypeExtension(type_extension) => context
+                .with_type(type_extension_name(type_extension), |context| {
+                    visit_type_extension(visitor, type_extension, context, user_context)
+                }),
+            Definition::DirectiveDefinition(directive_definition) => context
+                .with_directive_definition(directive_definition, |context| {
+                    visit_directive_definition(visitor, directive_definition, context, user_context)
+                }),
+        };
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    VisitorControl::Continue
+}
+
+fn visit_schema_definition<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    schema_definition: &'a SchemaDefinition,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_schema_definition(context, user_context, schema_definition);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && visit_directives(
+            visitor,
+            &schema_definition.directives,
+            context,
+            user_context,
+        )
+        .is_break()
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_schema_definition(context, user_context, schema_definition);
+
+    VisitorControl::Continue
+}
+
+fn visit_directive_definition<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    directive_definition: &'a DirectiveDefinition,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_directive_definition(context, user_context, directive_definition);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && visit_arguments(
+            visitor,
+            &directive_definition.arguments,
+            context,
+            user_context,
+        )
+        .is_break()
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_directive_definition(context, user_context, directive_definition);
+
+    VisitorControl::Continue
+}
+
+fn visit_type_definition<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    type_definition: &'a TypeDefinition,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_type_definition(context, user_context, type_definition);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children() {
+        let control = match type_definition {
+            TypeDefinition::Object(object) => {
+                visit_object_type(visitor, object, context, user_context)
+            }
+            TypeDefinition::Scalar(scalar) => {
+                visit_scalar_type(visitor, scalar, context, user_context)
+            }
+            TypeDefinition::Enum(enum_) => visit_enum_type(visitor, enum_, context, user_context),
+            TypeDefinition::Union(union) => visit_union_type(visitor, union, context, user_context),
+            TypeDefinition::InputObject(input_object) => {
+                visit_input_object_type(visitor, input_object, context, user_context)
+            }
+            TypeDefinition::Interface(interface) => {
+                visit_interface_type(visitor, interface, context, user_context)
+            }
+        };
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    visitor.leave_type_definition(context, user_context, type_definition);
+
+    VisitorControl::Continue
+}
+
+fn visit_object_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    object: &'a ObjectType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_object_type(context, user_context, object);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && (visit_directives(visitor, &object.directives, context, user_context).is_break()
+            || visit_fields(visitor, &object.fields, context, user_context).is_break())
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_object_type(context, user_context, object);
+
+    VisitorControl::Continue
+}
+
+fn visit_interface_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    interface: &'a InterfaceType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_interface_type(context, user_context, interface);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && (visit_directives(visitor, &interface.directives, context, user_context).is_break()
+            || visit_fields(visitor, &interface.fields, context, user_context).is_break())
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_interface_type(context, user_context, interface);
+
+    VisitorControl::Continue
+}
+
+fn visit_scalar_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    scalar: &'a ScalarType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_scalar_type(context, user_context, scalar);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && visit_directives(visitor, &scalar.directives, context, user_context).is_break()
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_scalar_type(context, user_context, scalar);
+
+    VisitorControl::Continue
+}
+
+fn visit_enum_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    enum_: &'a EnumType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_enum_type(context, user_context, enum_);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && (visit_directives(visitor, &enum_.directives, context, user_context).is_break()
+            || visit_enum_values(visitor, &enum_.values, context, user_context).is_break())
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_enum_type(context, user_context, enum_);
+
+    VisitorControl::Continue
+}
+
+fn visit_union_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    union: &'a UnionType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_union_type(context, user_context, union);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && (visit_directives(visitor, &union.directives, context, user_context).is_break()
+            || visit_union_members(visitor, &union.types, context, user_context).is_break())
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_union_type(context, user_context, union);
+
+    VisitorControl::Continue
+}
+
+fn visit_input_object_type<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    input_object: &'a InputObjectType,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_input_object_type(context, user_context, input_object);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children()
+        && (visit_directives(visitor, &input_object.directives, context, user_context).is_break()
+            || visit_input_fields(visitor, &input_object.fields, context, user_context).is_break())
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_input_object_type(context, user_context, input_object);
+
+    VisitorControl::Continue
+}
+
+fn visit_type_extension<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    type_extension: &'a schema::TypeExtension,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    let control = visitor.enter_type_extension(context, user_context, type_extension);
+
+    if control.is_break() {
+        return VisitorControl::Break;
+    }
+
+    if control.should_visit_children() {
+        let control = match type_extension {
+            schema::TypeExtension::Scalar(scalar) => {
+                visit_directives(visitor, &scalar.directives, context, user_context)
+            }
+            schema::TypeExtension::Object(object) => {
+                if visit_directives(visitor, &object.directives, context, user_context).is_break() {
+                    VisitorControl::Break
+                } else {
+                    visit_fields(visitor, &object.fields, context, user_context)
                 }
-                Definition::TypeDefinition(type_definition) => {
-                    self.enter_type_definition(type_definition, _visitor_context);
-
-                    match type_definition {
-                        TypeDefinition::Object(object) => {
-                            self.enter_object_type(object, _visitor_context);
-
-                            for field in &object.fields {
-                                self.enter_object_type_field(field, object, _visitor_context);
-                                // TODO: More advanced setup for fields: arguments, lists, null/non-null, directives
-                                self.leave_object_type_field(field, object, _visitor_context);
-                            }
-
-                            self.leave_object_type(object, _visitor_context);
-                        }
-                        TypeDefinition::Scalar(scalar) => {
-                            self.enter_scalar_type(scalar, _visitor_context);
-                            self.leave_scalar_type(scalar, _visitor_context);
-                        }
-                        TypeDefinition::Enum(enum_) => {
-                            self.enter_enum_type(enum_, _visitor_context);
-
-                            for value in &enum_.values {
-                                self.enter_enum_value(value, enum_, _visitor_context);
-                                self.leave_enum_value(value, enum_, _visitor_context);
-                            }
-
-                            self.leave_enum_type(enum_, _visitor_context);
-                        }
-                        TypeDefinition::Union(union) => {
-                            self.enter_union_type(union, _visitor_context);
-                            self.leave_union_type(union, _visitor_context);
-                        }
-                        TypeDefinition::InputObject(input_object) => {
-                            self.enter_input_object_type(input_object, _visitor_context);
-
-                            for field in &input_object.fields {
-                                self.enter_input_object_type_field(
-                                    field,
-                                    input_object,
-                                    _visitor_context,
-                                );
-                                self.leave_input_object_type_field(
-                                    field,
-                                    input_object,
-                                    _visitor_context,
-                                );
-                            }
-
-                            self.leave_input_object_type(input_object, _visitor_context);
-                        }
-                        TypeDefinition::Interface(interface) => {
-                            self.enter_interface_type(interface, _visitor_context);
-
-                            for field in &interface.fields {
-                                self.enter_interface_type_field(field, interface, _visitor_context);
-                                self.leave_interface_type_field(field, interface, _visitor_context);
-                            }
-
-                            self.leave_interface_type(interface, _visitor_context);
-                        }
-                    }
-
-                    self.leave_type_definition(type_definition, _visitor_context);
+            }
+            schema::TypeExtension::Interface(interface) => {
+                if visit_directives(visitor, &interface.directives, context, user_context)
+                    .is_break()
+                {
+                    VisitorControl::Break
+                } else {
+                    visit_fields(visitor, &interface.fields, context, user_context)
                 }
-                Definition::DirectiveDefinition(directive_definition) => {
-                    self.enter_directive_definition(directive_definition, _visitor_context);
-                    self.leave_directive_definition(directive_definition, _visitor_context);
+            }
+            schema::TypeExtension::Union(union) => {
+                if visit_directives(visitor, &union.directives, context, user_context).is_break() {
+                    VisitorControl::Break
+                } else {
+                    visit_union_members(visitor, &union.types, context, user_context)
+                }
+            }
+            schema::TypeExtension::Enum(enum_) => {
+                if visit_directives(visitor, &enum_.directives, context, user_context).is_break() {
+                    VisitorControl::Break
+                } else {
+                    visit_enum_values(visitor, &enum_.values, context, user_context)
                 }
-                Definition::TypeExtension(_type_extension) => {
-                    // TODO: implement this
-                    panic!("TypeExtension not supported at the moment");
+            }
+            schema::TypeExtension::InputObject(input_object) => {
+                if visit_directives(visitor, &input_object.directives, context, user_context)
+                    .is_break()
+                {
+                    VisitorControl::Break
+                } else {
+                    visit_input_fields(visitor, &input_object.fields, context, user_context)
                 }
             }
+        };
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    visitor.leave_type_extension(context, user_context, type_extension);
+
+    VisitorControl::Continue
+}
+
+fn visit_fields<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    fields: &'a [Field],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for field in fields {
+        let control = context.with_field(field, |context| {
+            let control = visitor.enter_field(context, user_context, field);
+
+            if control.is_break() {
+                return VisitorControl::Break;
+            }
+
+            if control.should_visit_children()
+                && (visit_type_reference(visitor, &field.field_type, context, user_context)
+                    .is_break()
+                    || visit_arguments(visitor, &field.arguments, context, user_context).is_break()
+                    || visit_directives(visitor, &field.directives, context, user_context)
+                        .is_break())
+            {
+                return VisitorControl::Break;
+            }
+
+            visitor.leave_field(context, user_context, field);
+
+            VisitorControl::Continue
+        });
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    VisitorControl::Continue
+}
+
+fn visit_arguments<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    arguments: &'a [InputValue],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for argument in arguments {
+        let control = context.with_input_value(argument, |context| {
+            let control = visitor.enter_argument(context, user_context, argument);
+
+            if control.is_break() {
+                return VisitorControl::Break;
+            }
+
+            if control.should_visit_children()
+                && visit_input_value_children(visitor, argument, context, user_context).is_break()
+            {
+                return VisitorControl::Break;
+            }
+
+            visitor.leave_argument(context, user_context, argument);
+
+            VisitorControl::Continue
+        });
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    VisitorControl::Continue
+}
+
+fn visit_input_fields<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    input_fields: &'a [InputValue],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for input_field in input_fields {
+        let control = context.with_input_value(input_field, |context| {
+            let control = visitor.enter_input_field(context, user_context, input_field);
+
+            if control.is_break() {
+                return VisitorControl::Break;
+            }
+
+            if control.should_visit_children()
+                && visit_input_value_children(visitor, input_field, context, user_context)
+                    .is_break()
+            {
+                return VisitorControl::Break;
+            }
+
+            visitor.leave_input_field(context, user_context, input_field);
+
+            VisitorControl::Continue
+        });
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+    }
+
+    VisitorControl::Continue
+}
+
+fn visit_input_value_children<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    input_value: &'a InputValue,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    if visit_type_reference(visitor, &input_value.value_type, context, user_context).is_break() {
+        return VisitorControl::Break;
+    }
+
+    if let Some(default_value) = &input_value.default_value {
+        let control = visitor.enter_default_value(context, user_context, default_value);
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+
+        visitor.leave_default_value(context, user_context, default_value);
+    }
+
+    visit_directives(visitor, &input_value.directives, context, user_context)
+}
+
+fn visit_enum_values<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    values: &'a [EnumValue],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for value in values {
+        let control = visitor.enter_enum_value(context, user_context, value);
+
+        if control.is_break() {
+            return VisitorControl::Break;
+        }
+
+        if control.should_visit_children()
+            && visit_directives(visitor, &value.directives, context, user_context).is_break()
+        {
+            return VisitorControl::Break;
         }
 
-        self.leave_document(document, _visitor_context);
+        visitor.leave_enum_value(context, user_context, value);
     }
 
-    fn enter_document(&self, _node: &Document, _visitor_context: &mut T) {}
-    fn leave_document(&self, _node: &Document, _visitor_context: &mut T) {}
+    VisitorControl::Continue
+}
+
+fn visit_union_members<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    members: &'a [String],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for member in members {
+        if visitor
+            .enter_union_member(context, user_context, member)
+            .is_break()
+        {
+            return VisitorControl::Break;
+        }
+
+        visitor.leave_union_member(context, user_context, member);
+    }
+
+    VisitorControl::Continue
+}
+
+fn visit_type_reference<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    type_reference: &'a Type,
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    if visitor
+        .enter_type_reference(context, user_context, type_reference)
+        .is_break()
+    {
+        return VisitorControl::Break;
+    }
+
+    visitor.leave_type_reference(context, user_context, type_reference);
+
+    VisitorControl::Continue
+}
+
+fn visit_directives<'a, Visitor, UserContext>(
+    visitor: &mut Visitor,
+    directives: &'a [Directive],
+    context: &mut SchemaVisitorContext<'a>,
+    user_context: &mut UserContext,
+) -> VisitorControl
+where
+    Visitor: SchemaVisitor<'a, UserContext>,
+{
+    for directive in directives {
+        if visitor
+            .enter_directive(context, user_context, directive)
+            .is_break()
+        {
+            return VisitorControl::Break;
+        }
+
+        visitor.leave_directive(context, user_context, directive);
+    }
+
+    VisitorControl::Continue
+}
+
+/// A trait for implenenting a visitor for GraphQL schema definition.
+///
+/// Use `visit_schema_document` to run the visitor. The `enter_*` methods return a `VisitorControl`
+/// that can skip the children of the visited node or stop the traversal.
+pub trait SchemaVisitor<'a, UserContext = ()> {
+    fn enter_document(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Document,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_document(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Document,
+    ) {
+    }
+
+    fn enter_schema_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a SchemaDefinition,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_schema_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a SchemaDefinition,
+    ) {
+    }
 
-    fn enter_schema_definition(&self, _node: &SchemaDefinition, _visitor_context: &mut T) {}
-    fn leave_schema_definition(&self, _node: &SchemaDefinition, _visitor_context: &mut T) {}
+    fn enter_directive_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a DirectiveDefinition,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_directive_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a DirectiveDefinition,
+    ) {
+    }
 
-    fn enter_directive_definition(&self, _node: &DirectiveDefinition, _visitor_context: &mut T) {}
-    fn leave_directive_definition(&self, _node: &DirectiveDefinition, _visitor_context: &mut T) {}
+    fn enter_type_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a TypeDefinition,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_type_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a TypeDefinition,
+    ) {
+    }
 
-    fn enter_type_definition(&self, _node: &TypeDefinition, _visitor_context: &mut T) {}
-    fn leave_type_definition(&self, _node: &TypeDefinition, _visitor_context: &mut T) {}
+    fn enter_type_extension(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a schema::TypeExtension,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_type_extension(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a schema::TypeExtension,
+    ) {
+    }
 
-    fn enter_interface_type(&self, _node: &InterfaceType, _visitor_context: &mut T) {}
-    fn leave_interface_type(&self, _node: &InterfaceType, _visitor_context: &mut T) {}
+    fn enter_interface_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InterfaceType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_interface_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InterfaceType,
+    ) {
+    }
 
-    fn enter_interface_type_field(
-        &self,
-        _node: &Field,
-        _type_: &InterfaceType,
-        _visitor_context: &mut T,
+    fn enter_object_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a ObjectType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_object_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a ObjectType,
     ) {
     }
-    fn leave_interface_type_field(
-        &self,
-        _node: &Field,
-        _type_: &InterfaceType,
-        _visitor_context: &mut T,
+
+    /// Called for the fields of object types, interface types and their extensions.
+    fn enter_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Field,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_field(&mut self, _: &mut SchemaVisitorContext<'a>, _: &mut UserContext, _: &'a Field) {
+    }
+
+    /// Called for the arguments of fields and directive definitions.
+    fn enter_argument(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputValue,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_argument(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputValue,
     ) {
     }
 
-    fn enter_object_type(&self, _node: &ObjectType, _visitor_context: &mut T) {}
-    fn leave_object_type(&self, _node: &ObjectType, _visitor_context: &mut T) {}
+    fn enter_input_object_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputObjectType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_input_object_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputObjectType,
+    ) {
+    }
 
-    fn enter_object_type_field(
-        &self,
-        _node: &Field,
-        _type_: &ObjectType,
-        _visitor_context: &mut T,
+    fn enter_input_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputValue,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_input_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a InputValue,
     ) {
     }
-    fn leave_object_type_field(
-        &self,
-        _node: &Field,
-        _type_: &ObjectType,
-        _visitor_context: &mut T,
+
+    /// Called for the default value of an argument or an input field.
+    fn enter_default_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Value,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_default_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Value,
     ) {
     }
 
-    fn enter_input_object_type(&self, _node: &InputObjectType, _visitor_context: &mut T) {}
-    fn leave_input_object_type(&self, _node: &InputObjectType, _visitor_context: &mut T) {}
+    /// Called for the type of a field, an argument or an input field.
+    fn enter_type_reference(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Type,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_type_reference(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Type,
+    ) {
+    }
 
-    fn enter_input_object_type_field(
-        &self,
-        _node: &InputValue,
-        _input_type: &InputObjectType,
-        _visitor_context: &mut T,
+    fn enter_union_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a UnionType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_union_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a UnionType,
     ) {
     }
-    fn leave_input_object_type_field(
-        &self,
-        _node: &InputValue,
-        _input_type: &InputObjectType,
-        _visitor_context: &mut T,
+
+    fn enter_union_member(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a String,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_union_member(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a String,
     ) {
     }
 
-    fn enter_union_type(&self, _node: &UnionType, _visitor_context: &mut T) {}
-    fn leave_union_type(&self, _node: &UnionType, _visitor_context: &mut T) {}
+    fn enter_scalar_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a ScalarType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_scalar_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a ScalarType,
+    ) {
+    }
 
-    fn enter_scalar_type(&self, _node: &ScalarType, _visitor_context: &mut T) {}
-    fn leave_scalar_type(&self, _node: &ScalarType, _visitor_context: &mut T) {}
+    fn enter_enum_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a EnumType,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_enum_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a EnumType,
+    ) {
+    }
 
-    fn enter_enum_type(&self, _node: &EnumType, _visitor_context: &mut T) {}
-    fn leave_enum_type(&self, _node: &EnumType, _visitor_context: &mut T) {}
+    fn enter_enum_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a EnumValue,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_enum_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a EnumValue,
+    ) {
+    }
 
-    fn enter_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
-    fn leave_enum_value(&self, _node: &EnumValue, _enum: &EnumType, _visitor_context: &mut T) {}
+    /// Called for every usage of a directive in the schema.
+    fn enter_directive(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Directive,
+    ) -> VisitorControl {
+        VisitorControl::Continue
+    }
+    fn leave_directive(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        _: &mut UserContext,
+        _: &'a Directive,
+    ) {
+    }
 }
 
-#[test]
-fn visit_schema() {
-    use graphql_parser::schema::parse_schema;
-    let schema_ast = parse_schema(
-        r#"
+#[cfg(test)]
+static TEST_VISITOR_SCHEMA: &str = r#"
     scalar Date
 
     type Query {
       user(id: ID!): User!
-      users(filter: UsersFilter): [User!]!
+      users(filter: UsersFilter, first: Int = 10): [User!]!
       now: Date
     }
 
@@ -210,149 +1083,220 @@ fn visit_schema() {
 
     union TestUnion = Test | User
 
-    "#,
-    )
-    .expect("Failed to parse schema");
+    directive @auth(requires: Role = ADMIN) on FIELD_DEFINITION
 
+    extend type User {
+      email: String @auth
+    }
+"#;
+
+#[test]
+fn visit_schema() {
+    use graphql_parser::schema::parse_schema;
+    let schema_ast = parse_schema(TEST_VISITOR_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+
+    #[derive(Default)]
     struct TestVisitorCollected {
         collected_object_type: Vec<String>,
         collected_scalar_type: Vec<String>,
         collected_union_type: Vec<String>,
+        collected_union_member: Vec<String>,
         collected_input_type: Vec<String>,
         collected_enum_type: Vec<String>,
         collected_enum_value: Vec<String>,
         collected_interface_type: Vec<String>,
-        collected_object_type_field: Vec<String>,
-        collected_interface_type_field: Vec<String>,
+        collected_field: Vec<String>,
+        collected_argument: Vec<String>,
         collected_input_type_fields: Vec<String>,
+        collected_default_value: Vec<String>,
+        collected_directive: Vec<String>,
+        collected_type_reference: Vec<String>,
     }
 
     struct TestVisitor;
 
-    impl TestVisitor {
-        fn collect_visited_info(&self, document: &Document) -> TestVisitorCollected {
-            let mut collected = TestVisitorCollected {
-                collected_object_type: Vec::new(),
-                collected_interface_type: Vec::new(),
-                collected_object_type_field: Vec::new(),
-                collected_interface_type_field: Vec::new(),
-                collected_scalar_type: Vec::new(),
-                collected_union_type: Vec::new(),
-                collected_enum_type: Vec::new(),
-                collected_enum_value: Vec::new(),
-                collected_input_type: Vec::new(),
-                collected_input_type_fields: Vec::new(),
-            };
-            self.visit_schema_document(document, &mut collected);
-
-            collected
+    impl<'a> SchemaVisitor<'a, TestVisitorCollected> for TestVisitor {
+        fn enter_object_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a ObjectType,
+        ) -> VisitorControl {
+            collected.collected_object_type.push(node.name.clone());
+            VisitorControl::Continue
         }
-    }
 
-    impl SchemaVisitor<TestVisitorCollected> for TestVisitor {
-        fn enter_object_type(
-            &self,
-            _node: &ObjectType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            _visitor_context
-                .collected_object_type
-                .push(_node.name.clone());
+        fn enter_field(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a Field,
+        ) -> VisitorControl {
+            let field_id = format!("{}.{}", context.current_type_name().unwrap(), node.name);
+            collected.collected_field.push(field_id);
+            VisitorControl::Continue
         }
 
-        fn enter_object_type_field(
-            &self,
-            _node: &Field,
-            _type_: &ObjectType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            let field_id = format!("{}.{}", _type_.name.as_str(), _node.name.as_str());
-            _visitor_context.collected_object_type_field.push(field_id);
+        fn enter_argument(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a InputValue,
+        ) -> VisitorControl {
+            let owner = match context.current_field() {
+                Some(field) => format!("{}.{}", context.current_type_name().unwrap(), field.name),
+                None => format!("@{}", context.current_directive_definition().unwrap().name),
+            };
+            collected
+                .collected_argument
+                .push(format!("{}({}:)", owner, node.name));
+            VisitorControl::Continue
         }
 
         fn enter_interface_type(
-            &self,
-            _node: &InterfaceType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            _visitor_context
-                .collected_interface_type
-                .push(_node.name.clone());
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a InterfaceType,
+        ) -> VisitorControl {
+            collected.collected_interface_type.push(node.name.clone());
+            VisitorControl::Continue
         }
 
-        fn enter_interface_type_field(
-            &self,
-            _node: &Field,
-            _type_: &InterfaceType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            _visitor_context
-                .collected_interface_type_field
-                .push(_node.name.clone());
+        fn enter_scalar_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a ScalarType,
+        ) -> VisitorControl {
+            collected.collected_scalar_type.push(node.name.clone());
+            VisitorControl::Continue
         }
 
-        fn enter_scalar_type(
-            &self,
-            _node: &ScalarType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            _visitor_context
-                .collected_scalar_type
-                .push(_node.name.clone());
+        fn enter_union_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a UnionType,
+        ) -> VisitorControl {
+            collected.collected_union_type.push(node.name.clone());
+            VisitorControl::Continue
         }
 
-        fn enter_union_type(&self, _node: &UnionType, _visitor_context: &mut TestVisitorCollected) {
-            _visitor_context
-                .collected_union_type
-                .push(_node.name.clone());
+        fn enter_union_member(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            member: &'a String,
+        ) -> VisitorControl {
+            collected.collected_union_member.push(member.clone());
+            VisitorControl::Continue
         }
 
-        fn enter_enum_type(&self, _node: &EnumType, _visitor_context: &mut TestVisitorCollected) {
-            _visitor_context
-                .collected_enum_type
-                .push(_node.name.clone());
+        fn enter_enum_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a EnumType,
+        ) -> VisitorControl {
+            collected.collected_enum_type.push(node.name.clone());
+            VisitorControl::Continue
         }
 
         fn enter_enum_value(
-            &self,
-            _node: &EnumValue,
-            _enum: &EnumType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            let enum_value_id = format!("{}.{}", _enum.name.as_str(), _node.name.as_str());
-            _visitor_context.collected_enum_value.push(enum_value_id);
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a EnumValue,
+        ) -> VisitorControl {
+            let enum_value_id = format!("{}.{}", context.current_type().name(), node.name);
+            collected.collected_enum_value.push(enum_value_id);
+            VisitorControl::Continue
         }
 
         fn enter_input_object_type(
-            &self,
-            _node: &InputObjectType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            _visitor_context
-                .collected_input_type
-                .push(_node.name.clone());
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a InputObjectType,
+        ) -> VisitorControl {
+            collected.collected_input_type.push(node.name.clone());
+            VisitorControl::Continue
         }
 
-        fn enter_input_object_type_field(
-            &self,
-            _node: &InputValue,
-            _input_type: &InputObjectType,
-            _visitor_context: &mut TestVisitorCollected,
-        ) {
-            let field_id = format!("{}.{}", _input_type.name.as_str(), _node.name.as_str());
-            _visitor_context.collected_input_type_fields.push(field_id);
+        fn enter_input_field(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            node: &'a InputValue,
+        ) -> VisitorControl {
+            let field_id = format!("{}.{}", context.current_type_name().unwrap(), node.name);
+            collected.collected_input_type_fields.push(field_id);
+            VisitorControl::Continue
+        }
+
+        fn enter_default_value(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            value: &'a Value,
+        ) -> VisitorControl {
+            collected.collected_default_value.push(format!(
+                "{}={}",
+                context.current_input_value().unwrap().name,
+                value
+            ));
+            VisitorControl::Continue
+        }
+
+        fn enter_directive(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            directive: &'a Directive,
+        ) -> VisitorControl {
+            collected.collected_directive.push(format!(
+                "{}.{}@{}",
+                context.current_type_name().unwrap(),
+                context.current_field().unwrap().name,
+                directive.name
+            ));
+            VisitorControl::Continue
+        }
+
+        fn enter_type_reference(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut TestVisitorCollected,
+            type_reference: &'a Type,
+        ) -> VisitorControl {
+            if context.current_type_name() == Some("Query")
+                && context.current_input_value().is_none()
+            {
+                collected
+                    .collected_type_reference
+                    .push(type_reference.to_string());
+            }
+            VisitorControl::Continue
         }
     }
 
-    let visitor = TestVisitor {};
-    let collected = visitor.collect_visited_info(&schema_ast);
+    let mut collected = TestVisitorCollected::default();
+    visit_schema_document(
+        &mut TestVisitor,
+        &schema_ast,
+        &mut SchemaVisitorContext::new(&schema_ast),
+        &mut collected,
+    );
 
     assert_eq!(
         collected.collected_object_type,
         vec!["Query", "User", "Test"]
     );
     assert_eq!(
-        collected.collected_object_type_field,
+        collected.collected_field,
         vec![
             "Query.user",
             "Query.users",
@@ -360,11 +1304,23 @@ fn visit_schema() {
             "User.id",
             "User.name",
             "User.role",
-            "Test.foo"
+            "Node.id",
+            "Test.foo",
+            "User.email"
+        ]
+    );
+    assert_eq!(
+        collected.collected_argument,
+        vec![
+            "Query.user(id:)",
+            "Query.users(filter:)",
+            "Query.users(first:)",
+            "@auth(requires:)"
         ]
     );
     assert_eq!(collected.collected_interface_type, vec!["Node"]);
     assert_eq!(collected.collected_union_type, vec!["TestUnion"]);
+    assert_eq!(collected.collected_union_member, vec!["Test", "User"]);
     assert_eq!(collected.collected_scalar_type, vec!["Date"]);
     assert_eq!(collected.collected_enum_type, vec!["Role"]);
     assert_eq!(
@@ -376,4 +1332,135 @@ fn visit_schema() {
         collected.collected_input_type_fields,
         vec!["UsersFilter.name"]
     );
+    assert_eq!(
+        collected.collected_default_value,
+        vec!["first=10", "requires=ADMIN"]
+    );
+    assert_eq!(collected.collected_directive, vec!["User.email@auth"]);
+    assert_eq!(
+        collected.collected_type_reference,
+        vec!["User!", "[User!]!", "Date"]
+    );
+}
+
+#[test]
+fn visit_schema_skip_children() {
+    use graphql_parser::schema::parse_schema;
+    let schema_ast = parse_schema(TEST_VISITOR_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+
+    struct SkipQueryVisitor;
+
+    impl<'a> SchemaVisitor<'a, Vec<String>> for SkipQueryVisitor {
+        fn enter_object_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            _: &mut Vec<String>,
+            node: &'a ObjectType,
+        ) -> VisitorControl {
+            if node.name == "Query" {
+                VisitorControl::SkipChildren
+            } else {
+                VisitorControl::Continue
+            }
+        }
+
+        fn leave_object_type(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            collected: &mut Vec<String>,
+            node: &'a ObjectType,
+        ) {
+            collected.push(format!("leave {}", node.name));
+        }
+
+        fn enter_field(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            collected: &mut Vec<String>,
+            node: &'a Field,
+        ) -> VisitorControl {
+            collected.push(format!(
+                "{}.{}",
+                context.current_type_name().unwrap(),
+                node.name
+            ));
+            VisitorControl::Continue
+        }
+    }
+
+    let mut collected = vec![];
+    visit_schema_document(
+        &mut SkipQueryVisitor,
+        &schema_ast,
+        &mut SchemaVisitorContext::new(&schema_ast),
+        &mut collected,
+    );
+
+    assert_eq!(
+        collected,
+        vec![
+            "leave Query",
+            "User.id",
+            "User.name",
+            "User.role",
+            "leave User",
+            "Node.id",
+            "Test.foo",
+            "leave Test",
+            "User.email"
+        ]
+    );
+}
+
+#[test]
+fn visit_schema_break() {
+    use graphql_parser::schema::parse_schema;
+    let schema_ast = parse_schema(TEST_VISITOR_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+
+    struct FindFirstNonNullListVisitor;
+
+    impl<'a> SchemaVisitor<'a, Option<String>> for FindFirstNonNullListVisitor {
+        fn enter_field(
+            &mut self,
+            context: &mut SchemaVisitorContext<'a>,
+            found: &mut Option<String>,
+            node: &'a Field,
+        ) -> VisitorControl {
+            if let Type::NonNullType(inner) = &node.field_type {
+                if let Type::ListType(_) = inner.as_ref() {
+                    *found = Some(format!(
+                        "{}.{}",
+                        context.current_type_name().unwrap(),
+                        node.name
+                    ));
+                    return VisitorControl::Break;
+                }
+            }
+
+            VisitorControl::Continue
+        }
+
+        fn leave_document(
+            &mut self,
+            _: &mut SchemaVisitorContext<'a>,
+            _: &mut Option<String>,
+            _: &'a Document,
+        ) {
+            panic!("leave_document should not be called after a break");
+        }
+    }
+
+    let mut found = None;
+    visit_schema_document(
+        &mut FindFirstNonNullListVisitor,
+        &schema_ast,
+        &mut SchemaVisitorContext::new(&schema_ast),
+        &mut found,
+    );
+
+    assert_eq!(found, Some("Query.users".to_string()));
 }
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+
+use super::{
+    collect_directives_variables_in_use, collect_fragments_in_use, collect_variables_in_use,
+    OperationDefinitionExtension, OperationTransformer, Transformed, TransformedValue,
+};
+use crate::static_graphql::query::{
+    Definition, Directive, Document, Field, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, Value,
+};
+
+/// Evaluates the `@skip(if:)` and `@include(if:)` directives of a selection, with the given
+/// variable values.
+///
+/// Returns `Some(false)` when the selection is excluded, `Some(true)` when it is included, and
+/// `None` when it depends on a condition that cannot be evaluated (a variable without a boolean
+/// value).
+pub fn evaluate_skip_include(
+    directives: &[Directive],
+    variables: &HashMap<String, JsonValue>,
+) -> Option<bool> {
+    let mut included = Some(true);
+
+    for directive in directives {
+        let skip = match directive.name.as_str() {
+            "skip" => true,
+            "include" => false,
+            _ => continue,
+        };
+
+        let condition = directive
+            .arguments
+            .iter()
+            .find(|(name, _)| name == "if")
+            .and_then(|(_, value)| match value {
+                Value::Boolean(value) => Some(*value),
+                Value::Variable(name) => variables.get(name).and_then(JsonValue::as_bool),
+                _ => None,
+            });
+
+        match condition {
+            Some(condition) if condition == skip => return Some(false),
+            Some(_) => {}
+            None => included = None,
+        }
+    }
+
+    included
+}
+
+/// Removes the selections excluded by `@skip` and `@include` from a document (see
+/// `SkipIncludeEvaluator`), then removes the variable definitions and the fragments that are not
+/// used anymore.
+///
+/// The default values of the variable definitions are used for the variables without a value:
+/// each operation is evaluated with its own defaults, and a fragment with the defaults shared by
+/// all the operations using it.
+pub fn apply_skip_include(document: &Document, variables: &HashMap<String, JsonValue>) -> Document {
+    let known_fragments: HashMap<&str, &FragmentDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    // The variables of each operation, and the operations using each fragment
+    let mut operations: Vec<(Vec<&str>, HashMap<String, JsonValue>)> = vec![];
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            let mut fragments_in_use: Vec<&str> = vec![];
+            collect_fragments_in_use(
+                operation.selection_set(),
+                &known_fragments,
+                &mut fragments_in_use,
+            );
+
+            operations.push((fragments_in_use, with_default_values(operation, variables)));
+        }
+    }
+
+    let mut operations_variables = operations.iter().map(|(_, variables)| variables);
+    let definitions = document
+        .definitions
+        .iter()
+        .map(|definition| {
+            let definition_variables = match definition {
+                Definition::Operation(_) => operations_variables.next().unwrap().clone(),
+                Definition::Fragment(fragment) => shared_variables(
+                    operations
+                        .iter()
+                        .filter(|(fragments_in_use, _)| {
+                            fragments_in_use.contains(&fragment.name.as_str())
+                        })
+                        .map(|(_, variables)| variables),
+                    variables,
+                ),
+            };
+
+            match SkipIncludeEvaluator::new(&definition_variables).transform_definition(definition)
+            {
+                Transformed::Keep => definition.clone(),
+                Transformed::Replace(definition) => definition,
+            }
+        })
+        .collect();
+
+    remove_unused_definitions(&Document { definitions })
+}
+
+/// Returns the variable values, with the boolean default values of the operation for the variables
+/// without a value.
+fn with_default_values(
+    operation: &OperationDefinition,
+    variables: &HashMap<String, JsonValue>,
+) -> HashMap<String, JsonValue> {
+    let mut variables = variables.clone();
+
+    for variable_definition in operation.variable_definitions() {
+        if let Some(Value::Boolean(value)) = &variable_definition.default_value {
+            variables
+                .entry(variable_definition.name.clone())
+                .or_insert(JsonValue::Bool(*value));
+        }
+    }
+
+    variables
+}
+
+/// Returns the variable values that are the same in all the given operations variables (or the
+/// given values, when no operation is given).
+fn shared_variables<'a>(
+    mut operations_variables: impl Iterator<Item = &'a HashMap<String, JsonValue>>,
+    variables: &HashMap<String, JsonValue>,
+) -> HashMap<String, JsonValue> {
+    let mut shared = match operations_variables.next() {
+        Some(first) => first.clone(),
+        None => return variables.clone(),
+    };
+
+    for operation_variables in operations_variables {
+        shared.retain(|name, value| operation_variables.get(name) == Some(value));
+    }
+
+    shared
+}
+
+/// An `OperationTransformer` evaluating the `@skip` and `@include` directives of fields, fragment
+/// spreads and inline fragments with the given variable values. The excluded selections are
+/// removed, and the directives are removed from the included selections.
+///
+/// Selections depending on conditions that cannot be evaluated are kept as is. A selection set
+/// where all the selections are excluded is replaced with `__typename`, to remain valid.
+pub struct SkipIncludeEvaluator<'a> {
+    variables: &'a HashMap<String, JsonValue>,
+}
+
+impl<'a> SkipIncludeEvaluator<'a> {
+    pub fn new(variables: &'a HashMap<String, JsonValue>) -> Self {
+        SkipIncludeEvaluator { variables }
+    }
+
+    fn without_skip_include(directives: &[Directive]) -> Vec<Directive> {
+        directives
+            .iter()
+            .filter(|directive| directive.name != "skip" && directive.name != "include")
+            .cloned()
+            .collect()
+    }
+}
+
+impl<'a> OperationTransformer<'static, String> for SkipIncludeEvaluator<'a> {
+    fn transform_selection_set(
+        &mut self,
+        selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        let mut items = Vec::with_capacity(selections.items.len());
+        let mut has_changes = false;
+
+        for selection in &selections.items {
+            let directives = match selection {
+                Selection::Field(field) => &field.directives,
+                Selection::FragmentSpread(spread) => &spread.directives,
+                Selection::InlineFragment(fragment) => &fragment.directives,
+            };
+
+            let selection = match evaluate_skip_include(directives, self.variables) {
+                Some(false) => {
+                    has_changes = true;
+                    continue;
+                }
+                Some(true) if directives.len() != Self::without_skip_include(directives).len() => {
+                    has_changes = true;
+
+                    let mut selection = selection.clone();
+                    match &mut selection {
+                        Selection::Field(field) => {
+                            field.directives = Self::without_skip_include(&field.directives)
+                        }
+                        Selection::FragmentSpread(spread) => {
+                            spread.directives = Self::without_skip_include(&spread.directives)
+                        }
+                        Selection::InlineFragment(fragment) => {
+                            fragment.directives = Self::without_skip_include(&fragment.directives)
+                        }
+                    }
+                    selection
+                }
+                _ => selection.clone(),
+            };
+
+            match self.transform_selection(&selection) {
+                Transformed::Keep => items.push(selection),
+                Transformed::Replace(selection) => {
+                    has_changes = true;
+                    items.push(selection)
+                }
+            }
+        }
+
+        if !has_changes {
+            return TransformedValue::Keep;
+        }
+
+        if items.is_empty() && !selections.items.is_empty() {
+            items.push(Selection::Field(Field {
+                position: selections.span.0,
+                alias: None,
+                name: "__typename".to_string(),
+                arguments: vec![],
+                directives: vec![],
+                selection_set: SelectionSet {
+                    span: (selections.span.0, selections.span.0),
+                    items: vec![],
+                },
+            }));
+        }
+
+        TransformedValue::Replace(items)
+    }
+}
+
+/// Removes the variable definitions not used by their operation, and the fragments not used by
+/// any operation.
+fn remove_unused_definitions(document: &Document) -> Document {
+    let known_fragments: HashMap<&str, &FragmentDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    let mut fragments_in_use: Vec<&str> = vec![];
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            collect_fragments_in_use(
+                operation.selection_set(),
+                &known_fragments,
+                &mut fragments_in_use,
+            );
+        }
+    }
+
+    let definitions = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => {
+                let mut variables_in_use: Vec<&str> = vec![];
+                collect_directives_variables_in_use(operation.directives(), &mut variables_in_use);
+                collect_variables_in_use(
+                    operation.selection_set(),
+                    &known_fragments,
+                    &mut variables_in_use,
+                );
+
+                let mut operation = operation.clone();
+                let variable_definitions = match &mut operation {
+                    OperationDefinition::Query(query) => &mut query.variable_definitions,
+                    OperationDefinition::Mutation(mutation) => &mut mutation.variable_definitions,
+                    OperationDefinition::Subscription(subscription) => {
+                        &mut subscription.variable_definitions
+                    }
+                    OperationDefinition::SelectionSet(_) => return Some(definition.clone()),
+                };
+                variable_definitions.retain(|variable_definition| {
+                    variables_in_use.contains(&variable_definition.name.as_str())
+                });
+
+                Some(Definition::Operation(operation))
+            }
+            Definition::Fragment(fragment) => {
+                if fragments_in_use.contains(&fragment.name.as_str()) {
+                    Some(definition.clone())
+                } else {
+                    None
+                }
+            }
+        })
+        .collect();
+
+    Document { definitions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(operation: &str, variables: serde_json::Value) -> String {
+        let document = graphql_parser::parse_query(operation)
+            .expect("Failed to parse query")
+            .into_static();
+        let variables = match variables {
+            JsonValue::Object(variables) => variables.into_iter().collect(),
+            _ => HashMap::new(),
+        };
+
+        apply_skip_include(&document, &variables).to_string()
+    }
+
+    #[test]
+    fn remove_excluded_selections() {
+        assert_eq!(
+            apply(
+                "
+query Dog($skipName: Boolean!, $withMother: Boolean = false, $unknown: Boolean, $id: ID) {
+  dog(id: $id) {
+    name @skip(if: $skipName)
+    nickname @include(if: true) @deprecated
+    barkVolume @include(if: $unknown)
+    ...MotherFields @include(if: $withMother)
+    ... on Dog @skip(if: false) {
+      ...Names
+    }
+  }
+  cat {
+    name @skip(if: true)
+  }
+}
+
+fragment MotherFields on Dog {
+  mother {
+    name
+  }
+}
+
+fragment Names on Dog {
+  name
+}
+",
+                serde_json::json!({ "skipName": true }),
+            ),
+            "query Dog($unknown: Boolean, $id: ID) {\n  dog(id: $id) {\n    nickname @deprecated\n    barkVolume @include(if: $unknown)\n    ... on Dog {\n      ...Names\n    }\n  }\n  cat {\n    __typename\n  }\n}\n\nfragment Names on Dog {\n  name\n}\n"
+        );
+    }
+
+    #[test]
+    fn use_default_values_per_operation() {
+        assert_eq!(
+            apply(
+                "
+query A($x: Boolean = true) {
+  dog {
+    name @include(if: $x)
+    ...Fields
+  }
+}
+
+query B($x: Boolean = false) {
+  dog {
+    name @include(if: $x)
+    ...Fields
+  }
+}
+
+fragment Fields on Dog {
+  nickname @include(if: $x)
+}
+",
+                serde_json::json!({}),
+            ),
+            "query A($x: Boolean = true) {\n  dog {\n    name\n    ...Fields\n  }\n}\n\nquery B($x: Boolean = false) {\n  dog {\n    ...Fields\n  }\n}\n\nfragment Fields on Dog {\n  nickname @include(if: $x)\n}\n"
+        );
+    }
+
+    #[test]
+    fn evaluate_conditions() {
+        let variables = HashMap::from([("yes".to_string(), JsonValue::Bool(true))]);
+        let directives = |source: &str| {
+            let document = graphql_parser::parse_query(&format!("{{ a {} }}", source))
+                .unwrap()
+                .into_static();
+
+            match &document.definitions[0] {
+                Definition::Operation(operation) => match &operation.selection_set().items[0] {
+                    Selection::Field(field) => field.directives.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(
+            evaluate_skip_include(&directives(""), &variables),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_skip_include(&directives("@skip(if: $yes)"), &variables),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate_skip_include(
+                &directives("@include(if: $yes) @skip(if: false)"),
+                &variables
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_skip_include(&directives("@include(if: $no)"), &variables),
+            None
+        );
+        assert_eq!(
+            evaluate_skip_include(&directives("@include(if: $no) @skip(if: true)"), &variables),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use super::{
+    FieldByNameExtension, OperationTransformer, SchemaDocumentExtension, Transformed,
+    TransformedValue, TypeExtension,
+};
+use crate::static_graphql::{
+    query::{
+        Document, Field, FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
+        Selection, SelectionSet, TypeCondition,
+    },
+    schema::{self, TypeDefinition},
+};
+
+/// The rewrites of an operation transformer that needs the types of the selection sets it
+/// transforms. It is run wrapped in a `TypeAware`, which keeps track of these types.
+///
+/// Each method is called with the wrapper: `current_type()` is the type of the selection set
+/// being transformed, the state of the transformer is `inner`, and the `default_transform_*`
+/// methods of the wrapper transform the children with their types.
+pub trait TypeAwareTransformer<'a>: Sized {
+    fn transform_document(
+        transformer: &mut TypeAware<'a, Self>,
+        document: &Document,
+    ) -> TransformedValue<Document> {
+        transformer.default_transform_document(document)
+    }
+
+    fn transform_selection_set(
+        transformer: &mut TypeAware<'a, Self>,
+        selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        transformer.transform_list(&selections.items, TypeAware::transform_selection)
+    }
+
+    /// Called with the type of the field as the current type.
+    fn transform_field(
+        transformer: &mut TypeAware<'a, Self>,
+        field: &Field,
+    ) -> Transformed<Selection> {
+        transformer.default_transform_field(field)
+    }
+
+    /// Called with the type of the parent selection set as the current type.
+    fn transform_fragment_spread(
+        transformer: &mut TypeAware<'a, Self>,
+        spread: &FragmentSpread,
+    ) -> Transformed<Selection> {
+        transformer.default_transform_fragment_spread(spread)
+    }
+}
+
+/// An `OperationTransformer` running a `TypeAwareTransformer`, and keeping track of the type of
+/// the selection set being transformed: the root type of the operations, the type condition of
+/// the fragments and inline fragments, and the types of the fields. The type is `None` when it is
+/// not defined in the schema.
+pub struct TypeAware<'a, T> {
+    pub schema: &'a schema::Document,
+    pub inner: T,
+    types: Vec<Option<&'a TypeDefinition>>,
+}
+
+impl<'a, T> TypeAware<'a, T> {
+    pub fn new(schema: &'a schema::Document, inner: T) -> Self {
+        TypeAware {
+            schema,
+            inner,
+            types: vec![],
+        }
+    }
+
+    /// The type of the selection set being transformed.
+    pub fn current_type(&self) -> Option<&'a TypeDefinition> {
+        self.types.last().copied().flatten()
+    }
+
+    /// Runs `f` with the given type as the type of the selection set being transformed (like the
+    /// type condition of a fragment being inlined).
+    pub fn with_type<R>(&mut self, type_name: &str, f: impl FnOnce(&mut Self) -> R) -> R {
+        let type_definition = self.schema.type_by_name(type_name);
+
+        self.with(type_definition, f)
+    }
+
+    fn with<R>(
+        &mut self,
+        type_definition: Option<&'a TypeDefinition>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.types.push(type_definition);
+        let result = f(self);
+        self.types.pop();
+
+        result
+    }
+}
+
+impl<'a, T: TypeAwareTransformer<'a>> OperationTransformer<'static, String> for TypeAware<'a, T> {
+    fn transform_document(&mut self, document: &Document) -> TransformedValue<Document> {
+        T::transform_document(self, document)
+    }
+
+    fn transform_operation(
+        &mut self,
+        operation: &OperationDefinition,
+    ) -> Transformed<OperationDefinition> {
+        let root_type = self
+            .schema
+            .type_by_name(self.schema.root_type_name(operation));
+
+        self.with(root_type, |transformer| {
+            transformer.default_transform_operation(operation)
+        })
+    }
+
+    fn transform_fragment(
+        &mut self,
+        fragment: &FragmentDefinition,
+    ) -> Transformed<FragmentDefinition> {
+        let TypeCondition::On(type_name) = &fragment.type_condition;
+
+        self.with_type(type_name, |transformer| {
+            transformer.default_transform_fragment(fragment)
+        })
+    }
+
+    fn transform_selection_set(
+        &mut self,
+        selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        T::transform_selection_set(self, selections)
+    }
+
+    fn transform_field(&mut self, field: &Field) -> Transformed<Selection> {
+        let field_type = self
+            .current_type()
+            .and_then(|parent_type| parent_type.field_by_name(&field.name))
+            .and_then(|field| self.schema.type_by_name(field.field_type.inner_type()));
+
+        self.with(field_type, |transformer| {
+            T::transform_field(transformer, field)
+        })
+    }
+
+    fn transform_inline_fragment(&mut self, fragment: &InlineFragment) -> Transformed<Selection> {
+        let fragment_type = match &fragment.type_condition {
+            Some(TypeCondition::On(type_name)) => self.schema.type_by_name(type_name),
+            None => self.current_type(),
+        };
+
+        self.with(fragment_type, |transformer| {
+            transformer.default_transform_inline_fragment(fragment)
+        })
+    }
+
+    fn transform_fragment_spread(&mut self, spread: &FragmentSpread) -> Transformed<Selection> {
+        T::transform_fragment_spread(self, spread)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{with_builtin_definitions, TypeDefinitionExtension};
+    use crate::validation::test_utils::TEST_SCHEMA;
+
+    /// Records the type of each field.
+    struct FieldTypes(Vec<String>);
+
+    impl<'a> TypeAwareTransformer<'a> for FieldTypes {
+        fn transform_field(
+            transformer: &mut TypeAware<'a, Self>,
+            field: &Field,
+        ) -> Transformed<Selection> {
+            let type_name = transformer
+                .current_type()
+                .map_or("?", |field_type| field_type.name())
+                .to_string();
+            transformer
+                .inner
+                .0
+                .push(format!("{}: {}", field.name, type_name));
+
+            transformer.default_transform_field(field)
+        }
+    }
+
+    #[test]
+    fn track_selection_set_types() {
+        let schema = with_builtin_definitions(
+            &graphql_parser::parse_schema(TEST_SCHEMA)
+                .expect("Failed to parse schema")
+                .into_static(),
+        );
+        let document = graphql_parser::parse_query(
+            "
+{
+  pet {
+    ... on Dog { mother { name } }
+    ...CatFields
+  }
+}
+
+fragment CatFields on Cat {
+  meowsVolume
+  unknown
+}
+",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let mut transformer = TypeAware::new(&schema, FieldTypes(vec![]));
+        transformer.transform_document(&document);
+
+        assert_eq!(
+            transformer.inner.0,
+            vec![
+                "pet: Pet",
+                "mother: Dog",
+                "name: String",
+                "meowsVolume: Int",
+                "unknown: ?"
+            ]
+        );
+    }
+}
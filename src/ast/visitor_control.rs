@@ -0,0 +1,21 @@
+/// Returned from the `enter_*` methods of a visitor, and controls how the traversal continues.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VisitorControl {
+    /// Visit the children of the current node, and continue the traversal.
+    #[default]
+    Continue,
+    /// Do not visit the children of the current node. The matching `leave_*` method is still called.
+    SkipChildren,
+    /// Stop the traversal. No other `enter_*` / `leave_*` methods are called.
+    Break,
+}
+
+impl VisitorControl {
+    pub fn is_break(&self) -> bool {
+        matches!(self, VisitorControl::Break)
+    }
+
+    pub fn should_visit_children(&self) -> bool {
+        matches!(self, VisitorControl::Continue)
+    }
+}
@@ -0,0 +1,123 @@
+use graphql_tools::ast::{completions_at, Completion, CompletionKind};
+use graphql_tools::static_graphql::schema;
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionItemTag, Documentation, Position};
+
+use crate::position::from_lsp_position;
+
+/// Returns the completions at the cursor (see `graphql_tools::ast::completions_at`): the fields of
+/// the type of a selection set, the directives, the arguments of a field or a directive, the
+/// fields of an input object, and the values of an enum or boolean input.
+pub fn completions(
+    schema: &schema::Document,
+    source: &str,
+    position: Position,
+) -> Vec<CompletionItem> {
+    completions_at(schema, source, from_lsp_position(source, position))
+        .into_iter()
+        .map(completion_item)
+        .collect()
+}
+
+fn completion_item(completion: Completion) -> CompletionItem {
+    let kind = match completion.kind {
+        CompletionKind::Field => CompletionItemKind::FIELD,
+        CompletionKind::Argument | CompletionKind::InputField => CompletionItemKind::PROPERTY,
+        CompletionKind::EnumValue => CompletionItemKind::ENUM_MEMBER,
+        CompletionKind::Value => CompletionItemKind::KEYWORD,
+        CompletionKind::Directive => CompletionItemKind::FUNCTION,
+    };
+
+    CompletionItem {
+        label: completion.label,
+        kind: Some(kind),
+        detail: completion.detail,
+        documentation: completion.description.map(Documentation::String),
+        tags: completion
+            .deprecation_reason
+            .map(|_| vec![CompletionItemTag::DEPRECATED]),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphql_tools::ast::with_builtin_definitions;
+
+    static SCHEMA: &str = "
+type Query {
+  dog: Dog
+}
+
+type Dog {
+  \"The name of the dog\"
+  name: String
+  barkVolume: Int @deprecated
+  doesKnowCommand(dogCommand: DogCommand!): Boolean
+}
+
+enum DogCommand {
+  SIT
+  DOWN
+}
+";
+
+    fn completions_of(source: &str, position: Position) -> Vec<CompletionItem> {
+        let schema = with_builtin_definitions(
+            &graphql_parser::parse_schema::<String>(SCHEMA)
+                .unwrap()
+                .into_static(),
+        );
+
+        completions(&schema, source, position)
+    }
+
+    fn labels(source: &str, position: Position) -> Vec<String> {
+        completions_of(source, position)
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn complete_fields() {
+        let fields = vec!["name", "barkVolume", "doesKnowCommand", "__typename"];
+
+        assert_eq!(labels("{ dog {  } }", Position::new(0, 8)), fields);
+        assert_eq!(labels("{\tdog { na } }", Position::new(0, 10)), fields);
+        assert_eq!(
+            labels("query {\n  dog {\n    na", Position::new(2, 6)),
+            fields
+        );
+        assert_eq!(
+            labels("{ dog }", Position::new(0, 4)),
+            vec!["dog", "__schema", "__type", "__typename"]
+        );
+
+        let items = completions_of("{ dog {  } }", Position::new(0, 8));
+        assert_eq!(
+            items[0].documentation,
+            Some(Documentation::String("The name of the dog".to_string()))
+        );
+        assert_eq!(items[1].tags, Some(vec![CompletionItemTag::DEPRECATED]));
+    }
+
+    #[test]
+    fn complete_arguments_and_values() {
+        assert_eq!(
+            labels("{ dog { doesKnowCommand() } }", Position::new(0, 24)),
+            vec!["dogCommand"]
+        );
+        assert_eq!(
+            labels(
+                "{ dog { doesKnowCommand(dogCommand: ) } }",
+                Position::new(0, 36)
+            ),
+            vec!["SIT", "DOWN"]
+        );
+        assert_eq!(
+            labels("{ dog @include(if: ) { name } }", Position::new(0, 19)),
+            vec!["true", "false"]
+        );
+    }
+}
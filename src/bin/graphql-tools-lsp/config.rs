@@ -0,0 +1,113 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_with::{serde_as, OneOrMany};
+
+/// The name of the configuration file, looked up at the root of the workspace.
+pub const CONFIG_FILE_NAME: &str = ".graphqlrc.json";
+
+/// The configuration of a project:
+///
+/// ```json
+/// {
+///   "schema": "schema.graphql",
+///   "documents": ["src/queries", "src/fragments.graphql"]
+/// }
+/// ```
+///
+/// Both `schema` and `documents` take a path or a list of paths, relative to the workspace root.
+/// A directory stands for all the `.graphql`, `.graphqls` and `.gql` files it contains.
+#[serde_as]
+#[derive(Deserialize, Debug, Default, PartialEq)]
+pub struct Config {
+    #[serde_as(as = "OneOrMany<_>")]
+    pub schema: Vec<String>,
+    #[serde_as(as = "OneOrMany<_>")]
+    #[serde(default)]
+    pub documents: Vec<String>,
+}
+
+impl Config {
+    /// Reads the configuration file of a workspace.
+    pub fn load(root: &Path) -> Result<Config, String> {
+        let path = root.join(CONFIG_FILE_NAME);
+        let content = fs::read_to_string(&path)
+            .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+        serde_json::from_str(&content)
+            .map_err(|error| format!("Failed to parse {}: {}", path.display(), error))
+    }
+
+    pub fn schema_files(&self, root: &Path) -> io::Result<Vec<PathBuf>> {
+        expand_paths(root, &self.schema)
+    }
+
+    pub fn document_files(&self, root: &Path) -> io::Result<Vec<PathBuf>> {
+        expand_paths(root, &self.documents)
+    }
+}
+
+fn expand_paths(root: &Path, paths: &[String]) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for path in paths {
+        collect_files(&root.join(path), &mut files)?;
+    }
+
+    Ok(files)
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else if is_graphql_file(&entry) {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_graphql_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("graphql" | "graphqls" | "gql")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config: Config = serde_json::from_str(
+            r#"{ "schema": "schema.graphql", "documents": ["queries", "fragments.gql"] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                schema: vec!["schema.graphql".to_string()],
+                documents: vec!["queries".to_string(), "fragments.gql".to_string()],
+            }
+        );
+
+        let config: Config = serde_json::from_str(r#"{ "schema": ["a.graphql"] }"#).unwrap();
+        assert!(config.documents.is_empty());
+    }
+}
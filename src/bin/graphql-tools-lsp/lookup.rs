@@ -0,0 +1,302 @@
+use graphql_parser::Pos;
+use graphql_tools::ast::{
+    visit_document, DeprecationExtension, FieldByNameExtension, OperationVisitor,
+    OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension,
+};
+use graphql_tools::static_graphql::query::{Field, FragmentSpread};
+use graphql_tools::static_graphql::schema::{self, TypeDefinition};
+use lsp_types::{
+    GotoDefinitionResponse, Hover, HoverContents, Location, MarkupContent, MarkupKind, Position,
+};
+
+use crate::position::{definition_name_range, name_at};
+use crate::workspace::Workspace;
+
+/// The node of a document under the cursor.
+pub enum Node<'a> {
+    Field {
+        parent_type: &'a TypeDefinition,
+        field: &'a schema::Field,
+    },
+    FragmentSpread(String),
+    Type(&'a TypeDefinition),
+}
+
+/// Finds the node named `name`, starting at `start`. The nodes are recorded by name, as the
+/// context of the visitor does not outlive the document.
+struct NodeFinder<'n> {
+    name: &'n str,
+    start: Pos,
+}
+
+enum FoundNode {
+    Field { parent_type: String },
+    FragmentSpread,
+}
+
+impl<'a, 'n> OperationVisitor<'a, Option<FoundNode>> for NodeFinder<'n> {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        found: &mut Option<FoundNode>,
+        field: &Field,
+    ) {
+        if field.name == self.name
+            && field.position.line == self.start.line
+            && field.position.column <= self.start.column
+        {
+            if let Some(parent_type) = visitor_context.current_parent_type() {
+                *found = Some(FoundNode::Field {
+                    parent_type: parent_type.name().to_string(),
+                });
+            }
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        found: &mut Option<FoundNode>,
+        fragment_spread: &FragmentSpread,
+    ) {
+        if fragment_spread.fragment_name == self.name
+            && fragment_spread.position.line == self.start.line
+            && fragment_spread.position.column <= self.start.column
+        {
+            *found = Some(FoundNode::FragmentSpread);
+        }
+    }
+}
+
+/// Returns the node of the document under the cursor: a field, a fragment spread, or a type name
+/// (in a type condition or a variable definition).
+pub fn node_at<'a>(
+    schema: &'a schema::Document,
+    source: &str,
+    position: Position,
+) -> Option<Node<'a>> {
+    let (name, start) = name_at(source, position)?;
+    let document = graphql_parser::parse_query::<String>(source)
+        .ok()?
+        .into_static();
+    let mut found = None;
+
+    visit_document(
+        &mut NodeFinder { name, start },
+        &document,
+        &mut OperationVisitorContext::new(&document, schema),
+        &mut found,
+    );
+
+    match found {
+        Some(FoundNode::Field { parent_type }) => {
+            let parent_type = schema.type_by_name(&parent_type)?;
+            let field = parent_type.field_by_name(&name.to_string())?;
+
+            Some(Node::Field { parent_type, field })
+        }
+        Some(FoundNode::FragmentSpread) => Some(Node::FragmentSpread(name.to_string())),
+        None => schema.type_by_name(name).map(Node::Type),
+    }
+}
+
+pub fn hover(workspace: &Workspace, source: &str, position: Position) -> Option<Hover> {
+    let (signature, description, deprecation_reason) =
+        match node_at(&workspace.schema, source, position)? {
+            Node::Field { parent_type, field } => {
+                let arguments = if field.arguments.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "({})",
+                        field
+                            .arguments
+                            .iter()
+                            .map(|argument| format!("{}: {}", argument.name, argument.value_type))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+
+                (
+                    format!(
+                        "{}.{}{}: {}",
+                        parent_type.name(),
+                        field.name,
+                        arguments,
+                        field.field_type
+                    ),
+                    field.description.as_ref(),
+                    field.deprecation_reason(),
+                )
+            }
+            Node::Type(type_definition) => (
+                format!(
+                    "{} {}",
+                    type_keyword(type_definition),
+                    type_definition.name()
+                ),
+                type_description(type_definition),
+                None,
+            ),
+            Node::FragmentSpread(_) => return None,
+        };
+
+    let mut value = format!("```graphql\n{}\n```", signature);
+
+    if let Some(description) = description {
+        value.push_str("\n\n");
+        value.push_str(description);
+    }
+
+    if let Some(reason) = deprecation_reason {
+        value.push_str(&format!("\n\n*Deprecated: {}*", reason));
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    })
+}
+
+pub fn definition(
+    workspace: &Workspace,
+    source: &str,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let location = match node_at(&workspace.schema, source, position)? {
+        Node::FragmentSpread(name) => workspace.find_fragment(&name)?,
+        Node::Type(type_definition) => workspace.schema_files.iter().find_map(|file| {
+            file.document
+                .definitions
+                .iter()
+                .find_map(|definition| match definition {
+                    schema::Definition::TypeDefinition(candidate)
+                        if candidate.name() == type_definition.name() =>
+                    {
+                        Some(Location::new(
+                            file.uri.clone(),
+                            definition_name_range(
+                                &file.source,
+                                type_position(candidate),
+                                candidate.name(),
+                            ),
+                        ))
+                    }
+                    _ => None,
+                })
+        })?,
+        Node::Field { parent_type, field } => workspace.schema_files.iter().find_map(|file| {
+            file.document
+                .definitions
+                .iter()
+                .filter_map(|definition| fields_of(definition, parent_type.name()))
+                .flatten()
+                .find(|candidate| candidate.name == field.name)
+                .map(|candidate| {
+                    Location::new(
+                        file.uri.clone(),
+                        definition_name_range(&file.source, candidate.position, &field.name),
+                    )
+                })
+        })?,
+    };
+
+    Some(GotoDefinitionResponse::Scalar(location))
+}
+
+/// Returns the fields declared by a definition or an extension of the type.
+fn fields_of<'a>(
+    definition: &'a schema::Definition,
+    type_name: &str,
+) -> Option<&'a [schema::Field]> {
+    match definition {
+        schema::Definition::TypeDefinition(TypeDefinition::Object(object))
+            if object.name == type_name =>
+        {
+            Some(&object.fields)
+        }
+        schema::Definition::TypeDefinition(TypeDefinition::Interface(interface))
+            if interface.name == type_name =>
+        {
+            Some(&interface.fields)
+        }
+        schema::Definition::TypeExtension(schema::TypeExtension::Object(object))
+            if object.name == type_name =>
+        {
+            Some(&object.fields)
+        }
+        schema::Definition::TypeExtension(schema::TypeExtension::Interface(interface))
+            if interface.name == type_name =>
+        {
+            Some(&interface.fields)
+        }
+        _ => None,
+    }
+}
+
+fn type_keyword(type_definition: &TypeDefinition) -> &'static str {
+    match type_definition {
+        TypeDefinition::Scalar(_) => "scalar",
+        TypeDefinition::Object(_) => "type",
+        TypeDefinition::Interface(_) => "interface",
+        TypeDefinition::Union(_) => "union",
+        TypeDefinition::Enum(_) => "enum",
+        TypeDefinition::InputObject(_) => "input",
+    }
+}
+
+fn type_description(type_definition: &TypeDefinition) -> Option<&String> {
+    match type_definition {
+        TypeDefinition::Scalar(scalar) => scalar.description.as_ref(),
+        TypeDefinition::Object(object) => object.description.as_ref(),
+        TypeDefinition::Interface(interface) => interface.description.as_ref(),
+        TypeDefinition::Union(union) => union.description.as_ref(),
+        TypeDefinition::Enum(enum_type) => enum_type.description.as_ref(),
+        TypeDefinition::InputObject(input_object) => input_object.description.as_ref(),
+    }
+}
+
+fn type_position(type_definition: &TypeDefinition) -> Pos {
+    match type_definition {
+        TypeDefinition::Scalar(scalar) => scalar.position,
+        TypeDefinition::Object(object) => object.position,
+        TypeDefinition::Interface(interface) => interface.position,
+        TypeDefinition::Union(union) => union.position,
+        TypeDefinition::Enum(enum_type) => enum_type.position,
+        TypeDefinition::InputObject(input_object) => input_object.position,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_node_under_cursor() {
+        let schema = graphql_parser::parse_schema::<String>(
+            "type Query { dog: Dog } type Dog { name: String }",
+        )
+        .unwrap()
+        .into_static();
+        let source =
+            "query {\n  dog {\n    ...DogFields\n  }\n}\n\nfragment DogFields on Dog {\n  name\n}";
+
+        assert!(matches!(
+            node_at(&schema, source, Position::new(1, 3)),
+            Some(Node::Field { parent_type, field }) if parent_type.name() == "Query" && field.name == "dog"
+        ));
+        assert!(matches!(
+            node_at(&schema, source, Position::new(2, 10)),
+            Some(Node::FragmentSpread(name)) if name == "DogFields"
+        ));
+        assert!(matches!(
+            node_at(&schema, source, Position::new(6, 23)),
+            Some(Node::Type(type_definition)) if type_definition.name() == "Dog"
+        ));
+        assert!(node_at(&schema, source, Position::new(6, 2)).is_none());
+    }
+}
@@ -0,0 +1,286 @@
+//! graphql-tools-lsp
+//! =================
+//!
+//! A GraphQL language server, speaking the Language Server Protocol over stdio. It is built with
+//! the `lsp` feature: `cargo install graphql-tools --features lsp`.
+//!
+//! The server reads the schema and the documents of the project from the `.graphqlrc.json` file at
+//! the root of the workspace (see `config::Config`), and provides:
+//!
+//! - the validation errors of the open documents, as diagnostics
+//! - the types and descriptions of the fields and types, on hover
+//! - the definitions of the fragments (in the documents), and of the fields and types (in the
+//!   schema)
+//! - the completion of the fields, directives, arguments, input fields and values
+//!
+//! Saving the configuration file or a file of the schema reloads the project. The documents of the
+//! project are read when it is loaded, and again when they are saved or closed.
+
+mod completion;
+mod config;
+mod lookup;
+mod position;
+mod workspace;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics, ShowMessage,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionResponse, InitializeParams, MessageType, OneOf,
+    PublishDiagnosticsParams, ServerCapabilities, ShowMessageParams, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::workspace::Workspace;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(true.into()),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![
+                "{".to_string(),
+                "(".to_string(),
+                ":".to_string(),
+                "@".to_string(),
+            ]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })?;
+    let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+
+    let mut workspace = Workspace::new(workspace_root(&params));
+    let errors = workspace.reload();
+    show_errors(&connection, errors)?;
+
+    main_loop(&connection, &mut workspace)?;
+
+    // The writer thread stops once the connection is dropped.
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
+
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> PathBuf {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(|uri| uri.to_file_path().ok())
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+fn main_loop(connection: &Connection, workspace: &mut Workspace) -> Result<()> {
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+
+                handle_request(connection, workspace, request)?;
+            }
+            Message::Notification(notification) => {
+                handle_notification(connection, workspace, notification)?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_request(connection: &Connection, workspace: &Workspace, request: Request) -> Result<()> {
+    let response = match request.method.as_str() {
+        HoverRequest::METHOD => respond(request, HoverRequest::METHOD, workspace, lookup::hover),
+        GotoDefinition::METHOD => respond(
+            request,
+            GotoDefinition::METHOD,
+            workspace,
+            lookup::definition,
+        ),
+        Completion::METHOD => respond(
+            request,
+            Completion::METHOD,
+            workspace,
+            |workspace, source, position| {
+                Some(CompletionResponse::Array(completion::completions(
+                    &workspace.schema,
+                    source,
+                    position,
+                )))
+            },
+        ),
+        _ => Response::new_err(
+            request.id,
+            ErrorCode::MethodNotFound as i32,
+            format!("Unsupported request {}", request.method),
+        ),
+    };
+
+    connection.sender.send(Message::Response(response))?;
+
+    Ok(())
+}
+
+/// Answers a request on a position of a document (hover, definition and completion requests all
+/// have the fields of `TextDocumentPositionParams`), or answers with an error when its parameters
+/// are invalid.
+fn respond<R, F>(request: Request, method: &str, workspace: &Workspace, handler: F) -> Response
+where
+    R: serde::Serialize,
+    F: FnOnce(&Workspace, &str, lsp_types::Position) -> Option<R>,
+{
+    let id = request.id.clone();
+    let (id, params) = match request.extract::<TextDocumentPositionParams>(method) {
+        Ok(extracted) => extracted,
+        Err(error) => {
+            return Response::new_err(
+                id,
+                ErrorCode::InvalidParams as i32,
+                format!("Invalid {} request: {:?}", method, error),
+            )
+        }
+    };
+
+    let result = workspace
+        .text(&params.text_document.uri)
+        .and_then(|source| handler(workspace, source, params.position));
+
+    Response::new_ok(id, result)
+}
+
+fn handle_notification(
+    connection: &Connection,
+    workspace: &mut Workspace,
+    notification: Notification,
+) -> Result<()> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: lsp_types::DidOpenTextDocumentParams =
+                match extract_notification(notification, DidOpenTextDocument::METHOD) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+            let uri = params.text_document.uri;
+
+            workspace.open(uri.clone(), params.text_document.text);
+            publish_diagnostics(connection, workspace, &uri)?;
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: lsp_types::DidChangeTextDocumentParams =
+                match extract_notification(notification, DidChangeTextDocument::METHOD) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+            let uri = params.text_document.uri;
+
+            // The documents are synchronized in full: the last change has the whole text.
+            if let Some(change) = params.content_changes.into_iter().last() {
+                workspace.change(uri.clone(), change.text);
+                publish_diagnostics(connection, workspace, &uri)?;
+            }
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: lsp_types::DidCloseTextDocumentParams =
+                match extract_notification(notification, DidCloseTextDocument::METHOD) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+
+            workspace.close(&params.text_document.uri);
+        }
+        DidSaveTextDocument::METHOD => {
+            let params: lsp_types::DidSaveTextDocumentParams =
+                match extract_notification(notification, DidSaveTextDocument::METHOD) {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+
+            let uri = params.text_document.uri;
+            if workspace.is_project_file(&uri) {
+                let errors = workspace.reload();
+                show_errors(connection, errors)?;
+            } else if workspace.is_project_document(&uri) {
+                workspace.refresh_project_document(&uri);
+            } else {
+                return Ok(());
+            }
+
+            let uris = workspace.documents.keys().cloned().collect::<Vec<_>>();
+            for uri in uris {
+                publish_diagnostics(connection, workspace, &uri)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Extracts the parameters of a notification. Invalid notifications are logged (on stderr, the
+/// protocol is on stdout) and ignored.
+fn extract_notification<P: DeserializeOwned>(
+    notification: Notification,
+    method: &str,
+) -> Option<P> {
+    match notification.extract(method) {
+        Ok(params) => Some(params),
+        Err(error) => {
+            eprintln!("Invalid {} notification: {:?}", method, error);
+            None
+        }
+    }
+}
+
+fn publish_diagnostics(
+    connection: &Connection,
+    workspace: &mut Workspace,
+    uri: &Url,
+) -> Result<()> {
+    let params = PublishDiagnosticsParams::new(uri.clone(), workspace.diagnostics(uri), None);
+
+    connection
+        .sender
+        .send(Message::Notification(Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            params,
+        )))?;
+
+    Ok(())
+}
+
+fn show_errors(connection: &Connection, errors: Vec<String>) -> Result<()> {
+    for error in errors {
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                ShowMessage::METHOD.to_string(),
+                ShowMessageParams {
+                    typ: MessageType::ERROR,
+                    message: error,
+                },
+            )))?;
+    }
+
+    Ok(())
+}
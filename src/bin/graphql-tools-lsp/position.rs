@@ -0,0 +1,223 @@
+use graphql_parser::Pos;
+use graphql_tools::ast::{column_width, is_name_char};
+use lsp_types::{Position, Range};
+
+/// Converts a position of the parser (1-based, with a tab counting as 8 columns) to an LSP position
+/// (0-based, in UTF-16 code units).
+pub fn to_lsp_position(source: &str, pos: Pos) -> Position {
+    let line = source.lines().nth(pos.line.saturating_sub(1)).unwrap_or("");
+    let mut column = 1;
+    let mut character = 0;
+
+    for c in line.chars() {
+        if column >= pos.column {
+            break;
+        }
+
+        column += column_width(c);
+        character += c.len_utf16();
+    }
+
+    Position::new(pos.line.saturating_sub(1) as u32, character as u32)
+}
+
+/// Converts an LSP position to a position of the parser.
+pub fn from_lsp_position(source: &str, position: Position) -> Pos {
+    let line = source.lines().nth(position.line as usize).unwrap_or("");
+    let mut column = 1;
+    let mut character = 0;
+
+    for c in line.chars() {
+        if character >= position.character as usize {
+            break;
+        }
+
+        column += column_width(c);
+        character += c.len_utf16();
+    }
+
+    Pos {
+        line: position.line as usize + 1,
+        column,
+    }
+}
+
+/// Returns the byte offset of an LSP position in the source.
+pub fn offset_of(source: &str, position: Position) -> usize {
+    let mut offset = 0;
+
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        if index == position.line as usize {
+            let mut character = 0;
+
+            for (byte, c) in line.char_indices() {
+                if character >= position.character as usize || c == '\n' || c == '\r' {
+                    return offset + byte;
+                }
+
+                character += c.len_utf16();
+            }
+
+            return offset + line.len();
+        }
+
+        offset += line.len();
+    }
+
+    source.len()
+}
+
+/// Returns the LSP position of a byte offset in the source.
+pub fn position_of_offset(source: &str, offset: usize) -> Position {
+    let line_start = source[..offset]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    Position::new(
+        source[..line_start].matches('\n').count() as u32,
+        source[line_start..offset].encode_utf16().count() as u32,
+    )
+}
+
+/// Returns the range of the name of a definition, from the position of the parser for the
+/// definition (the one of its description, or of its first keyword).
+pub fn definition_name_range(source: &str, pos: Pos, name: &str) -> Range {
+    let start = offset_of(source, to_lsp_position(source, pos));
+    let rest = &source[start..];
+    let description_length = if let Some(description) = rest.strip_prefix("\"\"\"") {
+        description.find("\"\"\"").map(|end| end + 6)
+    } else if let Some(description) = rest.strip_prefix('"') {
+        description.find('"').map(|end| end + 2)
+    } else {
+        None
+    };
+    let from = start + description_length.unwrap_or(0);
+
+    source[from..]
+        .match_indices(name)
+        .map(|(index, _)| from + index)
+        .find(|&index| {
+            !source[..index].ends_with(is_name_char)
+                && !source[index + name.len()..].starts_with(is_name_char)
+        })
+        .map(|index| {
+            Range::new(
+                position_of_offset(source, index),
+                position_of_offset(source, index + name.len()),
+            )
+        })
+        .unwrap_or_else(|| name_range(source, pos))
+}
+
+/// Returns the range of the name starting at the position of the parser (like the name of a
+/// field, at the location of a validation error), or an empty range when there is no name there.
+pub fn name_range(source: &str, pos: Pos) -> Range {
+    let start = to_lsp_position(source, pos);
+    let offset = offset_of(source, start);
+    let length: usize = source[offset..]
+        .chars()
+        .take_while(|c| is_name_char(*c))
+        .map(char::len_utf16)
+        .sum();
+
+    Range::new(
+        start,
+        Position::new(start.line, start.character + length as u32),
+    )
+}
+
+/// Returns the name under the cursor, with the position of its first character.
+pub fn name_at(source: &str, position: Position) -> Option<(&str, Pos)> {
+    let offset = offset_of(source, position);
+    let start = source[..offset]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_name_char(*c))
+        .map(|(index, c)| index + c.len_utf8())
+        .unwrap_or(0);
+    let end = source[offset..]
+        .find(|c| !is_name_char(c))
+        .map(|index| offset + index)
+        .unwrap_or(source.len());
+
+    if start == end {
+        return None;
+    }
+
+    let line_start = source[..start]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let character = source[line_start..start].encode_utf16().count();
+    let start_position = Position::new(position.line, character as u32);
+
+    Some((
+        &source[start..end],
+        from_lsp_position(source, start_position),
+    ))
+}
+
+/// Returns the position of a parse error, from its message.
+pub fn parse_error_position(message: &str) -> Pos {
+    message
+        .split("Parse error at ")
+        .nth(1)
+        .and_then(|rest| {
+            let position = rest.split_whitespace().next()?;
+            let (line, column) = position.split_once(':')?;
+
+            Some(Pos {
+                line: line.parse().ok()?,
+                column: column.parse().ok()?,
+            })
+        })
+        .unwrap_or(Pos { line: 1, column: 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_positions() {
+        let source = "query {\n\tdog { name }\n  café { name }\n}";
+
+        let pos = Pos { line: 2, column: 9 };
+        assert_eq!(to_lsp_position(source, pos), Position::new(1, 1));
+        assert_eq!(from_lsp_position(source, Position::new(1, 1)), pos);
+
+        let pos = Pos {
+            line: 3,
+            column: 10,
+        };
+        assert_eq!(to_lsp_position(source, pos), Position::new(2, 9));
+        assert_eq!(from_lsp_position(source, Position::new(2, 9)), pos);
+
+        assert_eq!(
+            name_at(source, Position::new(1, 3)),
+            Some(("dog", Pos { line: 2, column: 9 }))
+        );
+        assert_eq!(name_at(source, Position::new(1, 5)), None);
+        assert_eq!(
+            name_range(
+                source,
+                Pos {
+                    line: 2,
+                    column: 15
+                }
+            ),
+            Range::new(Position::new(1, 7), Position::new(1, 11))
+        );
+    }
+
+    #[test]
+    fn position_of_parse_error() {
+        let error = graphql_parser::parse_query::<String>("{\n  dog {\n  }\n}").unwrap_err();
+
+        assert_eq!(
+            parse_error_position(&error.to_string()),
+            Pos { line: 3, column: 3 }
+        );
+    }
+}
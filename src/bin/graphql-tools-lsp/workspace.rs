@@ -0,0 +1,504 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use graphql_tools::ast::{
+    merge_type_extensions, with_builtin_definitions, OperationDefinitionExtension,
+};
+use graphql_tools::static_graphql::{query, schema};
+use graphql_tools::validation::incremental::IncrementalValidator;
+use graphql_tools::validation::rules::default_rules_validation_plan;
+use graphql_tools::validation::utils::ValidationError;
+use graphql_tools::validation::validate::ValidationSeverity;
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Range,
+    Url,
+};
+
+use crate::config::Config;
+use crate::position::{definition_name_range, name_range, parse_error_position, to_lsp_position};
+
+const DIAGNOSTIC_SOURCE: &str = "graphql-tools";
+
+/// Fragments are used by the other documents of the project: the workspace reports the unused
+/// ones itself, instead of the rule checking each document on its own.
+const NO_UNUSED_FRAGMENTS: &str = "NoUnusedFragments";
+
+/// A file of the schema, kept to locate the definitions.
+pub struct SchemaFile {
+    pub uri: Url,
+    pub source: String,
+    pub document: schema::Document,
+}
+
+/// A document opened in the editor.
+pub struct OpenDocument {
+    pub text: String,
+    /// The parsed text, `None` when it does not parse.
+    document: Option<query::Document>,
+    validator: IncrementalValidator,
+}
+
+/// A document of the project, read and parsed when the project is loaded or when the file is
+/// saved or closed, to find the fragments it defines and spreads.
+struct ProjectDocument {
+    source: String,
+    document: Option<query::Document>,
+}
+
+/// The state of the server: the schema and the documents of the project, and the documents opened
+/// in the editor.
+pub struct Workspace {
+    pub root: PathBuf,
+    /// The schema, with its type extensions merged and the built-in definitions added.
+    pub schema: schema::Document,
+    pub schema_files: Vec<SchemaFile>,
+    pub documents: HashMap<Url, OpenDocument>,
+    /// The documents of the project, as saved on disk (the open ones are in `documents`).
+    project_documents: HashMap<Url, ProjectDocument>,
+}
+
+impl Workspace {
+    pub fn new(root: PathBuf) -> Self {
+        Workspace {
+            root,
+            schema: with_builtin_definitions(&schema::Document {
+                definitions: vec![],
+            }),
+            schema_files: vec![],
+            documents: HashMap::new(),
+            project_documents: HashMap::new(),
+        }
+    }
+
+    /// Loads the configuration, the schema and the documents of the project (again), and returns
+    /// the errors to report.
+    pub fn reload(&mut self) -> Vec<String> {
+        let mut errors = vec![];
+        self.schema_files.clear();
+        self.project_documents.clear();
+
+        match Config::load(&self.root) {
+            Ok(config) => {
+                match config.schema_files(&self.root) {
+                    Ok(files) => {
+                        for path in files {
+                            match load_schema_file(&path) {
+                                Ok(file) => self.schema_files.push(file),
+                                Err(error) => errors.push(error),
+                            }
+                        }
+                    }
+                    Err(error) => errors.push(format!("Failed to list schema files: {}", error)),
+                }
+
+                match config.document_files(&self.root) {
+                    Ok(files) => {
+                        for path in files {
+                            match Url::from_file_path(&path) {
+                                Ok(uri) => self.refresh_project_document(&uri),
+                                Err(_) => {
+                                    errors.push(format!("Invalid document path {}", path.display()))
+                                }
+                            }
+                        }
+                    }
+                    Err(error) => errors.push(format!("Failed to list documents: {}", error)),
+                }
+            }
+            Err(error) => errors.push(error),
+        }
+
+        let definitions = self
+            .schema_files
+            .iter()
+            .flat_map(|file| file.document.definitions.iter().cloned())
+            .collect();
+        self.schema =
+            with_builtin_definitions(&merge_type_extensions(&schema::Document { definitions }));
+
+        for document in self.documents.values_mut() {
+            document.validator.clear();
+        }
+
+        errors
+    }
+
+    /// Returns `true` when the file is the configuration file or a file of the schema.
+    pub fn is_project_file(&self, uri: &Url) -> bool {
+        uri.to_file_path()
+            .map(|path| {
+                path == self.root.join(crate::config::CONFIG_FILE_NAME)
+                    || self.schema_files.iter().any(|file| &file.uri == uri)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Reads and parses a document of the project again, after it is saved or closed. Files that
+    /// are not documents of the project, or that cannot be read anymore, are ignored.
+    pub fn refresh_project_document(&mut self, uri: &Url) {
+        let source = match uri.to_file_path().map(fs::read_to_string) {
+            Ok(Ok(source)) => source,
+            _ => {
+                self.project_documents.remove(uri);
+                return;
+            }
+        };
+
+        self.project_documents.insert(
+            uri.clone(),
+            ProjectDocument {
+                document: parse_document(&source),
+                source,
+            },
+        );
+    }
+
+    /// Returns `true` when the file is a document of the project.
+    pub fn is_project_document(&self, uri: &Url) -> bool {
+        self.project_documents.contains_key(uri)
+    }
+
+    pub fn open(&mut self, uri: Url, text: String) {
+        let mut plan = default_rules_validation_plan();
+        plan.set_severity(NO_UNUSED_FRAGMENTS, ValidationSeverity::Off);
+
+        self.documents.insert(
+            uri,
+            OpenDocument {
+                document: parse_document(&text),
+                text,
+                validator: IncrementalValidator::new(plan),
+            },
+        );
+    }
+
+    pub fn change(&mut self, uri: Url, text: String) {
+        match self.documents.get_mut(&uri) {
+            Some(document) => {
+                document.document = parse_document(&text);
+                document.text = text;
+            }
+            None => self.open(uri, text),
+        }
+    }
+
+    /// Closes a document: a document of the project is read from disk again, as its changes may
+    /// not have been saved.
+    pub fn close(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+
+        if self.is_project_document(uri) {
+            self.refresh_project_document(uri);
+        }
+    }
+
+    pub fn text(&self, uri: &Url) -> Option<&str> {
+        self.documents
+            .get(uri)
+            .map(|document| document.text.as_str())
+    }
+
+    /// Validates an open document, and returns the parse error or the validation errors.
+    ///
+    /// The fragments spread by the document and defined in other documents of the project are
+    /// validated with it, and only the errors located in the document are returned. A fragment is
+    /// reported as unused when no document of the project spreads it.
+    pub fn diagnostics(&mut self, uri: &Url) -> Vec<Diagnostic> {
+        let (text, document) = match self.documents.get(uri) {
+            Some(document) => (document.text.clone(), document.document.clone()),
+            None => return vec![],
+        };
+
+        let document = match document {
+            Some(document) => document,
+            None => {
+                let message = match graphql_parser::parse_query::<String>(&text) {
+                    Err(error) => error.to_string(),
+                    Ok(_) => return vec![],
+                };
+                let position = to_lsp_position(&text, parse_error_position(&message));
+
+                return vec![Diagnostic {
+                    range: Range::new(position, position),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                    message: message.trim_end().to_string(),
+                    ..Default::default()
+                }];
+            }
+        };
+
+        let own_fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                query::Definition::Fragment(fragment) => Some(fragment),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let is_own = |name: &String| own_fragments.iter().any(|own| &own.name == name);
+
+        let mut spreads = HashSet::new();
+        for definition in &document.definitions {
+            collect_spreads(definition_selection_set(definition), &mut spreads);
+        }
+        let mut missing = spreads
+            .iter()
+            .filter(|name| !is_own(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut unused = own_fragments
+            .iter()
+            .filter(|fragment| !spreads.contains(&fragment.name))
+            .collect::<Vec<_>>();
+
+        let mut project_fragments = HashMap::new();
+        if !missing.is_empty() || !unused.is_empty() {
+            let mut spread_elsewhere = HashSet::new();
+
+            for (_, _, other) in self.parsed_documents(Some(uri)) {
+                for definition in &other.definitions {
+                    collect_spreads(definition_selection_set(definition), &mut spread_elsewhere);
+
+                    if let query::Definition::Fragment(fragment) = definition {
+                        project_fragments
+                            .entry(fragment.name.as_str())
+                            .or_insert(fragment);
+                    }
+                }
+            }
+
+            unused.retain(|fragment| !spread_elsewhere.contains(&fragment.name));
+        }
+
+        // The fragments of the other documents are appended after the document, so the locations
+        // in the document do not move.
+        let mut source = text.clone();
+        let mut appended = HashSet::new();
+        while let Some(name) = missing.pop() {
+            let fragment = match project_fragments.get(name.as_str()) {
+                Some(fragment) if appended.insert(name) => fragment,
+                _ => continue,
+            };
+
+            source.push('\n');
+            source.push_str(&fragment.to_string());
+
+            let mut dependencies = HashSet::new();
+            collect_spreads(&fragment.selection_set, &mut dependencies);
+            missing.extend(dependencies.into_iter().filter(|name| !is_own(name)));
+        }
+
+        let result = match self.documents.get_mut(uri) {
+            Some(document) => match document.validator.validate(&self.schema, &source) {
+                Ok(result) => result,
+                Err(_) => return vec![],
+            },
+            None => return vec![],
+        };
+
+        let line_count = text.lines().count();
+        let in_document = |errors: Vec<ValidationError>| {
+            errors.into_iter().filter_map(move |mut error| {
+                if error.locations.is_empty() {
+                    return Some(error);
+                }
+
+                error
+                    .locations
+                    .retain(|location| location.line <= line_count);
+
+                if error.locations.is_empty() {
+                    None
+                } else {
+                    Some(error)
+                }
+            })
+        };
+
+        in_document(result.errors)
+            .map(|error| to_diagnostic(uri, &text, &error, DiagnosticSeverity::ERROR))
+            .chain(
+                in_document(result.warnings)
+                    .map(|error| to_diagnostic(uri, &text, &error, DiagnosticSeverity::WARNING)),
+            )
+            .chain(unused.into_iter().map(|fragment| Diagnostic {
+                range: definition_name_range(&text, fragment.position, &fragment.name),
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(NO_UNUSED_FRAGMENTS.to_string())),
+                source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                message: format!("Fragment \"{}\" is never used.", fragment.name),
+                ..Default::default()
+            }))
+            .collect()
+    }
+
+    /// Finds the definition of a fragment, in the open documents and in the documents of the
+    /// project.
+    pub fn find_fragment(&self, name: &str) -> Option<Location> {
+        self.parsed_documents(None)
+            .find_map(|(uri, source, document)| {
+                document
+                    .definitions
+                    .iter()
+                    .find_map(|definition| match definition {
+                        query::Definition::Fragment(fragment) if fragment.name == name => {
+                            Some(Location::new(
+                                uri.clone(),
+                                definition_name_range(source, fragment.position, name),
+                            ))
+                        }
+                        _ => None,
+                    })
+            })
+    }
+
+    /// Returns the open documents and the (other) documents of the project that parse, except the
+    /// given one, with their sources.
+    fn parsed_documents<'w>(
+        &'w self,
+        except: Option<&'w Url>,
+    ) -> impl Iterator<Item = (&'w Url, &'w str, &'w query::Document)> {
+        let open_documents = self.documents.iter().filter_map(|(uri, document)| {
+            Some((uri, document.text.as_str(), document.document.as_ref()?))
+        });
+        let project_documents = self
+            .project_documents
+            .iter()
+            .filter(move |(uri, _)| !self.documents.contains_key(uri))
+            .filter_map(|(uri, document)| {
+                Some((uri, document.source.as_str(), document.document.as_ref()?))
+            });
+
+        open_documents
+            .chain(project_documents)
+            .filter(move |(uri, _, _)| Some(*uri) != except)
+    }
+}
+
+fn parse_document(source: &str) -> Option<query::Document> {
+    graphql_parser::parse_query::<String>(source)
+        .ok()
+        .map(|document| document.into_static())
+}
+
+fn definition_selection_set(definition: &query::Definition) -> &query::SelectionSet {
+    match definition {
+        query::Definition::Operation(operation) => operation.selection_set(),
+        query::Definition::Fragment(fragment) => &fragment.selection_set,
+    }
+}
+
+fn collect_spreads(selection_set: &query::SelectionSet, spreads: &mut HashSet<String>) {
+    for selection in &selection_set.items {
+        match selection {
+            query::Selection::Field(field) => collect_spreads(&field.selection_set, spreads),
+            query::Selection::FragmentSpread(spread) => {
+                spreads.insert(spread.fragment_name.clone());
+            }
+            query::Selection::InlineFragment(inline) => {
+                collect_spreads(&inline.selection_set, spreads)
+            }
+        }
+    }
+}
+
+fn load_schema_file(path: &Path) -> Result<SchemaFile, String> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    let document = graphql_parser::parse_schema(&source)
+        .map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?
+        .into_static();
+    let uri = Url::from_file_path(path)
+        .map_err(|_| format!("Invalid schema file path {}", path.display()))?;
+
+    Ok(SchemaFile {
+        uri,
+        source,
+        document,
+    })
+}
+
+fn to_diagnostic(
+    uri: &Url,
+    source: &str,
+    error: &ValidationError,
+    severity: DiagnosticSeverity,
+) -> Diagnostic {
+    let mut ranges = error
+        .locations
+        .iter()
+        .map(|location| name_range(source, *location));
+    let range = ranges.next().unwrap_or_default();
+    let related_information = ranges
+        .map(|range| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), range),
+            message: error.message.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(error.error_code.to_string())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: error.message.clone(),
+        related_information: if related_information.is_empty() {
+            None
+        } else {
+            Some(related_information)
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_with_project_fragments() {
+        let mut workspace = Workspace::new(PathBuf::new());
+        workspace.schema = with_builtin_definitions(
+            &graphql_parser::parse_schema::<String>(
+                "type Query { dog: Dog } type Dog { name: String, owner: Human } type Human { name: String }",
+            )
+            .unwrap()
+            .into_static(),
+        );
+
+        let operation = Url::parse("file:///project/operation.graphql").unwrap();
+        let fragments = Url::parse("file:///project/fragments.graphql").unwrap();
+        workspace.open(
+            operation.clone(),
+            "query {\n  dog {\n    ...DogFields\n    ...Unknown\n  }\n}".to_string(),
+        );
+        workspace.open(
+            fragments.clone(),
+            "fragment DogFields on Dog {\n  name\n  owner {\n    ...HumanFields\n  }\n}\n\nfragment HumanFields on Human {\n  name\n  age\n}\n\nfragment Unused on Dog {\n  name\n}".to_string(),
+        );
+
+        let messages = |diagnostics: Vec<Diagnostic>| {
+            diagnostics
+                .into_iter()
+                .map(|diagnostic| (diagnostic.range.start.line, diagnostic.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            messages(workspace.diagnostics(&operation)),
+            vec![(3, "Unknown fragment \"Unknown\".".to_string())]
+        );
+        assert_eq!(
+            messages(workspace.diagnostics(&fragments)),
+            vec![
+                (
+                    9,
+                    "Cannot query field \"age\" on type \"Human\".".to_string()
+                ),
+                (12, "Fragment \"Unused\" is never used.".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Support for Apollo Federation v2 subgraph schemas.
+//!
+//! `graphql_parser` does not support schema extensions, so `@link` must be applied on a schema
+//! definition (`schema @link(...) { query: Query }`) instead of `extend schema @link(...)`.
+//!
+//! https://www.apollographql.com/docs/federation/subgraph-spec/
+
+pub mod subgraph;
+
+pub use self::subgraph::*;
@@ -0,0 +1,407 @@
+use lazy_static::lazy_static;
+
+use crate::ast::{
+    merge_type_extensions, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+};
+use crate::static_graphql::schema::{
+    Definition, Directive, Document, Field, ObjectType, TypeDefinition, UnionType, Value,
+};
+use crate::validation::{field_set::validate_field_set, utils::ValidationError};
+
+/// The definitions of the Apollo Federation v2 subgraph specification: the federation directives,
+/// the `_Any`, `FieldSet` and `link__Import` scalars, the `_Service` type and the `link__Purpose`
+/// enum.
+pub static FEDERATION_V2_DEFINITIONS: &str = "
+scalar _Any
+scalar FieldSet
+scalar link__Import
+
+enum link__Purpose {
+  SECURITY
+  EXECUTION
+}
+
+type _Service {
+  sdl: String
+}
+
+directive @link(url: String!, as: String, import: [link__Import], for: link__Purpose) repeatable on SCHEMA
+directive @key(fields: FieldSet!, resolvable: Boolean = true) repeatable on OBJECT | INTERFACE
+directive @requires(fields: FieldSet!) on FIELD_DEFINITION
+directive @provides(fields: FieldSet!) on FIELD_DEFINITION
+directive @external on OBJECT | FIELD_DEFINITION
+directive @shareable repeatable on OBJECT | FIELD_DEFINITION
+directive @override(from: String!) on FIELD_DEFINITION
+directive @inaccessible on FIELD_DEFINITION | OBJECT | INTERFACE | UNION | ARGUMENT_DEFINITION | SCALAR | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT | INTERFACE | UNION | ARGUMENT_DEFINITION | SCALAR | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+";
+
+lazy_static! {
+    static ref FEDERATION_V2_DOCUMENT: Document =
+        graphql_parser::parse_schema(FEDERATION_V2_DEFINITIONS)
+            .expect("Failed to parse federation definitions")
+            .into_static();
+    static ref ENTITIES_FIELD: Field =
+        root_field("_entities(representations: [_Any!]!): [_Entity]!");
+    static ref SERVICE_FIELD: Field = root_field("_service: _Service!");
+}
+
+fn root_field(sdl: &str) -> Field {
+    let document = graphql_parser::parse_schema(&format!("type Query {{ {} }}", sdl))
+        .expect("Failed to parse federation root field")
+        .into_static();
+
+    match document.definitions.into_iter().next() {
+        Some(Definition::TypeDefinition(TypeDefinition::Object(object))) => {
+            object.fields.into_iter().next().unwrap()
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Returns `true` when the directive is the federation directive with the given name, either
+/// imported (`@key`) or namespaced (`@federation__key`).
+pub fn is_federation_directive(directive: &Directive, name: &str) -> bool {
+    directive.name == name
+        || directive
+            .name
+            .strip_prefix("federation__")
+            .is_some_and(|n| n == name)
+}
+
+fn directive_string_argument<'a>(directive: &'a Directive, name: &str) -> Option<&'a str> {
+    directive
+        .arguments
+        .iter()
+        .find(|(argument_name, _)| argument_name == name)
+        .and_then(|(_, value)| match value {
+            Value::String(value) => Some(value.as_str()),
+            _ => None,
+        })
+}
+
+fn is_resolvable_key(directive: &Directive) -> bool {
+    !directive
+        .arguments
+        .iter()
+        .any(|(name, value)| name == "resolvable" && matches!(value, Value::Boolean(false)))
+}
+
+/// Returns the names of the entity types of a subgraph schema: the object types with at least one
+/// resolvable `@key`. Type extensions are taken into account.
+pub fn entity_type_names(document: &Document) -> Vec<String> {
+    merge_type_extensions(document)
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.directives.iter().any(|directive| {
+                    is_federation_directive(directive, "key") && is_resolvable_key(directive)
+                }) =>
+            {
+                Some(object.name.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Builds the schema served by a subgraph from its SDL, so operations sent to the subgraph (like
+/// the `_entities` queries of a gateway) can be validated with this crate:
+///
+/// - type extensions are merged into the types they extend (see `merge_type_extensions`),
+/// - the federation definitions (`FEDERATION_V2_DEFINITIONS`) that are not defined by the SDL are
+///   added,
+/// - the `_Entity` union of the entity types and the `_entities` root field are added when the
+///   subgraph has entities, and the `_service` root field is always added.
+pub fn build_subgraph_schema(document: &Document) -> Document {
+    let entities = entity_type_names(document);
+    let mut schema = merge_type_extensions(document);
+
+    for definition in &FEDERATION_V2_DOCUMENT.definitions {
+        let is_defined = match definition {
+            Definition::TypeDefinition(type_definition) => {
+                schema.type_by_name(type_definition.name()).is_some()
+            }
+            Definition::DirectiveDefinition(directive_definition) => schema
+                .directive_by_name(&directive_definition.name)
+                .is_some(),
+            _ => true,
+        };
+
+        if !is_defined {
+            schema.definitions.push(definition.clone());
+        }
+    }
+
+    let query_type_name = schema
+        .schema_definition()
+        .query
+        .clone()
+        .unwrap_or_else(|| "Query".to_string());
+
+    if schema.object_type_by_name(&query_type_name).is_none() {
+        schema
+            .definitions
+            .push(Definition::TypeDefinition(TypeDefinition::Object(
+                ObjectType::new(query_type_name.clone()),
+            )));
+    }
+
+    if !entities.is_empty() && schema.type_by_name("_Entity").is_none() {
+        let mut entity_union = UnionType::new("_Entity".to_string());
+        entity_union.types = entities;
+
+        schema
+            .definitions
+            .push(Definition::TypeDefinition(TypeDefinition::Union(
+                entity_union,
+            )));
+    }
+
+    let has_entities = schema.type_by_name("_Entity").is_some();
+
+    for definition in schema.definitions.iter_mut() {
+        if let Definition::TypeDefinition(TypeDefinition::Object(query_type)) = definition {
+            if query_type.name != query_type_name {
+                continue;
+            }
+
+            if has_entities && !query_type.fields.iter().any(|f| f.name == "_entities") {
+                query_type.fields.push(ENTITIES_FIELD.clone());
+            }
+
+            if !query_type.fields.iter().any(|f| f.name == "_service") {
+                query_type.fields.push(SERVICE_FIELD.clone());
+            }
+
+            break;
+        }
+    }
+
+    schema
+}
+
+/// Validates the federation directives of a subgraph schema: the `fields` of `@key` and
+/// `@requires` must be valid field sets on the type they are used in, and the `fields` of
+/// `@provides` must be a valid field set on the type returned by the field (see
+/// `validate_field_set`). Type extensions are taken into account.
+pub fn validate_subgraph_schema(document: &Document) -> Vec<ValidationError> {
+    let schema = merge_type_extensions(document);
+    let mut errors = vec![];
+
+    for definition in &schema.definitions {
+        let (type_definition, directives, fields) = match definition {
+            Definition::TypeDefinition(type_definition @ TypeDefinition::Object(object)) => {
+                (type_definition, &object.directives, &object.fields)
+            }
+            Definition::TypeDefinition(type_definition @ TypeDefinition::Interface(interface)) => {
+                (type_definition, &interface.directives, &interface.fields)
+            }
+            _ => continue,
+        };
+
+        let type_name = type_definition.name();
+
+        for directive in directives {
+            if is_federation_directive(directive, "key") {
+                validate_directive_field_set(
+                    &schema,
+                    Some(type_definition),
+                    directive,
+                    &format!("On type \"{}\"", type_name),
+                    "FederationKeyFields",
+                    &mut errors,
+                );
+            }
+        }
+
+        for field in fields {
+            for directive in &field.directives {
+                let context = format!("On field \"{}.{}\"", type_name, field.name);
+
+                if is_federation_directive(directive, "requires") {
+                    validate_directive_field_set(
+                        &schema,
+                        Some(type_definition),
+                        directive,
+                        &context,
+                        "FederationRequiresFields",
+                        &mut errors,
+                    );
+                } else if is_federation_directive(directive, "provides") {
+                    validate_directive_field_set(
+                        &schema,
+                        schema
+                            .type_by_name(field.field_type.inner_type())
+                            .filter(|t| t.is_composite_type()),
+                        directive,
+                        &context,
+                        "FederationProvidesFields",
+                        &mut errors,
+                    );
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+fn validate_directive_field_set(
+    schema: &Document,
+    parent_type: Option<&TypeDefinition>,
+    directive: &Directive,
+    context: &str,
+    error_code: &'static str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let fields = match directive_string_argument(directive, "fields") {
+        Some(fields) => fields,
+        None => {
+            errors.push(ValidationError {
+                error_code,
+                message: format!(
+                    "{}, @{} must have a \"fields\" argument of type String.",
+                    context, directive.name
+                ),
+                locations: vec![directive.position],
+            });
+            return;
+        }
+    };
+
+    let parent_type = match parent_type {
+        Some(parent_type) => parent_type,
+        None => {
+            errors.push(ValidationError {
+                error_code,
+                message: format!(
+                    "{}, @{} can only be used on fields returning a composite type.",
+                    context, directive.name
+                ),
+                locations: vec![directive.position],
+            });
+            return;
+        }
+    };
+
+    for error in validate_field_set(schema, parent_type, fields, directive.position) {
+        errors.push(ValidationError {
+            error_code,
+            message: format!(
+                "{}, for @{}(fields: \"{}\"): {}",
+                context, directive.name, fields, error.message
+            ),
+            locations: error.locations,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::{get_messages, INTROSPECTION_SCHEMA};
+    use crate::validation::validate::validate;
+
+    static SUBGRAPH: &str = "
+schema @link(url: \"https://specs.apollo.dev/federation/v2.3\", import: [\"@key\", \"@shareable\"]) {
+  query: Query
+}
+
+type Product @key(fields: \"upc\") @key(fields: \"sku organization { id }\") {
+  upc: String!
+  sku: String!
+  name: String @shareable
+  organization: Organization!
+}
+
+type Organization @key(fields: \"id\", resolvable: false) {
+  id: ID!
+}
+
+type Review @federation__key(fields: \"id\") {
+  id: ID!
+  product: Product
+}
+
+extend type Query {
+  topProducts: [Product]
+}
+";
+
+    fn subgraph_document(sdl: &str) -> Document {
+        graphql_parser::parse_schema(&format!("{}{}", sdl, INTROSPECTION_SCHEMA))
+            .expect("Failed to parse schema")
+            .into_static()
+    }
+
+    #[test]
+    fn entity_types() {
+        assert_eq!(
+            entity_type_names(&subgraph_document(SUBGRAPH)),
+            vec!["Product".to_string(), "Review".to_string()]
+        );
+    }
+
+    #[test]
+    fn validate_entities_operation() {
+        let schema = build_subgraph_schema(&subgraph_document(SUBGRAPH));
+        let operation = graphql_parser::parse_query(
+            "query ($representations: [_Any!]!) {
+              _entities(representations: $representations) {
+                ... on Product {
+                  name
+                }
+              }
+              _service {
+                sdl
+              }
+              topProducts {
+                upc
+              }
+            }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+
+        let errors = validate(&schema, &operation, &default_rules_validation_plan());
+        assert_eq!(get_messages(&errors).len(), 0);
+    }
+
+    #[test]
+    fn valid_key_fields() {
+        let errors = validate_subgraph_schema(&subgraph_document(SUBGRAPH));
+        assert_eq!(errors.len(), 0);
+    }
+
+    #[test]
+    fn invalid_key_fields() {
+        let errors = validate_subgraph_schema(&subgraph_document(
+            "
+type Product @key(fields: \"id\") @key(fields: \"organization\") @key(fields: \"upc { value }\") {
+  upc: String!
+  price: Int @requires(fields: \"weight\")
+  organization: Organization! @provides(fields: \"name\")
+}
+
+type Organization {
+  id: ID!
+}
+",
+        ));
+
+        assert_eq!(
+            get_messages(&errors),
+            vec![
+                "On type \"Product\", for @key(fields: \"id\"): Cannot query field \"id\" on type \"Product\".",
+                "On type \"Product\", for @key(fields: \"organization\"): Field \"organization\" of type \"Organization!\" must have a selection of subfields. Did you mean \"organization { ... }\"?",
+                "On type \"Product\", for @key(fields: \"upc { value }\"): Cannot query field \"value\" on type \"String\".",
+                "On type \"Product\", for @key(fields: \"upc { value }\"): Field \"upc\" must not have a selection since type \"String!\" has no subfields.",
+                "On field \"Product.price\", for @requires(fields: \"weight\"): Cannot query field \"weight\" on type \"Product\".",
+                "On field \"Product.organization\", for @provides(fields: \"name\"): Cannot query field \"name\" on type \"Organization\"."
+            ]
+        );
+    }
+}
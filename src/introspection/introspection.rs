@@ -94,6 +94,8 @@ pub struct IntrospectionInputObjectType {
     pub description: Option<String>,
     #[serde(rename = "inputFields")]
     pub input_fields: Vec<IntrospectionInputValue>,
+    #[serde(rename = "isOneOf")]
+    pub is_one_of: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -259,3 +261,45 @@ fn test_shopify_introspection() {
         .expect("failed to open json file");
     parse_introspection(json_file).expect("failed to parse introspection json");
 }
+
+#[test]
+fn test_one_of_introspection() {
+    let introspection = parse_introspection_from_string(
+        r#"{
+          "__schema": {
+            "description": null,
+            "queryType": { "name": "Query" },
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [
+              {
+                "kind": "INPUT_OBJECT",
+                "name": "PetInput",
+                "description": null,
+                "inputFields": [],
+                "isOneOf": true
+              },
+              {
+                "kind": "INPUT_OBJECT",
+                "name": "Filter",
+                "description": null,
+                "inputFields": []
+              }
+            ],
+            "directives": []
+          }
+        }"#,
+    )
+    .expect("failed to parse introspection json");
+
+    let one_of = introspection
+        .__schema
+        .types
+        .iter()
+        .map(|t| match t {
+            IntrospectionType::INPUT_OBJECT(input_object) => input_object.is_one_of,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(one_of, vec![Some(true), None]);
+}
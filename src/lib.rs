@@ -28,10 +28,14 @@ pub mod static_graphql {
     static_graphql!(schema, schema, {
       Field, Directive, InterfaceType, ObjectType, Value, TypeDefinition,
       EnumType, Type, Document, ScalarType, InputValue, DirectiveDefinition,
-      UnionType, InputObjectType, EnumValue, SchemaDefinition,
+      UnionType, InputObjectType, EnumValue, SchemaDefinition, TypeExtension, Definition,
     });
 }
 
+pub mod federation;
+
 pub mod introspection;
 
+pub mod query_planning;
+
 pub mod validation;
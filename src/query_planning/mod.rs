@@ -0,0 +1,6 @@
+//! Helpers for planning the execution of operations across several GraphQL services, for simple
+//! schema-stitching gateways.
+
+pub mod split_operation;
+
+pub use self::split_operation::*;
@@ -0,0 +1,696 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::ast::{
+    collect_directives_variables_in_use, collect_fragments_in_use, collect_grouped_fields,
+    collect_variables_in_use, AstNodeWithName, CollectFieldsOptions, FieldByNameExtension,
+    FragmentCondition, OperationDefinitionExtension, OperationTransformer, OperationVisitorContext,
+    SchemaDocumentExtension, Transformed, TransformedValue, TypeDefinitionExtension, TypeExtension,
+};
+use crate::static_graphql::{
+    query::{
+        Definition, Document, Field, FragmentDefinition, InlineFragment, OperationDefinition,
+        Selection, SelectionSet, TypeCondition, VariableDefinition,
+    },
+    schema::{self, TypeDefinition},
+};
+
+/// The plan of an operation split across services by `split_operation`.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    /// The name of the root type of the operation.
+    pub root_type: String,
+    /// The documents to send to the services, in the order of their first root field.
+    pub fetches: Vec<QueryPlanFetch>,
+    /// The response keys of the root fields, in the order of their first occurrence in the
+    /// operation, with the service resolving each of them.
+    pub merge: Vec<QueryPlanMerge>,
+}
+
+/// A sub-operation to send to a service.
+#[derive(Debug, Clone)]
+pub struct QueryPlanFetch {
+    pub service: String,
+    /// A document with the sub-operation, and only the fragments it uses.
+    pub document: Document,
+}
+
+/// A root field of the operation, and the service its value is taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlanMerge {
+    pub response_key: String,
+    /// The service resolving the field, or `None` for `__typename`, which is resolved with the
+    /// name of the root type.
+    pub service: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryPlanError {
+    /// The document has no operation.
+    NoOperation,
+    /// The document has several operations and no operation name was given.
+    MissingOperationName,
+    /// No operation with the given name exists in the document.
+    UnknownOperation(String),
+    /// The schema has no root type for the operation.
+    MissingRootType(String),
+    /// A root field is not owned by any service.
+    UnownedField(String),
+    /// A field is owned by another service than the root field it is selected in.
+    NestedFieldOwnership {
+        coordinate: String,
+        service: String,
+        root_service: String,
+    },
+}
+
+impl fmt::Display for QueryPlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryPlanError::NoOperation => write!(f, "Must provide an operation."),
+            QueryPlanError::MissingOperationName => write!(
+                f,
+                "Must provide operation name if query contains multiple operations."
+            ),
+            QueryPlanError::UnknownOperation(name) => {
+                write!(f, "Unknown operation named \"{}\".", name)
+            }
+            QueryPlanError::MissingRootType(name) => {
+                write!(f, "Schema does not define the root type \"{}\".", name)
+            }
+            QueryPlanError::UnownedField(coordinate) => {
+                write!(f, "Field \"{}\" is not owned by any service.", coordinate)
+            }
+            QueryPlanError::NestedFieldOwnership {
+                coordinate,
+                service,
+                root_service,
+            } => write!(
+                f,
+                "Field \"{}\" is owned by service \"{}\", but is selected in a root field of service \"{}\".",
+                coordinate, service, root_service
+            ),
+        }
+    }
+}
+
+impl QueryPlan {
+    pub fn fetch_for_service(&self, service: &str) -> Option<&QueryPlanFetch> {
+        self.fetches.iter().find(|fetch| fetch.service == service)
+    }
+
+    /// Merges the `data` of the responses of the services, by service name, into the `data` of
+    /// the response of the operation. The response keys missing from the result of their service
+    /// (like the fields skipped with `@skip` or `@include`) are left out.
+    pub fn merge_results(&self, results: &HashMap<String, JsonValue>) -> Map<String, JsonValue> {
+        let mut data = Map::new();
+
+        for merge in &self.merge {
+            let value = match &merge.service {
+                Some(service) => match results
+                    .get(service)
+                    .and_then(|result| result.get(&merge.response_key))
+                {
+                    Some(value) => value.clone(),
+                    None => continue,
+                },
+                None => JsonValue::String(self.root_type.clone()),
+            };
+
+            data.insert(merge.response_key.clone(), value);
+        }
+
+        data
+    }
+}
+
+/// Splits an operation of a document into one sub-operation per service, given the service owning
+/// each root field, by schema coordinate (`Query.me`).
+///
+/// The root fields are collected (following fragments) and each of them is sent, with its whole
+/// selection set, to the service owning it. Nested fields can be listed in `ownership` too, but
+/// they must be owned by the same service as their root field: resolving a nested field with
+/// another service would need entity fetches, which are not planned.
+///
+/// A root field selected in fragments with `@skip` or `@include` is sent within inline fragments
+/// with the same directives, so the service evaluates them. A `__typename` root field is resolved
+/// by the plan, unless it is only selected in such fragments: it is then sent to the service of
+/// the first root field.
+///
+/// Each sub-document only has the variable definitions and fragments used by its sub-operation.
+/// The operation is expected to be valid.
+pub fn split_operation(
+    schema: &schema::Document,
+    document: &Document,
+    operation_name: Option<&str>,
+    ownership: &HashMap<String, String>,
+) -> Result<QueryPlan, QueryPlanError> {
+    let operation = find_operation(document, operation_name)?;
+    let root_type_name = schema.root_type_name(operation);
+    let root_type = schema
+        .type_by_name(root_type_name)
+        .ok_or_else(|| QueryPlanError::MissingRootType(root_type_name.to_string()))?;
+
+    let context = OperationVisitorContext::new(document, schema);
+    let collected = collect_grouped_fields(
+        operation.selection_set(),
+        root_type,
+        &context,
+        CollectFieldsOptions {
+            conditions: true,
+            ..Default::default()
+        },
+    );
+    let root_fields = collected
+        .fields
+        .iter_with_conditions()
+        .map(|(field, conditions)| RootField { field, conditions })
+        .collect::<Vec<_>>();
+
+    let mut services: Vec<(String, Vec<Selection>)> = vec![];
+    let mut merge: Vec<QueryPlanMerge> = vec![];
+    let mut conditional_typenames = vec![];
+    let always_typename = root_fields.iter().any(|root_field| {
+        root_field.field.name == "__typename" && root_field.conditions.is_empty()
+    });
+
+    for root_field in &root_fields {
+        let field = root_field.field;
+        let response_key = field.alias.clone().unwrap_or_else(|| field.name.clone());
+
+        let service = if field.name == "__typename" {
+            if !always_typename {
+                conditional_typenames.push(root_field);
+            }
+
+            None
+        } else {
+            let coordinate = format!("{}.{}", root_type.name(), field.name);
+            let service = ownership
+                .get(&coordinate)
+                .ok_or(QueryPlanError::UnownedField(coordinate))?;
+
+            if let Some(field_type) = root_type
+                .field_by_name(&field.name)
+                .and_then(|f| schema.type_by_name(f.field_type.inner_type()))
+            {
+                check_nested_ownership(
+                    schema,
+                    &field.selection_set,
+                    field_type,
+                    service,
+                    ownership,
+                    &context.known_fragments,
+                    &mut HashSet::new(),
+                )?;
+            }
+
+            match services.iter_mut().find(|(name, _)| name == service) {
+                Some((_, selections)) => selections.push(root_field.to_selection()),
+                None => services.push((service.clone(), vec![root_field.to_selection()])),
+            }
+
+            Some(service.clone())
+        };
+
+        if !merge.iter().any(|m| m.response_key == response_key) {
+            merge.push(QueryPlanMerge {
+                response_key,
+                service,
+            });
+        }
+    }
+
+    if let Some((service, selections)) = services.first_mut() {
+        for root_field in conditional_typenames {
+            selections.push(root_field.to_selection());
+
+            for m in merge.iter_mut() {
+                if m.response_key == root_field.response_key() {
+                    m.service = Some(service.clone());
+                }
+            }
+        }
+    }
+
+    let fetches = services
+        .into_iter()
+        .map(|(service, selections)| QueryPlanFetch {
+            document: sub_document(document, operation, selections),
+            service,
+        })
+        .collect();
+
+    Ok(QueryPlan {
+        root_type: root_type.name().to_string(),
+        fetches,
+        merge,
+    })
+}
+
+/// A root field, with the `@skip` and `@include` directives of the fragments it is selected in
+/// that depend on variables, from the outermost fragment.
+struct RootField<'a, 'c> {
+    field: &'a Field,
+    conditions: &'c [FragmentCondition<'a>],
+}
+
+impl<'a, 'c> RootField<'a, 'c> {
+    fn response_key(&self) -> &str {
+        self.field.alias.as_deref().unwrap_or(&self.field.name)
+    }
+
+    /// Returns the field, within an inline fragment for each of its conditions.
+    fn to_selection(&self) -> Selection {
+        self.conditions.iter().rev().fold(
+            Selection::Field(self.field.clone()),
+            |selection, condition| {
+                Selection::InlineFragment(InlineFragment {
+                    position: condition.position,
+                    type_condition: None,
+                    directives: condition.directives.iter().cloned().cloned().collect(),
+                    selection_set: SelectionSet {
+                        span: (condition.position, condition.position),
+                        items: vec![selection],
+                    },
+                })
+            },
+        )
+    }
+}
+
+fn find_operation<'a>(
+    document: &'a Document,
+    operation_name: Option<&str>,
+) -> Result<&'a OperationDefinition, QueryPlanError> {
+    let mut operations = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        });
+
+    match operation_name {
+        Some(name) => operations
+            .find(|operation| operation.node_name() == Some(name))
+            .ok_or_else(|| QueryPlanError::UnknownOperation(name.to_string())),
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => Ok(operation),
+            (Some(_), Some(_)) => Err(QueryPlanError::MissingOperationName),
+            (None, _) => Err(QueryPlanError::NoOperation),
+        },
+    }
+}
+
+fn check_nested_ownership(
+    schema: &schema::Document,
+    selection_set: &SelectionSet,
+    parent_type: &TypeDefinition,
+    root_service: &str,
+    ownership: &HashMap<String, String>,
+    known_fragments: &HashMap<&str, &FragmentDefinition>,
+    visited_fragments: &mut HashSet<String>,
+) -> Result<(), QueryPlanError> {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                let coordinate = format!("{}.{}", parent_type.name(), field.name);
+
+                if let Some(service) = ownership.get(&coordinate) {
+                    if service != root_service {
+                        return Err(QueryPlanError::NestedFieldOwnership {
+                            coordinate,
+                            service: service.clone(),
+                            root_service: root_service.to_string(),
+                        });
+                    }
+                }
+
+                if let Some(field_type) = parent_type
+                    .field_by_name(&field.name)
+                    .and_then(|f| schema.type_by_name(f.field_type.inner_type()))
+                {
+                    check_nested_ownership(
+                        schema,
+                        &field.selection_set,
+                        field_type,
+                        root_service,
+                        ownership,
+                        known_fragments,
+                        visited_fragments,
+                    )?;
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment_type = match &fragment.type_condition {
+                    Some(TypeCondition::On(type_name)) => schema.type_by_name(type_name),
+                    None => Some(parent_type),
+                };
+
+                if let Some(fragment_type) = fragment_type {
+                    check_nested_ownership(
+                        schema,
+                        &fragment.selection_set,
+                        fragment_type,
+                        root_service,
+                        ownership,
+                        known_fragments,
+                        visited_fragments,
+                    )?;
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                if !visited_fragments.insert(spread.fragment_name.clone()) {
+                    continue;
+                }
+
+                if let Some(fragment) = known_fragments.get(spread.fragment_name.as_str()) {
+                    let TypeCondition::On(type_name) = &fragment.type_condition;
+
+                    if let Some(fragment_type) = schema.type_by_name(type_name) {
+                        check_nested_ownership(
+                            schema,
+                            &fragment.selection_set,
+                            fragment_type,
+                            root_service,
+                            ownership,
+                            known_fragments,
+                            visited_fragments,
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the document of a sub-operation with the given root selections, the variable
+/// definitions they use and the fragments they spread.
+fn sub_document(
+    document: &Document,
+    operation: &OperationDefinition,
+    selections: Vec<Selection>,
+) -> Document {
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let selection_set = SelectionSet {
+        span: operation.selection_set().span,
+        items: selections,
+    };
+
+    let mut fragments_in_use: Vec<&str> = vec![];
+    collect_fragments_in_use(&selection_set, &fragments, &mut fragments_in_use);
+    let fragments_in_use = fragments_in_use
+        .into_iter()
+        .map(String::from)
+        .collect::<HashSet<_>>();
+
+    let mut variables_in_use: Vec<&str> = vec![];
+    collect_directives_variables_in_use(operation.directives(), &mut variables_in_use);
+    collect_variables_in_use(&selection_set, &fragments, &mut variables_in_use);
+    let variables_in_use = variables_in_use
+        .into_iter()
+        .map(String::from)
+        .collect::<HashSet<_>>();
+
+    let mut transformer = SubOperationTransformer {
+        selections: Some(selection_set.items),
+        variables: &variables_in_use,
+    };
+    let sub_operation = match transformer.transform_operation(operation) {
+        Transformed::Keep => operation.clone(),
+        Transformed::Replace(sub_operation) => sub_operation,
+    };
+
+    let mut definitions = vec![Definition::Operation(sub_operation)];
+    definitions.extend(
+        document
+            .definitions
+            .iter()
+            .filter(|definition| match definition {
+                Definition::Fragment(fragment) => fragments_in_use.contains(&fragment.name),
+                _ => false,
+            })
+            .cloned(),
+    );
+
+    Document { definitions }
+}
+
+/// Replaces the root selections of an operation, and removes the variable definitions that are not
+/// used anymore.
+struct SubOperationTransformer<'a> {
+    selections: Option<Vec<Selection>>,
+    variables: &'a HashSet<String>,
+}
+
+impl<'a> OperationTransformer<'static, String> for SubOperationTransformer<'a> {
+    fn transform_selection_set(
+        &mut self,
+        _selections: &SelectionSet,
+    ) -> TransformedValue<Vec<Selection>> {
+        // Only the root selection set is transformed: the new root selections are not visited.
+        match self.selections.take() {
+            Some(selections) => TransformedValue::Replace(selections),
+            None => TransformedValue::Keep,
+        }
+    }
+
+    fn transform_variable_definitions(
+        &mut self,
+        variable_definitions: &Vec<VariableDefinition>,
+    ) -> TransformedValue<Vec<VariableDefinition>> {
+        TransformedValue::Replace(
+            variable_definitions
+                .iter()
+                .filter(|variable_definition| self.variables.contains(&variable_definition.name))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SCHEMA: &str = "
+type Query {
+  me: User
+  product(upc: String!): Product
+  topProducts(first: Int): [Product]
+  recommended: [Product]
+}
+
+type User {
+  id: ID!
+  name: String
+}
+
+type Product {
+  upc: String!
+  name: String
+  reviews: [Review]
+}
+
+type Review {
+  body: String
+  author: User
+}
+";
+
+    fn ownership() -> HashMap<String, String> {
+        HashMap::from([
+            ("Query.me".to_string(), "accounts".to_string()),
+            ("Query.product".to_string(), "products".to_string()),
+            ("Query.topProducts".to_string(), "products".to_string()),
+            ("Product.reviews".to_string(), "reviews".to_string()),
+        ])
+    }
+
+    fn split(operation: &str, operation_name: Option<&str>) -> Result<QueryPlan, QueryPlanError> {
+        let schema = graphql_parser::parse_schema(SCHEMA)
+            .expect("Failed to parse schema")
+            .into_static();
+        let document = graphql_parser::parse_query(operation)
+            .expect("Failed to parse query")
+            .into_static();
+
+        split_operation(&schema, &document, operation_name, &ownership())
+    }
+
+    #[test]
+    fn split_root_fields_by_service() {
+        let plan = split(
+            "
+query Home($upc: String!, $first: Int, $withName: Boolean!) {
+  me {
+    ...UserFields
+  }
+  product(upc: $upc) {
+    name @include(if: $withName)
+  }
+  ...TopProducts
+  __typename
+}
+
+fragment UserFields on User {
+  id
+  name
+}
+
+fragment TopProducts on Query {
+  top: topProducts(first: $first) {
+    upc
+  }
+}
+
+query Other {
+  me {
+    id
+  }
+}
+",
+            Some("Home"),
+        )
+        .unwrap();
+
+        assert_eq!(plan.fetches.len(), 2);
+        assert_eq!(
+            plan.fetch_for_service("accounts").unwrap().document.to_string(),
+            "query Home {\n  me {\n    ...UserFields\n  }\n}\n\nfragment UserFields on User {\n  id\n  name\n}\n"
+        );
+        assert_eq!(
+            plan.fetch_for_service("products").unwrap().document.to_string(),
+            "query Home($upc: String!, $first: Int, $withName: Boolean!) {\n  product(upc: $upc) {\n    name @include(if: $withName)\n  }\n  top: topProducts(first: $first) {\n    upc\n  }\n}\n"
+        );
+
+        assert_eq!(
+            plan.merge
+                .iter()
+                .map(|merge| (merge.response_key.as_str(), merge.service.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("me", Some("accounts")),
+                ("product", Some("products")),
+                ("top", Some("products")),
+                ("__typename", None),
+            ]
+        );
+
+        let data = plan.merge_results(&HashMap::from([
+            (
+                "accounts".to_string(),
+                serde_json::json!({ "me": { "id": "1", "name": "Ada" } }),
+            ),
+            (
+                "products".to_string(),
+                serde_json::json!({ "product": null, "top": [{ "upc": "1" }] }),
+            ),
+        ]));
+
+        assert_eq!(
+            JsonValue::Object(data),
+            serde_json::json!({
+                "me": { "id": "1", "name": "Ada" },
+                "product": null,
+                "top": [{ "upc": "1" }],
+                "__typename": "Query",
+            })
+        );
+    }
+
+    #[test]
+    fn split_conditional_root_fragments() {
+        let plan = split(
+            "
+query Q($v: Boolean!, $first: Int) {
+  ... @include(if: $v) {
+    me {
+      id
+    }
+    __typename
+  }
+  ...Products @skip(if: $v)
+  ... @skip(if: true) {
+    product(upc: \"1\") {
+      name
+    }
+  }
+}
+
+fragment Products on Query {
+  topProducts(first: $first) {
+    upc
+  }
+}
+",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.fetch_for_service("accounts").unwrap().document.to_string(),
+            "query Q($v: Boolean!) {\n  ... @include(if: $v) {\n    me {\n      id\n    }\n  }\n  ... @include(if: $v) {\n    __typename\n  }\n}\n"
+        );
+        assert_eq!(
+            plan.fetch_for_service("products").unwrap().document.to_string(),
+            "query Q($v: Boolean!, $first: Int) {\n  ... @skip(if: $v) {\n    topProducts(first: $first) {\n      upc\n    }\n  }\n}\n"
+        );
+
+        let data = plan.merge_results(&HashMap::from([
+            ("accounts".to_string(), serde_json::json!({})),
+            (
+                "products".to_string(),
+                serde_json::json!({ "topProducts": [{ "upc": "1" }] }),
+            ),
+        ]));
+
+        assert_eq!(
+            JsonValue::Object(data),
+            serde_json::json!({ "topProducts": [{ "upc": "1" }] })
+        );
+    }
+
+    #[test]
+    fn split_errors() {
+        assert_eq!(
+            split("{ me { id } } { me { name } }", None).unwrap_err(),
+            QueryPlanError::MissingOperationName
+        );
+        assert_eq!(
+            split("{ me { id } }", Some("Home")).unwrap_err(),
+            QueryPlanError::UnknownOperation("Home".to_string())
+        );
+        assert_eq!(
+            split("query Home { me { id } }", None)
+                .unwrap()
+                .fetches
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            split("{ me { id } recommended { upc } }", None).unwrap_err(),
+            QueryPlanError::UnownedField("Query.recommended".to_string())
+        );
+
+        let error = split("{ topProducts { reviews { body } } }", None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Field \"Product.reviews\" is owned by service \"reviews\", but is selected in a root field of service \"products\"."
+        );
+    }
+}
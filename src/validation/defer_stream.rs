@@ -0,0 +1,88 @@
+use lazy_static::lazy_static;
+
+use crate::ast::SchemaDocumentExtension;
+use crate::static_graphql::schema::{Definition, Document};
+
+/// The definitions of the `@defer` and `@stream` directives of the incremental delivery RFC.
+///
+/// See https://github.com/graphql/graphql-spec/pull/742
+pub static DEFER_STREAM_DEFINITIONS: &str = "
+directive @defer(if: Boolean! = true, label: String) on FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @stream(if: Boolean! = true, label: String, initialCount: Int! = 0) on FIELD
+";
+
+lazy_static! {
+    static ref DEFER_STREAM_DOCUMENT: Document =
+        graphql_parser::parse_schema(DEFER_STREAM_DEFINITIONS)
+            .expect("Failed to parse defer and stream definitions")
+            .into_static();
+}
+
+/// Returns the schema with the `@defer` and `@stream` directives (`DEFER_STREAM_DEFINITIONS`)
+/// added, unless the schema already defines them. Operations using incremental delivery can then
+/// be validated against the schema: the `DeferStreamDirectiveOnRootField`,
+/// `DeferStreamDirectiveOnValidOperations`, `DeferStreamDirectiveLabel` and
+/// `StreamDirectiveOnListField` rules check how the directives are used.
+pub fn with_defer_stream_directives(schema: &Document) -> Document {
+    let mut schema = schema.clone();
+
+    for definition in &DEFER_STREAM_DOCUMENT.definitions {
+        if let Definition::DirectiveDefinition(directive_definition) = definition {
+            if schema
+                .directive_by_name(&directive_definition.name)
+                .is_none()
+            {
+                schema.definitions.push(definition.clone());
+            }
+        }
+    }
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::{get_messages, INTROSPECTION_SCHEMA, TEST_SCHEMA};
+    use crate::validation::validate::validate;
+
+    #[test]
+    fn validate_operations_using_defer_and_stream() {
+        let schema =
+            graphql_parser::parse_schema(&format!("{}{}", TEST_SCHEMA, INTROSPECTION_SCHEMA))
+                .expect("Failed to parse schema")
+                .into_static();
+        let operation = graphql_parser::parse_query(
+            "
+{
+  human {
+    relatives @stream(initialCount: 1, label: \"relatives\") {
+      name
+    }
+    ... @defer(label: \"pets\") {
+      pets {
+        name
+      }
+    }
+  }
+}
+",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+        let plan = default_rules_validation_plan();
+
+        assert_eq!(
+            get_messages(&validate(&schema, &operation, &plan)),
+            vec![
+                "Unknown directive \"@stream\".",
+                "Unknown directive \"@defer\"."
+            ]
+        );
+
+        let schema = with_defer_stream_directives(&schema);
+        assert!(validate(&schema, &operation, &plan).is_empty());
+        assert_eq!(with_defer_stream_directives(&schema), schema);
+    }
+}
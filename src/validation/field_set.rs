@@ -0,0 +1,223 @@
+use std::fmt;
+
+use graphql_parser::Pos;
+
+use super::{
+    rules::{FieldsOnCorrectType, KnownArgumentNames, LeafFieldSelections},
+    utils::ValidationError,
+    validate::{validate, ValidationPlan},
+};
+use crate::{
+    ast::TypeDefinitionExtension,
+    static_graphql::{
+        query::{self, Definition, OperationDefinition},
+        schema::{self, TypeDefinition},
+    },
+};
+
+#[derive(Debug)]
+pub enum FieldSetError {
+    /// The field set is not valid GraphQL.
+    Syntax(graphql_parser::query::ParseError),
+    /// The field set closes its selection set, and is followed by other definitions (like
+    /// `id } query Other { id`).
+    UnexpectedDefinitions,
+}
+
+impl fmt::Display for FieldSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldSetError::Syntax(error) => write!(f, "{}", error),
+            FieldSetError::UnexpectedDefinitions => {
+                write!(f, "The field set must only contain fields.")
+            }
+        }
+    }
+}
+
+/// Parses a field set, like the `fields` argument of `@key(fields: "id organization { id }")`,
+/// into a selection set. The field set is the content of a selection set, without the braces.
+pub fn parse_field_set(fields: &str) -> Result<query::SelectionSet, FieldSetError> {
+    // The closing brace is on its own line, so a comment at the end of the field set does not
+    // comment it out.
+    let source = format!("{{{}\n}}", fields);
+    let document = graphql_parser::parse_query(&source)
+        .map_err(FieldSetError::Syntax)?
+        .into_static();
+    let mut definitions = document.definitions.into_iter();
+
+    match (definitions.next(), definitions.next()) {
+        (Some(Definition::Operation(OperationDefinition::SelectionSet(selection_set))), None) => {
+            Ok(selection_set)
+        }
+        _ => Err(FieldSetError::UnexpectedDefinitions),
+    }
+}
+
+/// Validates a field set against its parent type, with the `FieldsOnCorrectType`,
+/// `LeafFieldSelections` and `KnownArgumentNames` rules.
+///
+/// The locations of the errors are relative to `position`, which is usually the position of the
+/// directive the field set is used in: a field at the line `l` and column `c` of the field set is
+/// reported at `position.line + l - 1`, and at the column `position.column + c - 1` on the first
+/// line of the field set (or `c` on the next lines).
+pub fn validate_field_set(
+    schema: &schema::Document,
+    parent_type: &TypeDefinition,
+    fields: &str,
+    position: Pos,
+) -> Vec<ValidationError> {
+    // The field set is validated as the selection set of a fragment on the parent type, starting
+    // at the second line of the document so positions are easy to map back.
+    let source = format!(
+        "fragment FieldSet on {} {{\n{}\n}}",
+        parent_type.name(),
+        fields
+    );
+
+    let document = match graphql_parser::parse_query(&source) {
+        Ok(document) if document.definitions.len() == 1 => document.into_static(),
+        Ok(_) => {
+            return vec![syntax_error(
+                fields,
+                FieldSetError::UnexpectedDefinitions,
+                position,
+            )]
+        }
+        Err(error) => return vec![syntax_error(fields, FieldSetError::Syntax(error), position)],
+    };
+
+    let plan = ValidationPlan::from(vec![
+        Box::new(FieldsOnCorrectType::new()),
+        Box::new(LeafFieldSelections::new()),
+        Box::new(KnownArgumentNames::new()),
+    ]);
+
+    validate(schema, &document, &plan)
+        .into_iter()
+        .map(|mut error| {
+            error.locations = error
+                .locations
+                .iter()
+                .map(|location| relative_position(position, *location))
+                .collect();
+
+            error
+        })
+        .collect()
+}
+
+fn syntax_error(fields: &str, error: FieldSetError, position: Pos) -> ValidationError {
+    ValidationError {
+        error_code: "FieldSetSyntax",
+        message: format!("Invalid field set \"{}\": {}", fields, error),
+        locations: vec![position],
+    }
+}
+
+fn relative_position(position: Pos, location: Pos) -> Pos {
+    let line = location.line.saturating_sub(1).max(1);
+
+    if line == 1 {
+        Pos {
+            line: position.line,
+            column: position.column + location.column - 1,
+        }
+    } else {
+        Pos {
+            line: position.line + line - 1,
+            column: location.column,
+        }
+    }
+}
+
+#[test]
+fn parse_valid_field_set() {
+    let selection_set = parse_field_set("id organization { id }").unwrap();
+
+    assert_eq!(selection_set.items.len(), 2);
+    assert_eq!(parse_field_set("id # key").unwrap().items.len(), 1);
+    assert!(matches!(
+        parse_field_set("id {"),
+        Err(FieldSetError::Syntax(_))
+    ));
+    assert!(matches!(
+        parse_field_set("id } query X { a"),
+        Err(FieldSetError::UnexpectedDefinitions)
+    ));
+}
+
+#[test]
+fn validate_valid_field_set() {
+    use crate::ast::SchemaDocumentExtension;
+    use crate::validation::test_utils::*;
+
+    let schema = graphql_parser::parse_schema(&format!("{}{}", TEST_SCHEMA, INTROSPECTION_SCHEMA))
+        .expect("Failed to parse schema")
+        .into_static();
+    let dog = schema.type_by_name("Dog").unwrap();
+
+    let errors = validate_field_set(
+        &schema,
+        dog,
+        "name(surname: true) mother { name }",
+        Pos { line: 1, column: 1 },
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn validate_invalid_field_set() {
+    use crate::ast::SchemaDocumentExtension;
+    use crate::validation::test_utils::*;
+
+    let schema = graphql_parser::parse_schema(&format!("{}{}", TEST_SCHEMA, INTROSPECTION_SCHEMA))
+        .expect("Failed to parse schema")
+        .into_static();
+    let dog = schema.type_by_name("Dog").unwrap();
+
+    let errors = validate_field_set(
+        &schema,
+        dog,
+        "unknown name(unknownArg: true) mother",
+        Pos {
+            line: 10,
+            column: 5,
+        },
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Cannot query field \"unknown\" on type \"Dog\".",
+            "Field \"mother\" of type \"Dog\" must have a selection of subfields. Did you mean \"mother { ... }\"?",
+            "Unknown argument \"unknownArg\" on field \"Dog.name\"."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 10,
+            column: 5
+        }]
+    );
+    assert_eq!(
+        errors[1].locations,
+        vec![Pos {
+            line: 10,
+            column: 36
+        }]
+    );
+
+    let errors = validate_field_set(
+        &schema,
+        dog,
+        "id {",
+        Pos {
+            line: 10,
+            column: 5,
+        },
+    );
+    assert_eq!(errors[0].error_code, "FieldSetSyntax");
+}
@@ -0,0 +1,416 @@
+use std::collections::{HashMap, HashSet};
+
+use graphql_parser::Pos;
+
+use super::utils::ValidationError;
+use super::validate::{run_rules, ValidationPlan, ValidationResult};
+use crate::ast::{
+    collect_fragments_in_use, with_builtin_definitions, OperationDefinitionExtension,
+};
+use crate::static_graphql::{
+    query::{self, Definition, Document, FragmentDefinition, OperationDefinition},
+    schema,
+};
+
+/// The rules checking the definitions of a document against each other. They are run on the
+/// whole document on each validation.
+const DOCUMENT_RULES: &[&str] = &[
+    "UniqueOperationNames",
+    "LoneAnonymousOperation",
+    "UniqueFragmentNames",
+    "NoUnusedFragments",
+];
+
+/// Validates the successive versions of a document (like the ones of a file edited in an IDE),
+/// only running the rules again for the definitions that changed.
+///
+/// Each operation is validated with the fragments it depends on, and each fragment not used by
+/// any operation is validated with the fragments it depends on. The results are cached by the
+/// source text of these definitions: an operation is validated again when it changes, or when
+/// one of its fragments changes. Edits moving definitions around only update the locations of the
+/// cached errors. The rules checking the definitions against each other (like
+/// `UniqueOperationNames`) are run on the whole document on each validation.
+///
+/// The cache does not depend on the schema: call `clear()` when the schema changes.
+pub struct IncrementalValidator {
+    plan: ValidationPlan,
+    cache: HashMap<String, CachedResult>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResult {
+    errors: Vec<CachedError>,
+    warnings: Vec<CachedError>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedError {
+    error_code: &'static str,
+    message: String,
+    locations: Vec<CachedLocation>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CachedLocation {
+    /// A location in one of the definitions validated together, relative to its first line.
+    Relative {
+        definition: usize,
+        line: usize,
+        column: usize,
+    },
+    Absolute(Pos),
+}
+
+impl IncrementalValidator {
+    pub fn new(plan: ValidationPlan) -> Self {
+        IncrementalValidator {
+            plan,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn plan(&self) -> &ValidationPlan {
+        &self.plan
+    }
+
+    /// Drops the cached results, so the next validation runs all the rules again.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Parses and validates a version of the document. The results of the definitions that did
+    /// not change since the previous validation are reused.
+    pub fn validate(
+        &mut self,
+        schema: &schema::Document,
+        source: &str,
+    ) -> Result<ValidationResult, query::ParseError> {
+        let document = graphql_parser::parse_query(source)?.into_static();
+
+        if self.plan.builtin_definitions {
+            let schema = with_builtin_definitions(schema);
+
+            return Ok(self.validate_document(&schema, &document, source));
+        }
+
+        Ok(self.validate_document(schema, &document, source))
+    }
+
+    fn validate_document(
+        &mut self,
+        schema: &schema::Document,
+        document: &Document,
+        source: &str,
+    ) -> ValidationResult {
+        let lines = source.lines().collect::<Vec<_>>();
+        let mut result = run_rules(schema, document, &self.plan, |rule| {
+            DOCUMENT_RULES.contains(&rule.error_code())
+        });
+
+        let known_fragments: HashMap<&str, &FragmentDefinition> = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                _ => None,
+            })
+            .collect();
+
+        let mut fragments_in_use: Vec<&str> = vec![];
+        for definition in &document.definitions {
+            if let Definition::Operation(operation) = definition {
+                collect_fragments_in_use(
+                    operation.selection_set(),
+                    &known_fragments,
+                    &mut fragments_in_use,
+                );
+            }
+        }
+
+        let mut used_keys = HashSet::new();
+
+        for definition in &document.definitions {
+            let selection_set = match definition {
+                Definition::Operation(operation) => operation.selection_set(),
+                Definition::Fragment(fragment) => {
+                    if fragments_in_use.contains(&fragment.name.as_str()) {
+                        continue;
+                    }
+
+                    &fragment.selection_set
+                }
+            };
+
+            let mut dependencies: Vec<&str> = vec![];
+            collect_fragments_in_use(selection_set, &known_fragments, &mut dependencies);
+            dependencies.sort_unstable();
+
+            let mut unit = vec![definition];
+            unit.extend(dependencies.iter().filter_map(|name| {
+                document.definitions.iter().find(|dependency| {
+                    !std::ptr::eq(*dependency, definition)
+                        && matches!(dependency, Definition::Fragment(fragment) if fragment.name == *name)
+                })
+            }));
+
+            let key = unit
+                .iter()
+                .map(|definition| {
+                    let (start, end) = definition_lines(definition);
+                    lines
+                        .get(start - 1..end.min(lines.len()))
+                        .unwrap_or_default()
+                        .join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\u{0}");
+
+            let cached = match self.cache.get(&key) {
+                Some(cached) => cached.clone(),
+                None => {
+                    let unit_document = Document {
+                        definitions: document
+                            .definitions
+                            .iter()
+                            .filter(|definition| unit.iter().any(|d| std::ptr::eq(*d, *definition)))
+                            .cloned()
+                            .collect(),
+                    };
+                    let unit_result = run_rules(schema, &unit_document, &self.plan, |rule| {
+                        !DOCUMENT_RULES.contains(&rule.error_code())
+                    });
+                    let cached = CachedResult {
+                        errors: to_cached_errors(&unit_result.errors, &unit),
+                        warnings: to_cached_errors(&unit_result.warnings, &unit),
+                    };
+
+                    self.cache.insert(key.clone(), cached.clone());
+                    cached
+                }
+            };
+
+            append_unique(
+                &mut result.errors,
+                from_cached_errors(&cached.errors, &unit),
+            );
+            append_unique(
+                &mut result.warnings,
+                from_cached_errors(&cached.warnings, &unit),
+            );
+            used_keys.insert(key);
+        }
+
+        self.cache.retain(|key, _| used_keys.contains(key));
+
+        result
+    }
+}
+
+/// Returns the first and last lines of a definition.
+fn definition_lines(definition: &Definition) -> (usize, usize) {
+    let (start, selection_set) = match definition {
+        Definition::Operation(operation) => {
+            let start = match operation {
+                OperationDefinition::Query(query) => query.position,
+                OperationDefinition::Mutation(mutation) => mutation.position,
+                OperationDefinition::Subscription(subscription) => subscription.position,
+                OperationDefinition::SelectionSet(selection_set) => selection_set.span.0,
+            };
+
+            (start, operation.selection_set())
+        }
+        Definition::Fragment(fragment) => (fragment.position, &fragment.selection_set),
+    };
+
+    (start.line, selection_set.span.1.line)
+}
+
+fn to_cached_errors(errors: &[ValidationError], unit: &[&Definition]) -> Vec<CachedError> {
+    errors
+        .iter()
+        .map(|error| CachedError {
+            error_code: error.error_code,
+            message: error.message.clone(),
+            locations: error
+                .locations
+                .iter()
+                .map(|location| {
+                    unit.iter()
+                        .position(|definition| {
+                            let (start, end) = definition_lines(definition);
+                            (start..=end).contains(&location.line)
+                        })
+                        .map(|index| CachedLocation::Relative {
+                            definition: index,
+                            line: location.line - definition_lines(unit[index]).0,
+                            column: location.column,
+                        })
+                        .unwrap_or(CachedLocation::Absolute(*location))
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn from_cached_errors(errors: &[CachedError], unit: &[&Definition]) -> Vec<ValidationError> {
+    errors
+        .iter()
+        .map(|error| ValidationError {
+            error_code: error.error_code,
+            message: error.message.clone(),
+            locations: error
+                .locations
+                .iter()
+                .map(|location| match *location {
+                    CachedLocation::Relative {
+                        definition,
+                        line,
+                        column,
+                    } => Pos {
+                        line: definition_lines(unit[definition]).0 + line,
+                        column,
+                    },
+                    CachedLocation::Absolute(position) => position,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Appends the errors, skipping the ones already reported (the errors of a fragment are reported
+/// by each operation using it).
+fn append_unique(errors: &mut Vec<ValidationError>, new_errors: Vec<ValidationError>) {
+    for error in new_errors {
+        let exists = errors.iter().any(|existing| {
+            existing.error_code == error.error_code
+                && existing.message == error.message
+                && existing.locations == error.locations
+        });
+
+        if !exists {
+            errors.push(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::ast::OperationVisitorContext;
+    use crate::validation::rules::{default_rules_validation_plan, ValidationRule};
+    use crate::validation::test_utils::{INTROSPECTION_SCHEMA, TEST_SCHEMA};
+    use crate::validation::utils::ValidationErrorContext;
+    use crate::validation::validate::validate;
+
+    /// Counts the definitions validated again.
+    struct CountingRule(Arc<AtomicUsize>);
+
+    impl ValidationRule for CountingRule {
+        fn validate<'a>(
+            &self,
+            _: &mut OperationVisitorContext<'a>,
+            _: &mut ValidationErrorContext,
+        ) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn error_code<'a>(&self) -> &'a str {
+            "Counting"
+        }
+    }
+
+    fn messages_and_locations(errors: &[ValidationError]) -> Vec<(String, Vec<Pos>)> {
+        let mut errors = errors
+            .iter()
+            .map(|error| (error.message.clone(), error.locations.clone()))
+            .collect::<Vec<_>>();
+        errors.sort_by_key(|(message, locations)| {
+            (
+                locations.first().map(|l| (l.line, l.column)),
+                message.clone(),
+            )
+        });
+        errors
+    }
+
+    #[test]
+    fn revalidate_changed_definitions() {
+        let schema =
+            graphql_parser::parse_schema(&format!("{}{}", TEST_SCHEMA, INTROSPECTION_SCHEMA))
+                .expect("Failed to parse schema")
+                .into_static();
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut plan = default_rules_validation_plan();
+        plan.add_rule(Box::new(CountingRule(count.clone())));
+        let mut validator = IncrementalValidator::new(plan);
+
+        let check = |validator: &mut IncrementalValidator, source: &str| {
+            let result = validator.validate(&schema, source).unwrap();
+            let document = graphql_parser::parse_query(source).unwrap().into_static();
+            let expected = validate(&schema, &document, &default_rules_validation_plan());
+
+            assert_eq!(
+                messages_and_locations(&result.errors),
+                messages_and_locations(&expected)
+            );
+        };
+
+        let source = "
+query Dog {
+  dog {
+    ...DogFields
+  }
+}
+
+query Pet {
+  pet {
+    ... on Dog {
+      ...DogFields
+    }
+    unknownPetField
+  }
+}
+
+fragment DogFields on Dog {
+  name
+  unknownDogField
+}
+
+fragment Unused on Dog {
+  barkVolume
+}
+";
+        check(&mut validator, source);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // Moving the definitions around only updates the locations.
+        check(&mut validator, &format!("\n\n{}", source));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // Changing an operation only validates it again.
+        check(
+            &mut validator,
+            &format!("\n\n{}", source.replace("unknownPetField", "name")),
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+
+        // Changing a fragment validates the operations using it again.
+        check(
+            &mut validator,
+            &format!(
+                "\n\n{}",
+                source
+                    .replace("unknownPetField", "name")
+                    .replace("unknownDogField", "nickname")
+            ),
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 6);
+
+        validator.clear();
+        check(&mut validator, source);
+        assert_eq!(count.load(Ordering::SeqCst), 9);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Alphabetized selections
+///
+/// The fields of a selection set must be sorted alphabetically by name. Fragment spreads and
+/// inline fragments are not sorted, and do not break the order of the fields around them.
+pub struct AlphabetizedSelections;
+
+impl AlphabetizedSelections {
+    pub fn new() -> Self {
+        AlphabetizedSelections
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for AlphabetizedSelections {
+    fn enter_selection_set(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        selection_set: &SelectionSet,
+    ) {
+        let mut previous: Option<&Field> = None;
+
+        for selection in &selection_set.items {
+            if let Selection::Field(field) = selection {
+                if let Some(previous_field) = previous {
+                    if field.name < previous_field.name {
+                        user_context.report_error(ValidationError {
+                            error_code: self.error_code(),
+                            message: format!(
+                                "Field \"{}\" should be before field \"{}\".",
+                                field.name, previous_field.name
+                            ),
+                            locations: vec![field.position],
+                        });
+                    }
+                }
+
+                previous = Some(field);
+            }
+        }
+    }
+}
+
+impl ValidationRule for AlphabetizedSelections {
+    fn error_code<'a>(&self) -> &'a str {
+        "AlphabetizedSelections"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut AlphabetizedSelections::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn sorted_selections() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(AlphabetizedSelections::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { barkVolume ...DogFields name nickname } }
+        fragment DogFields on Dog { name }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn unsorted_selections() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(AlphabetizedSelections::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { nickname name barkVolume } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Field \"name\" should be before field \"nickname\".",
+            "Field \"barkVolume\" should be before field \"name\"."
+        ]
+    );
+}
@@ -0,0 +1,144 @@
+use serde::Deserialize;
+
+use super::{
+    AlphabetizedSelections, MatchFragmentNameToType, NamingConvention, NoAnonymousOperations,
+    NoDuplicateFields, NoTypenameOnRoot, OperationNamingConvention, RequireIdWhenAvailable,
+};
+use crate::validation::validate::{ValidationPlan, ValidationSeverity};
+
+/// Configuration of the lint rules for executable documents, usually loaded from a JSON (or any
+/// other `serde` format) file:
+///
+/// ```json
+/// {
+///   "noAnonymousOperations": "error",
+///   "operationNamingConvention": { "severity": "warning", "style": "PascalCase" },
+///   "requireIdWhenAvailable": { "severity": "error", "fieldName": "id" }
+/// }
+/// ```
+///
+/// Rules that are not configured are not executed.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LintConfig {
+    pub no_anonymous_operations: Option<ValidationSeverity>,
+    pub operation_naming_convention: Option<OperationNamingConventionConfig>,
+    pub no_typename_on_root: Option<ValidationSeverity>,
+    pub match_fragment_name_to_type: Option<ValidationSeverity>,
+    pub require_id_when_available: Option<RequireIdWhenAvailableConfig>,
+    pub no_duplicate_fields: Option<ValidationSeverity>,
+    pub alphabetized_selections: Option<ValidationSeverity>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationNamingConventionConfig {
+    #[serde(default)]
+    pub severity: ValidationSeverity,
+    #[serde(default)]
+    pub style: Option<NamingConvention>,
+    #[serde(default)]
+    pub require_operation_type_suffix: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequireIdWhenAvailableConfig {
+    #[serde(default)]
+    pub severity: ValidationSeverity,
+    #[serde(default = "default_id_field_name")]
+    pub field_name: String,
+}
+
+fn default_id_field_name() -> String {
+    "id".to_string()
+}
+
+impl LintConfig {
+    /// Creates a validation plan with the configured lint rules only.
+    pub fn to_validation_plan(&self) -> ValidationPlan {
+        let mut plan = ValidationPlan::new();
+        self.apply(&mut plan);
+
+        plan
+    }
+
+    /// Adds the configured lint rules to an existing validation plan, for example the plan
+    /// returned by `default_rules_validation_plan`.
+    pub fn apply(&self, plan: &mut ValidationPlan) {
+        if let Some(severity) = self.no_anonymous_operations {
+            plan.add_rule_with_severity(Box::new(NoAnonymousOperations::new()), severity);
+        }
+
+        if let Some(config) = &self.operation_naming_convention {
+            plan.add_rule_with_severity(
+                Box::new(OperationNamingConvention::new(
+                    config.style,
+                    config.require_operation_type_suffix,
+                )),
+                config.severity,
+            );
+        }
+
+        if let Some(severity) = self.no_typename_on_root {
+            plan.add_rule_with_severity(Box::new(NoTypenameOnRoot::new()), severity);
+        }
+
+        if let Some(severity) = self.match_fragment_name_to_type {
+            plan.add_rule_with_severity(Box::new(MatchFragmentNameToType::new()), severity);
+        }
+
+        if let Some(config) = &self.require_id_when_available {
+            plan.add_rule_with_severity(
+                Box::new(RequireIdWhenAvailable::with_field_name(&config.field_name)),
+                config.severity,
+            );
+        }
+
+        if let Some(severity) = self.no_duplicate_fields {
+            plan.add_rule_with_severity(Box::new(NoDuplicateFields::new()), severity);
+        }
+
+        if let Some(severity) = self.alphabetized_selections {
+            plan.add_rule_with_severity(Box::new(AlphabetizedSelections::new()), severity);
+        }
+    }
+}
+
+#[test]
+fn lint_config_from_json() {
+    use crate::validation::test_utils::*;
+    use crate::validation::validate::validate_with_severity;
+
+    let config: LintConfig = serde_json::from_str(
+        r#"{
+          "noAnonymousOperations": "error",
+          "operationNamingConvention": { "severity": "warning", "style": "PascalCase" },
+          "noDuplicateFields": "off"
+        }"#,
+    )
+    .expect("Failed to parse lint config");
+    let plan = config.to_validation_plan();
+
+    assert_eq!(plan.rules.len(), 3);
+
+    let schema_ast = graphql_parser::parse_schema(TEST_SCHEMA).expect("Failed to parse schema");
+    let operation_ast = graphql_parser::parse_query(
+        "
+        { dog { name name } }
+        query getDog { dog { name } }
+    ",
+    )
+    .expect("Failed to parse query");
+
+    let result = validate_with_severity(&schema_ast, &operation_ast, &plan);
+
+    assert_eq!(
+        get_messages(&result.errors),
+        vec!["Anonymous GraphQL operations are forbidden. Make sure to name your query!"]
+    );
+    assert_eq!(
+        get_messages(&result.warnings),
+        vec!["Operation \"getDog\" should be in PascalCase format."]
+    );
+}
@@ -0,0 +1,87 @@
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Match fragment name to type
+///
+/// The name of a fragment must start with the name of the type it applies to, so
+/// `fragment DogFields on Dog` is valid while `fragment PetFields on Dog` is not.
+pub struct MatchFragmentNameToType;
+
+impl MatchFragmentNameToType {
+    pub fn new() -> Self {
+        MatchFragmentNameToType
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for MatchFragmentNameToType {
+    fn enter_fragment_definition(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        fragment: &FragmentDefinition,
+    ) {
+        let TypeCondition::On(type_name) = &fragment.type_condition;
+
+        if !fragment.name.starts_with(type_name.as_str()) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Fragment \"{}\" should start with the name of its type \"{}\".",
+                    fragment.name, type_name
+                ),
+                locations: vec![fragment.position],
+            });
+        }
+    }
+}
+
+impl ValidationRule for MatchFragmentNameToType {
+    fn error_code<'a>(&self) -> &'a str {
+        "MatchFragmentNameToType"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut MatchFragmentNameToType::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn fragment_names_matching_type() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(MatchFragmentNameToType::new()));
+    let errors = test_operation_with_schema(
+        "fragment DogFields on Dog { name }
+        fragment Dog on Dog { name }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn fragment_names_not_matching_type() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(MatchFragmentNameToType::new()));
+    let errors =
+        test_operation_with_schema("fragment PetFields on Dog { name }", TEST_SCHEMA, &mut plan);
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["Fragment \"PetFields\" should start with the name of its type \"Dog\"."]
+    );
+}
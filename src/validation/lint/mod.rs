@@ -0,0 +1,29 @@
+//! Lint rules for executable documents.
+//!
+//! Unlike the rules in `validation::rules`, these rules are not part of the GraphQL specification:
+//! they enforce conventions on the documents of a project. They implement `ValidationRule`, so they
+//! can be added to any `ValidationPlan` (usually with the `Warning` severity), or configured with a
+//! `LintConfig`.
+//!
+//! Lint rules for schemas are in the `schema` module.
+
+pub mod alphabetized_selections;
+pub mod config;
+pub mod match_fragment_name_to_type;
+pub mod naming_convention;
+pub mod no_anonymous_operations;
+pub mod no_duplicate_fields;
+pub mod no_typename_on_root;
+pub mod operation_naming_convention;
+pub mod require_id_when_available;
+pub mod schema;
+
+pub use self::alphabetized_selections::*;
+pub use self::config::*;
+pub use self::match_fragment_name_to_type::*;
+pub use self::naming_convention::*;
+pub use self::no_anonymous_operations::*;
+pub use self::no_duplicate_fields::*;
+pub use self::no_typename_on_root::*;
+pub use self::operation_naming_convention::*;
+pub use self::require_id_when_available::*;
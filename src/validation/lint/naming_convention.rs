@@ -0,0 +1,67 @@
+use serde::Deserialize;
+
+/// A naming convention that names of definitions can be checked against.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamingConvention {
+    /// `PascalCase`: starts with an upper case letter, and contains only letters and digits.
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+    /// `camelCase`: starts with a lower case letter, and contains only letters and digits.
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    /// `UPPER_CASE`: contains only upper case letters, digits and underscores.
+    #[serde(rename = "UPPER_CASE")]
+    UpperCase,
+    /// `snake_case`: contains only lower case letters, digits and underscores.
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+}
+
+impl NamingConvention {
+    pub fn is_match(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return false,
+        };
+
+        match self {
+            NamingConvention::PascalCase => {
+                first.is_ascii_uppercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            NamingConvention::CamelCase => {
+                first.is_ascii_lowercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            NamingConvention::UpperCase => {
+                first.is_ascii_uppercase()
+                    && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+            NamingConvention::SnakeCase => {
+                first.is_ascii_lowercase()
+                    && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NamingConvention::PascalCase => "PascalCase",
+            NamingConvention::CamelCase => "camelCase",
+            NamingConvention::UpperCase => "UPPER_CASE",
+            NamingConvention::SnakeCase => "snake_case",
+        }
+    }
+}
+
+#[test]
+fn naming_conventions() {
+    assert!(NamingConvention::PascalCase.is_match("GetUser2"));
+    assert!(!NamingConvention::PascalCase.is_match("getUser"));
+    assert!(!NamingConvention::PascalCase.is_match("Get_User"));
+    assert!(NamingConvention::CamelCase.is_match("getUser"));
+    assert!(!NamingConvention::CamelCase.is_match("GetUser"));
+    assert!(NamingConvention::UpperCase.is_match("GET_USER_2"));
+    assert!(!NamingConvention::UpperCase.is_match("Get_USER"));
+    assert!(NamingConvention::SnakeCase.is_match("get_user"));
+    assert!(!NamingConvention::SnakeCase.is_match(""));
+}
@@ -0,0 +1,102 @@
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// No anonymous operations
+///
+/// Every operation of the document must be named, including documents with a single operation.
+/// Named operations are easier to trace, and are required by most persisted documents stores.
+pub struct NoAnonymousOperations;
+
+impl NoAnonymousOperations {
+    pub fn new() -> Self {
+        NoAnonymousOperations
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoAnonymousOperations {
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        operation_definition: &OperationDefinition,
+    ) {
+        let (kind, location) = match operation_definition {
+            OperationDefinition::SelectionSet(selection_set) => ("query", selection_set.span.0),
+            OperationDefinition::Query(query) if query.name.is_none() => ("query", query.position),
+            OperationDefinition::Mutation(mutation) if mutation.name.is_none() => {
+                ("mutation", mutation.position)
+            }
+            OperationDefinition::Subscription(subscription) if subscription.name.is_none() => {
+                ("subscription", subscription.position)
+            }
+            _ => return,
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code(),
+            message: format!(
+                "Anonymous GraphQL operations are forbidden. Make sure to name your {}!",
+                kind
+            ),
+            locations: vec![location],
+        });
+    }
+}
+
+impl ValidationRule for NoAnonymousOperations {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoAnonymousOperations"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut NoAnonymousOperations::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn named_operations() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoAnonymousOperations::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { name } }
+        mutation DoSomething { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn anonymous_operations() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoAnonymousOperations::new()));
+    let errors = test_operation_with_schema(
+        "{ dog { name } }
+        subscription { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Anonymous GraphQL operations are forbidden. Make sure to name your query!",
+            "Anonymous GraphQL operations are forbidden. Make sure to name your subscription!"
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// No duplicate fields
+///
+/// A field must not be selected more than once under the same response name in a selection set.
+/// Such duplicates are valid GraphQL, as they are merged, but are usually a mistake.
+pub struct NoDuplicateFields;
+
+impl NoDuplicateFields {
+    pub fn new() -> Self {
+        NoDuplicateFields
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoDuplicateFields {
+    fn enter_selection_set(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        selection_set: &SelectionSet,
+    ) {
+        let mut response_names = HashSet::new();
+
+        for selection in &selection_set.items {
+            if let Selection::Field(field) = selection {
+                let response_name = field.alias.as_ref().unwrap_or(&field.name);
+
+                if !response_names.insert(response_name) {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        message: format!("Field \"{}\" is selected more than once.", response_name),
+                        locations: vec![field.position],
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl ValidationRule for NoDuplicateFields {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoDuplicateFields"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut NoDuplicateFields::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn unique_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDuplicateFields::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { name otherName: name } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn duplicate_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDuplicateFields::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { name nickname name: nickname } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages, vec!["Field \"name\" is selected more than once."]);
+}
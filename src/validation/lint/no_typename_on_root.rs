@@ -0,0 +1,127 @@
+use crate::ast::{
+    collect_grouped_fields, visit_document, CollectFieldsOptions, OperationDefinitionExtension,
+    OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// No `__typename` on root
+///
+/// The root selection set of an operation must not select `__typename`, as the root type of an
+/// operation is always known.
+pub struct NoTypenameOnRoot;
+
+impl NoTypenameOnRoot {
+    pub fn new() -> Self {
+        NoTypenameOnRoot
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoTypenameOnRoot {
+    fn enter_operation_definition(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        operation_definition: &OperationDefinition,
+    ) {
+        let root_type = match visitor_context.current_type() {
+            Some(root_type) => root_type,
+            None => return,
+        };
+
+        // The fields of the root selection set, including the ones selected through fragments.
+        let root_fields = collect_grouped_fields(
+            operation_definition.selection_set(),
+            root_type,
+            visitor_context,
+            CollectFieldsOptions::default(),
+        )
+        .fields;
+
+        for (_, fields) in root_fields {
+            for field in fields {
+                if field.name == "__typename" {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        message:
+                            "`__typename` should not be selected on the root type of an operation."
+                                .to_string(),
+                        locations: vec![field.position],
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl ValidationRule for NoTypenameOnRoot {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoTypenameOnRoot"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut NoTypenameOnRoot::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn typename_on_nested_selections() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoTypenameOnRoot::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { dog { __typename name } }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn typename_on_root() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoTypenameOnRoot::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { __typename dog { name } }
+        mutation DoSomething { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[0],
+        "`__typename` should not be selected on the root type of an operation."
+    );
+}
+
+#[test]
+fn typename_on_root_through_fragments() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoTypenameOnRoot::new()));
+    let errors = test_operation_with_schema(
+        "query GetDog { ...RootFields dog { name } }
+        query GetCat { ... { __typename } }
+        query GetPet { ... on QueryRoot { __typename } }
+        fragment RootFields on QueryRoot { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 3);
+}
@@ -0,0 +1,128 @@
+use super::NamingConvention;
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Operation naming convention
+///
+/// Operation names must follow the configured naming convention, and when configured, must end
+/// with the type of the operation (`Query`, `Mutation` or `Subscription`).
+#[derive(Clone)]
+pub struct OperationNamingConvention {
+    style: Option<NamingConvention>,
+    require_operation_type_suffix: bool,
+}
+
+impl OperationNamingConvention {
+    pub fn new(style: Option<NamingConvention>, require_operation_type_suffix: bool) -> Self {
+        OperationNamingConvention {
+            style,
+            require_operation_type_suffix,
+        }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for OperationNamingConvention {
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut OperationVisitorContext,
+        user_context: &mut ValidationErrorContext,
+        operation_definition: &OperationDefinition,
+    ) {
+        let (name, suffix, position) = match operation_definition {
+            OperationDefinition::Query(query) => (&query.name, "Query", query.position),
+            OperationDefinition::Mutation(mutation) => {
+                (&mutation.name, "Mutation", mutation.position)
+            }
+            OperationDefinition::Subscription(subscription) => {
+                (&subscription.name, "Subscription", subscription.position)
+            }
+            OperationDefinition::SelectionSet(_) => return,
+        };
+
+        let name = match name {
+            Some(name) => name,
+            None => return,
+        };
+
+        if let Some(style) = &self.style {
+            if !style.is_match(name) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: format!(
+                        "Operation \"{}\" should be in {} format.",
+                        name,
+                        style.as_str()
+                    ),
+                    locations: vec![position],
+                });
+            }
+        }
+
+        if self.require_operation_type_suffix && !name.ends_with(suffix) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!("Operation \"{}\" should have \"{}\" suffix.", name, suffix),
+                locations: vec![position],
+            });
+        }
+    }
+}
+
+impl ValidationRule for OperationNamingConvention {
+    fn error_code<'a>(&self) -> &'a str {
+        "OperationNamingConvention"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(&mut self.clone(), &ctx.operation, ctx, error_collector);
+    }
+}
+
+#[test]
+fn valid_operation_names() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(OperationNamingConvention::new(
+        Some(NamingConvention::PascalCase),
+        true,
+    )));
+    let errors = test_operation_with_schema(
+        "query GetDogQuery { dog { name } }
+        mutation UpdateDogMutation { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn invalid_operation_names() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(OperationNamingConvention::new(
+        Some(NamingConvention::PascalCase),
+        true,
+    )));
+    let errors = test_operation_with_schema(
+        "query getDogQuery { dog { name } }
+        mutation UpdateDog { __typename }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Operation \"getDogQuery\" should be in PascalCase format.",
+            "Operation \"UpdateDog\" should have \"Mutation\" suffix."
+        ]
+    );
+}
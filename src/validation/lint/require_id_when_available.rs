@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::ast::{
+    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
+    TypeDefinitionExtension,
+};
+use crate::static_graphql::query::*;
+use crate::validation::rules::ValidationRule;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Require id when available
+///
+/// When the type of a field with a selection set has an identifier field (`id` by default), the
+/// identifier must be selected, either directly or through inline fragments and fragment spreads.
+/// This lets normalized client caches identify the returned objects.
+#[derive(Clone)]
+pub struct RequireIdWhenAvailable {
+    field_name: String,
+}
+
+impl RequireIdWhenAvailable {
+    pub fn new() -> Self {
+        Self::with_field_name("id")
+    }
+
+    pub fn with_field_name(field_name: &str) -> Self {
+        RequireIdWhenAvailable {
+            field_name: field_name.to_string(),
+        }
+    }
+}
+
+fn selects_field<'a>(
+    field_name: &str,
+    selection_set: &'a SelectionSet,
+    known_fragments: &HashMap<&str, &'a FragmentDefinition>,
+    visited_fragments: &mut Vec<&'a str>,
+) -> bool {
+    selection_set.items.iter().any(|selection| match selection {
+        Selection::Field(field) => field.name == field_name,
+        Selection::InlineFragment(inline_fragment) => selects_field(
+            field_name,
+            &inline_fragment.selection_set,
+            known_fragments,
+            visited_fragments,
+        ),
+        Selection::FragmentSpread(fragment_spread) => {
+            let fragment_name = fragment_spread.fragment_name.as_str();
+
+            if visited_fragments.contains(&fragment_name) {
+                return false;
+            }
+
+            visited_fragments.push(fragment_name);
+
+            match known_fragments.get(fragment_name) {
+                Some(fragment) => selects_field(
+                    field_name,
+                    &fragment.selection_set,
+                    known_fragments,
+                    visited_fragments,
+                ),
+                None => false,
+            }
+        }
+    })
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for RequireIdWhenAvailable {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        if field.selection_set.items.is_empty() {
+            return;
+        }
+
+        if let Some(field_type) = visitor_context.current_type() {
+            if field_type.field_by_name(&self.field_name).is_none() {
+                return;
+            }
+
+            if !selects_field(
+                &self.field_name,
+                &field.selection_set,
+                &visitor_context.known_fragments,
+                &mut vec![],
+            ) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: format!(
+                        "Field \"{}\" must be selected when it's available on type \"{}\".",
+                        self.field_name,
+                        field_type.name()
+                    ),
+                    locations: vec![field.position],
+                });
+            }
+        }
+    }
+}
+
+impl ValidationRule for RequireIdWhenAvailable {
+    fn error_code<'a>(&self) -> &'a str {
+        "RequireIdWhenAvailable"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(&mut self.clone(), &ctx.operation, ctx, error_collector);
+    }
+}
+
+#[cfg(test)]
+static ID_SCHEMA: &str = "
+type User {
+  id: ID!
+  name: String
+  friends: [User]
+  avatar: Image
+}
+
+type Image {
+  url: String
+}
+
+type Query {
+  me: User
+}
+";
+
+#[test]
+fn id_selected() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(RequireIdWhenAvailable::new()));
+    let errors = test_operation_with_schema(
+        "query Me {
+          me {
+            id
+            avatar { url }
+            friends { ...UserFields }
+          }
+        }
+
+        fragment UserFields on User {
+          ... on User { id }
+          name
+        }",
+        ID_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn id_not_selected() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(RequireIdWhenAvailable::new()));
+    let errors = test_operation_with_schema(
+        "query Me {
+          me {
+            name
+            friends { ...UserFields }
+          }
+        }
+
+        fragment UserFields on User {
+          name
+        }",
+        ID_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Field \"id\" must be selected when it's available on type \"User\".",
+            "Field \"id\" must be selected when it's available on type \"User\"."
+        ]
+    );
+}
@@ -0,0 +1,159 @@
+use serde::Deserialize;
+
+use super::{
+    InputTypeSuffix, MutationNaming, NoNullableBooleanFields, RelayCompliance,
+    RequireDeprecationReason, RequireDescriptions, SchemaLintPlan, SchemaNamingConvention,
+};
+use crate::validation::lint::NamingConvention;
+use crate::validation::validate::ValidationSeverity;
+
+/// Configuration of the schema lint rules, usually loaded from a JSON (or any other `serde`
+/// format) file:
+///
+/// ```json
+/// {
+///   "namingConvention": { "severity": "error", "enumValues": "UPPER_CASE" },
+///   "requireDescriptions": "warning",
+///   "inputTypeSuffix": { "severity": "error", "suffix": "Input" }
+/// }
+/// ```
+///
+/// Rules that are not configured are not executed.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SchemaLintConfig {
+    pub naming_convention: Option<SchemaNamingConventionConfig>,
+    pub require_descriptions: Option<ValidationSeverity>,
+    pub input_type_suffix: Option<InputTypeSuffixConfig>,
+    pub no_nullable_boolean_fields: Option<ValidationSeverity>,
+    pub require_deprecation_reason: Option<ValidationSeverity>,
+    pub relay_compliance: Option<ValidationSeverity>,
+    pub mutation_naming: Option<MutationNamingConfig>,
+}
+
+/// Conventions that are not configured use the defaults of `SchemaNamingConvention`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaNamingConventionConfig {
+    #[serde(default)]
+    pub severity: ValidationSeverity,
+    #[serde(default)]
+    pub types: Option<NamingConvention>,
+    #[serde(default)]
+    pub fields: Option<NamingConvention>,
+    #[serde(default)]
+    pub enum_values: Option<NamingConvention>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InputTypeSuffixConfig {
+    #[serde(default)]
+    pub severity: ValidationSeverity,
+    #[serde(default = "default_input_type_suffix")]
+    pub suffix: String,
+}
+
+fn default_input_type_suffix() -> String {
+    "Input".to_string()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MutationNamingConfig {
+    #[serde(default)]
+    pub severity: ValidationSeverity,
+    #[serde(default)]
+    pub forbidden_prefixes: Option<Vec<String>>,
+    #[serde(default)]
+    pub payload_suffix: Option<String>,
+}
+
+impl SchemaLintConfig {
+    /// Creates a schema lint plan with the configured rules.
+    pub fn to_lint_plan(&self) -> SchemaLintPlan {
+        let mut plan = SchemaLintPlan::new();
+
+        if let Some(config) = &self.naming_convention {
+            let mut rule = SchemaNamingConvention::new();
+            rule.types = config.types.or(rule.types);
+            rule.fields = config.fields.or(rule.fields);
+            rule.enum_values = config.enum_values.or(rule.enum_values);
+
+            plan.add_rule_with_severity(Box::new(rule), config.severity);
+        }
+
+        if let Some(severity) = self.require_descriptions {
+            plan.add_rule_with_severity(Box::new(RequireDescriptions::new()), severity);
+        }
+
+        if let Some(config) = &self.input_type_suffix {
+            plan.add_rule_with_severity(
+                Box::new(InputTypeSuffix::with_suffix(&config.suffix)),
+                config.severity,
+            );
+        }
+
+        if let Some(severity) = self.no_nullable_boolean_fields {
+            plan.add_rule_with_severity(Box::new(NoNullableBooleanFields::new()), severity);
+        }
+
+        if let Some(severity) = self.require_deprecation_reason {
+            plan.add_rule_with_severity(Box::new(RequireDeprecationReason::new()), severity);
+        }
+
+        if let Some(severity) = self.relay_compliance {
+            plan.add_rule_with_severity(Box::new(RelayCompliance::new()), severity);
+        }
+
+        if let Some(config) = &self.mutation_naming {
+            let rule = match &config.forbidden_prefixes {
+                Some(forbidden_prefixes) => MutationNaming::with_options(
+                    forbidden_prefixes.clone(),
+                    config.payload_suffix.clone(),
+                ),
+                None => MutationNaming::new().with_payload_suffix(config.payload_suffix.clone()),
+            };
+
+            plan.add_rule_with_severity(Box::new(rule), config.severity);
+        }
+
+        plan
+    }
+}
+
+#[test]
+fn schema_lint_config_from_json() {
+    use super::lint_schema;
+    use crate::validation::test_utils::*;
+
+    let config: SchemaLintConfig = serde_json::from_str(
+        r#"{
+          "namingConvention": { "severity": "error" },
+          "requireDescriptions": "warning"
+        }"#,
+    )
+    .expect("Failed to parse lint config");
+    let plan = config.to_lint_plan();
+
+    assert_eq!(plan.rules.len(), 2);
+
+    let schema_ast = graphql_parser::parse_schema(
+        "
+\"The root query type\"
+type Query {
+  \"The current user\"
+  user_name: String
+}
+",
+    )
+    .expect("Failed to parse schema");
+
+    let result = lint_schema(&schema_ast, &plan);
+
+    assert_eq!(
+        get_messages(&result.errors),
+        vec!["Field \"user_name\" should be in camelCase format."]
+    );
+    assert_eq!(result.warnings.len(), 0);
+}
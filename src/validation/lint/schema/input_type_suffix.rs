@@ -0,0 +1,83 @@
+use super::SchemaLintRule;
+use crate::ast::{visit_schema_document, SchemaVisitor, SchemaVisitorContext, VisitorControl};
+use crate::static_graphql::schema::InputObjectType;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Input type suffix
+///
+/// Names of input object types must end with the configured suffix (`Input` by default).
+#[derive(Clone)]
+pub struct InputTypeSuffix {
+    suffix: String,
+}
+
+impl InputTypeSuffix {
+    pub fn new() -> Self {
+        Self::with_suffix("Input")
+    }
+
+    pub fn with_suffix(suffix: &str) -> Self {
+        InputTypeSuffix {
+            suffix: suffix.to_string(),
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for InputTypeSuffix {
+    fn enter_input_object_type(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        input_object: &'a InputObjectType,
+    ) -> VisitorControl {
+        if !input_object.name.ends_with(&self.suffix) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Input type \"{}\" should have \"{}\" suffix.",
+                    input_object.name, self.suffix
+                ),
+                locations: vec![input_object.position],
+            });
+        }
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for InputTypeSuffix {
+    fn error_code<'a>(&self) -> &'a str {
+        "InputTypeSuffix"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(&mut self.clone(), ctx.schema, ctx, error_collector);
+    }
+}
+
+#[test]
+fn input_type_suffix() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+input CreateUserInput {
+  name: String
+}
+
+input UserFilter {
+  name: String
+}
+",
+        Box::new(InputTypeSuffix::new()),
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Input type \"UserFilter\" should have \"Input\" suffix."]
+    );
+}
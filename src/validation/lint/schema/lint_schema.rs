@@ -0,0 +1,88 @@
+use graphql_parser::Pos;
+
+use super::SchemaLintRule;
+use crate::{
+    ast::SchemaVisitorContext,
+    static_graphql::schema::{self, TypeDefinition},
+    validation::{
+        utils::ValidationErrorContext,
+        validate::{RuleSeverities, ValidationResult, ValidationSeverity},
+    },
+};
+
+pub struct SchemaLintPlan {
+    pub rules: Vec<Box<dyn SchemaLintRule>>,
+    pub severities: RuleSeverities,
+}
+
+impl SchemaLintPlan {
+    pub fn new() -> Self {
+        Self {
+            rules: vec![],
+            severities: RuleSeverities::default(),
+        }
+    }
+
+    pub fn from(rules: Vec<Box<dyn SchemaLintRule>>) -> Self {
+        Self {
+            rules,
+            severities: RuleSeverities::default(),
+        }
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn SchemaLintRule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn add_rule_with_severity(
+        &mut self,
+        rule: Box<dyn SchemaLintRule>,
+        severity: ValidationSeverity,
+    ) {
+        self.severities.set(rule.error_code(), severity);
+        self.rules.push(rule);
+    }
+}
+
+/// Runs the rules of the plan on the schema, and returns the findings split by severity. The
+/// locations of the findings point to the definitions they are about.
+pub fn lint_schema<'a>(schema: &'a schema::Document, plan: &'a SchemaLintPlan) -> ValidationResult {
+    let mut result = ValidationResult::default();
+    let mut context = SchemaVisitorContext::new(schema);
+
+    for rule in plan.rules.iter() {
+        let severity = plan.severities.get(rule.error_code());
+
+        if severity == ValidationSeverity::Off {
+            continue;
+        }
+
+        let mut error_collector = ValidationErrorContext::new();
+        rule.lint(&mut context, &mut error_collector);
+        result.append(severity, &mut error_collector.errors);
+    }
+
+    result
+}
+
+pub(crate) fn type_definition_position(type_definition: &TypeDefinition) -> Pos {
+    match type_definition {
+        TypeDefinition::Object(o) => o.position,
+        TypeDefinition::Interface(i) => i.position,
+        TypeDefinition::Union(u) => u.position,
+        TypeDefinition::Scalar(s) => s.position,
+        TypeDefinition::Enum(e) => e.position,
+        TypeDefinition::InputObject(i) => i.position,
+    }
+}
+
+pub(crate) fn type_definition_description(type_definition: &TypeDefinition) -> Option<&String> {
+    match type_definition {
+        TypeDefinition::Object(o) => o.description.as_ref(),
+        TypeDefinition::Interface(i) => i.description.as_ref(),
+        TypeDefinition::Union(u) => u.description.as_ref(),
+        TypeDefinition::Scalar(s) => s.description.as_ref(),
+        TypeDefinition::Enum(e) => e.description.as_ref(),
+        TypeDefinition::InputObject(i) => i.description.as_ref(),
+    }
+}
@@ -0,0 +1,27 @@
+//! Lint rules for schemas.
+//!
+//! These rules enforce style and design conventions on a schema, using `SchemaVisitor`. They are
+//! executed with a `SchemaLintPlan` (or configured with a `SchemaLintConfig`), and the location of
+//! every finding points to the definition it is about.
+
+pub mod config;
+pub mod input_type_suffix;
+pub mod lint_schema;
+pub mod mutation_naming;
+pub mod no_nullable_boolean_fields;
+pub mod relay_compliance;
+pub mod require_deprecation_reason;
+pub mod require_descriptions;
+pub mod rule;
+pub mod schema_naming_convention;
+
+pub use self::config::*;
+pub use self::input_type_suffix::*;
+pub use self::lint_schema::*;
+pub use self::mutation_naming::*;
+pub use self::no_nullable_boolean_fields::*;
+pub use self::relay_compliance::*;
+pub use self::require_deprecation_reason::*;
+pub use self::require_descriptions::*;
+pub use self::rule::*;
+pub use self::schema_naming_convention::*;
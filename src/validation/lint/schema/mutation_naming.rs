@@ -0,0 +1,147 @@
+use super::SchemaLintRule;
+use crate::ast::{
+    visit_schema_document, SchemaDocumentExtension, SchemaVisitor, SchemaVisitorContext,
+    TypeExtension, VisitorControl,
+};
+use crate::static_graphql::schema::Field;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Mutation naming
+///
+/// Fields of the mutation type must describe an action: their names must not start with one of the
+/// forbidden prefixes (`get`, `list`, `fetch` and `query` by default), and when configured, the
+/// names of their return types must end with a suffix (like `Payload`).
+#[derive(Clone)]
+pub struct MutationNaming {
+    forbidden_prefixes: Vec<String>,
+    payload_suffix: Option<String>,
+}
+
+impl MutationNaming {
+    pub fn new() -> Self {
+        MutationNaming {
+            forbidden_prefixes: vec![
+                "get".to_string(),
+                "list".to_string(),
+                "fetch".to_string(),
+                "query".to_string(),
+            ],
+            payload_suffix: None,
+        }
+    }
+
+    pub fn with_payload_suffix(mut self, payload_suffix: Option<String>) -> Self {
+        self.payload_suffix = payload_suffix;
+        self
+    }
+
+    pub fn with_options(forbidden_prefixes: Vec<String>, payload_suffix: Option<String>) -> Self {
+        MutationNaming {
+            forbidden_prefixes,
+            payload_suffix,
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for MutationNaming {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        // Without a schema definition, the mutation type is the type named `Mutation`
+        let mutation_type_name = visitor_context
+            .schema
+            .mutation_type()
+            .map_or("Mutation", |mutation_type| mutation_type.name.as_str());
+
+        if visitor_context.current_type_name() != Some(mutation_type_name) {
+            return VisitorControl::SkipChildren;
+        }
+
+        if let Some(prefix) = self
+            .forbidden_prefixes
+            .iter()
+            .find(|prefix| field.name.starts_with(prefix.as_str()))
+        {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Mutation \"{}\" should not start with \"{}\".",
+                    field.name, prefix
+                ),
+                locations: vec![field.position],
+            });
+        }
+
+        if let Some(suffix) = &self.payload_suffix {
+            let return_type = field.field_type.inner_type();
+
+            if !return_type.ends_with(suffix.as_str()) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: format!(
+                        "Mutation \"{}\" should return a type with \"{}\" suffix, found \"{}\".",
+                        field.name, suffix, return_type
+                    ),
+                    locations: vec![field.position],
+                });
+            }
+        }
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for MutationNaming {
+    fn error_code<'a>(&self) -> &'a str {
+        "MutationNaming"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(&mut self.clone(), ctx.schema, ctx, error_collector);
+    }
+}
+
+#[test]
+fn mutation_naming() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+type Query {
+  getUser: User
+}
+
+type Mutation {
+  createUser: CreateUserPayload
+  getOrCreateUser: User
+}
+
+type User {
+  name: String
+}
+
+type CreateUserPayload {
+  user: User
+}
+",
+        Box::new(MutationNaming::with_options(
+            vec!["get".to_string()],
+            Some("Payload".to_string()),
+        )),
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Mutation \"getOrCreateUser\" should not start with \"get\".",
+            "Mutation \"getOrCreateUser\" should return a type with \"Payload\" suffix, found \"User\"."
+        ]
+    );
+}
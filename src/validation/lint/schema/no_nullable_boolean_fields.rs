@@ -0,0 +1,81 @@
+use super::SchemaLintRule;
+use crate::ast::{visit_schema_document, SchemaVisitor, SchemaVisitorContext, VisitorControl};
+use crate::static_graphql::schema::{Field, Type};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// No nullable Boolean fields
+///
+/// Fields of type `Boolean` must be non-nullable, as a `null` value for a boolean field is
+/// ambiguous for clients.
+pub struct NoNullableBooleanFields;
+
+impl NoNullableBooleanFields {
+    pub fn new() -> Self {
+        NoNullableBooleanFields
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for NoNullableBooleanFields {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        if let Type::NamedType(type_name) = &field.field_type {
+            if type_name == "Boolean" {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: format!(
+                        "Field \"{}.{}\" of type \"Boolean\" should be non-nullable.",
+                        visitor_context.current_type_name().unwrap_or_default(),
+                        field.name
+                    ),
+                    locations: vec![field.position],
+                });
+            }
+        }
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for NoNullableBooleanFields {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoNullableBooleanFields"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(
+            &mut NoNullableBooleanFields::new(),
+            ctx.schema,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn nullable_boolean_fields() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+type User {
+  isAdmin: Boolean!
+  isActive: Boolean
+  flags: [Boolean]
+}
+",
+        Box::new(NoNullableBooleanFields::new()),
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Field \"User.isActive\" of type \"Boolean\" should be non-nullable."]
+    );
+}
@@ -0,0 +1,541 @@
+use std::collections::HashSet;
+
+use graphql_parser::Pos;
+
+use super::{type_definition_position, SchemaLintRule};
+use crate::ast::{
+    visit_schema_document, SchemaDocumentExtension, SchemaVisitor, SchemaVisitorContext,
+    TypeExtension, VisitorControl,
+};
+use crate::static_graphql::schema::{
+    self, Definition, Document, Field, ObjectType, Type, TypeDefinition,
+};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Relay compliance
+///
+/// A schema is compliant with the Relay server specification when:
+///
+/// - it has a `Node` interface with an `id: ID!` field,
+/// - its query type has a `node(id: ID!): Node` field,
+/// - the connection types (object types whose name ends with `Connection`) have an `edges` field
+///   returning a list of edge types, and a `pageInfo: PageInfo!` field,
+/// - the edge types have a `node` field that does not return a list, and a `cursor` field,
+/// - the `PageInfo` type has the `hasPreviousPage: Boolean!`, `hasNextPage: Boolean!`,
+///   `startCursor` and `endCursor` fields,
+/// - the fields returning a connection type have the forward (`first` and `after`) and/or the
+///   backward (`last` and `before`) pagination arguments.
+///
+/// Fields added with type extensions are taken into account.
+///
+/// https://relay.dev/docs/guides/graphql-server-specification/
+pub struct RelayCompliance {
+    /// The edge types already checked: an edge type can be shared by several connection types.
+    checked_edge_types: HashSet<String>,
+}
+
+impl RelayCompliance {
+    pub fn new() -> Self {
+        RelayCompliance {
+            checked_edge_types: HashSet::new(),
+        }
+    }
+
+    fn report(&self, user_context: &mut ValidationErrorContext, message: String, position: Pos) {
+        user_context.report_error(ValidationError {
+            error_code: self.error_code(),
+            message,
+            locations: vec![position],
+        });
+    }
+
+    fn query_type<'a>(&self, schema: &'a Document) -> Option<&'a ObjectType> {
+        schema
+            .schema_definition()
+            .query
+            .as_ref()
+            .and_then(|name| schema.object_type_by_name(name))
+    }
+}
+
+/// Checks the schema against the Relay server specification, see `RelayCompliance`.
+pub fn check_relay_compliance(schema: &Document) -> Vec<ValidationError> {
+    let mut context = SchemaVisitorContext::new(schema);
+    let mut error_collector = ValidationErrorContext::new();
+    RelayCompliance::new().lint(&mut context, &mut error_collector);
+
+    error_collector.errors
+}
+
+/// Returns the fields of an object type, including the fields added by its extensions.
+fn object_fields<'a>(schema: &'a Document, type_name: &str) -> Vec<&'a Field> {
+    schema
+        .definitions
+        .iter()
+        .flat_map(|definition| match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object))
+                if object.name == type_name =>
+            {
+                object.fields.iter().collect()
+            }
+            Definition::TypeExtension(schema::TypeExtension::Object(extension))
+                if extension.name == type_name =>
+            {
+                extension.fields.iter().collect()
+            }
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn find_field<'a>(fields: &[&'a Field], name: &str) -> Option<&'a Field> {
+    fields.iter().find(|field| field.name == name).copied()
+}
+
+fn is_named(t: &Type, name: &str) -> bool {
+    matches!(t, Type::NamedType(n) if n == name)
+}
+
+fn is_non_null_named(t: &Type, name: &str) -> bool {
+    matches!(t, Type::NonNullType(inner) if is_named(inner, name))
+}
+
+fn is_list(t: &Type) -> bool {
+    match t {
+        Type::NonNullType(inner) => inner.is_list_type(),
+        _ => t.is_list_type(),
+    }
+}
+
+fn is_connection_type(schema: &Document, type_name: &str) -> bool {
+    type_name.ends_with("Connection") && schema.object_type_by_name(type_name).is_some()
+}
+
+impl RelayCompliance {
+    fn check_node_interface(&self, schema: &Document, user_context: &mut ValidationErrorContext) {
+        let query_type = match self.query_type(schema) {
+            Some(query_type) => query_type,
+            None => {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: "Schema must have a query type.".to_string(),
+                    locations: vec![],
+                });
+                return;
+            }
+        };
+
+        match schema.type_by_name("Node") {
+            Some(TypeDefinition::Interface(node)) => {
+                let has_id = node
+                    .fields
+                    .iter()
+                    .any(|field| field.name == "id" && is_non_null_named(&field.field_type, "ID"));
+
+                if !has_id {
+                    self.report(
+                        user_context,
+                        "Interface \"Node\" must have an \"id\" field that returns \"ID!\"."
+                            .to_string(),
+                        node.position,
+                    );
+                }
+            }
+            Some(other) => self.report(
+                user_context,
+                "Type \"Node\" must be an interface.".to_string(),
+                type_definition_position(other),
+            ),
+            None => self.report(
+                user_context,
+                "Schema must have a \"Node\" interface.".to_string(),
+                query_type.position,
+            ),
+        }
+
+        let query_fields = object_fields(schema, &query_type.name);
+
+        match find_field(&query_fields, "node") {
+            Some(node_field) => {
+                let has_id_argument = node_field
+                    .arguments
+                    .iter()
+                    .any(|arg| arg.name == "id" && is_non_null_named(&arg.value_type, "ID"));
+
+                if !is_named(&node_field.field_type, "Node") || !has_id_argument {
+                    self.report(
+                        user_context,
+                        format!(
+                            "Field \"{}.node\" must be defined as \"node(id: ID!): Node\".",
+                            query_type.name
+                        ),
+                        node_field.position,
+                    );
+                }
+            }
+            None => self.report(
+                user_context,
+                format!(
+                    "Type \"{}\" must have a \"node(id: ID!): Node\" field.",
+                    query_type.name
+                ),
+                query_type.position,
+            ),
+        }
+    }
+
+    fn check_page_info(&self, schema: &Document, user_context: &mut ValidationErrorContext) {
+        let page_info = match schema.object_type_by_name("PageInfo") {
+            Some(page_info) => page_info,
+            None => return,
+        };
+        let fields = object_fields(schema, "PageInfo");
+
+        for name in ["hasPreviousPage", "hasNextPage"] {
+            if !find_field(&fields, name)
+                .is_some_and(|field| is_non_null_named(&field.field_type, "Boolean"))
+            {
+                self.report(
+                    user_context,
+                    format!(
+                        "Type \"PageInfo\" must have a \"{}\" field that returns \"Boolean!\".",
+                        name
+                    ),
+                    page_info.position,
+                );
+            }
+        }
+
+        for name in ["startCursor", "endCursor"] {
+            if find_field(&fields, name)
+                .filter(|field| !is_list(&field.field_type))
+                .is_none()
+            {
+                self.report(
+                    user_context,
+                    format!(
+                        "Type \"PageInfo\" must have a \"{}\" field that returns a cursor.",
+                        name
+                    ),
+                    page_info.position,
+                );
+            }
+        }
+    }
+
+    fn check_connection_type(
+        &mut self,
+        schema: &Document,
+        connection: &ObjectType,
+        user_context: &mut ValidationErrorContext,
+    ) {
+        let fields = object_fields(schema, &connection.name);
+
+        match find_field(&fields, "edges") {
+            Some(edges) if is_list(&edges.field_type) => {
+                let edge_type_name = edges.field_type.inner_type();
+
+                match schema.object_type_by_name(edge_type_name) {
+                    Some(edge) => self.check_edge_type(schema, edge, user_context),
+                    None => self.report(
+                        user_context,
+                        format!(
+                            "Field \"{}.edges\" must return a list of object types.",
+                            connection.name
+                        ),
+                        edges.position,
+                    ),
+                }
+            }
+            _ => self.report(
+                user_context,
+                format!(
+                    "Connection type \"{}\" must have an \"edges\" field that returns a list type.",
+                    connection.name
+                ),
+                connection.position,
+            ),
+        }
+
+        if !find_field(&fields, "pageInfo")
+            .is_some_and(|field| is_non_null_named(&field.field_type, "PageInfo"))
+        {
+            self.report(
+                user_context,
+                format!(
+                    "Connection type \"{}\" must have a \"pageInfo\" field that returns \"PageInfo!\".",
+                    connection.name
+                ),
+                connection.position,
+            );
+        }
+    }
+
+    fn check_edge_type(
+        &mut self,
+        schema: &Document,
+        edge: &ObjectType,
+        user_context: &mut ValidationErrorContext,
+    ) {
+        if !self.checked_edge_types.insert(edge.name.clone()) {
+            return;
+        }
+
+        let fields = object_fields(schema, &edge.name);
+
+        if find_field(&fields, "node")
+            .filter(|field| !is_list(&field.field_type))
+            .is_none()
+        {
+            self.report(
+                user_context,
+                format!(
+                    "Edge type \"{}\" must have a \"node\" field that does not return a list type.",
+                    edge.name
+                ),
+                edge.position,
+            );
+        }
+
+        if find_field(&fields, "cursor")
+            .filter(|field| !is_list(&field.field_type))
+            .is_none()
+        {
+            self.report(
+                user_context,
+                format!(
+                    "Edge type \"{}\" must have a \"cursor\" field that returns a cursor.",
+                    edge.name
+                ),
+                edge.position,
+            );
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for RelayCompliance {
+    fn enter_document(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        document: &'a Document,
+    ) -> VisitorControl {
+        self.check_node_interface(document, user_context);
+        self.check_page_info(document, user_context);
+
+        VisitorControl::Continue
+    }
+
+    fn enter_object_type(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        object_type: &'a ObjectType,
+    ) -> VisitorControl {
+        if object_type.name.ends_with("Connection") {
+            self.check_connection_type(visitor_context.schema, object_type, user_context);
+        }
+
+        VisitorControl::Continue
+    }
+
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        let return_type = field.field_type.inner_type();
+
+        if !is_connection_type(visitor_context.schema, return_type) {
+            return VisitorControl::SkipChildren;
+        }
+
+        let has_argument = |name: &str| field.arguments.iter().any(|arg| arg.name == name);
+        let is_int_argument = |name: &str| {
+            field
+                .arguments
+                .iter()
+                .any(|arg| arg.name == name && is_named(&arg.value_type, "Int"))
+        };
+
+        let forward = is_int_argument("first") && has_argument("after");
+        let backward = is_int_argument("last") && has_argument("before");
+
+        if !forward && !backward {
+            self.report(
+                user_context,
+                format!(
+                    "Field \"{}.{}\" returns a connection and must have the \"first: Int\" and \"after\" arguments, and/or the \"last: Int\" and \"before\" arguments.",
+                    visitor_context.current_type_name().unwrap_or_default(),
+                    field.name
+                ),
+                field.position,
+            );
+        }
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for RelayCompliance {
+    fn error_code<'a>(&self) -> &'a str {
+        "RelayCompliance"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(
+            &mut RelayCompliance::new(),
+            ctx.schema,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[cfg(test)]
+static RELAY_SCHEMA: &str = "
+interface Node {
+  id: ID!
+}
+
+type PageInfo {
+  hasPreviousPage: Boolean!
+  hasNextPage: Boolean!
+  startCursor: String
+  endCursor: String
+}
+
+type User implements Node {
+  id: ID!
+  friends(first: Int, after: String, last: Int, before: String): UserConnection
+}
+
+type UserConnection {
+  edges: [UserEdge]
+  pageInfo: PageInfo!
+}
+
+type UserEdge {
+  node: User
+  cursor: String!
+}
+
+type Query {
+  viewer: User
+}
+
+extend type Query {
+  node(id: ID!): Node
+  users(first: Int, after: String): UserConnection
+}
+";
+
+#[test]
+fn compliant_schema() {
+    let schema = graphql_parser::parse_schema(RELAY_SCHEMA)
+        .expect("Failed to parse schema")
+        .into_static();
+
+    let errors = check_relay_compliance(&schema);
+    assert_eq!(errors.len(), 0);
+}
+
+#[test]
+fn non_compliant_schema() {
+    use crate::validation::test_utils::*;
+
+    let schema = graphql_parser::parse_schema(
+        "
+interface Node {
+  id: ID
+}
+
+type PageInfo {
+  hasNextPage: Boolean
+  startCursor: String
+  endCursor: String
+}
+
+type User implements Node {
+  id: ID
+  friends(first: Int): UserConnection
+}
+
+type UserConnection {
+  edges: [UserEdge]
+  pageInfo: PageInfo
+}
+
+type UserEdge {
+  node: [User]
+}
+
+type PostConnection {
+  edges: UserEdge
+  pageInfo: PageInfo!
+}
+
+type Query {
+  viewer: User
+}
+",
+    )
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let errors = check_relay_compliance(&schema);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Interface \"Node\" must have an \"id\" field that returns \"ID!\".",
+            "Type \"Query\" must have a \"node(id: ID!): Node\" field.",
+            "Type \"PageInfo\" must have a \"hasPreviousPage\" field that returns \"Boolean!\".",
+            "Type \"PageInfo\" must have a \"hasNextPage\" field that returns \"Boolean!\".",
+            "Field \"User.friends\" returns a connection and must have the \"first: Int\" and \"after\" arguments, and/or the \"last: Int\" and \"before\" arguments.",
+            "Edge type \"UserEdge\" must have a \"node\" field that does not return a list type.",
+            "Edge type \"UserEdge\" must have a \"cursor\" field that returns a cursor.",
+            "Connection type \"UserConnection\" must have a \"pageInfo\" field that returns \"PageInfo!\".",
+            "Connection type \"PostConnection\" must have an \"edges\" field that returns a list type."
+        ]
+    );
+    assert_eq!(errors[0].locations, vec![Pos { line: 2, column: 1 }]);
+}
+
+#[test]
+fn shared_edge_type() {
+    use crate::validation::test_utils::*;
+
+    let schema = graphql_parser::parse_schema(&format!(
+        "{}
+type UserFriendsConnection {{
+  edges: [BrokenEdge]
+  pageInfo: PageInfo!
+}}
+
+type UserFollowersConnection {{
+  edges: [BrokenEdge]
+  pageInfo: PageInfo!
+}}
+
+type BrokenEdge {{
+  node: User
+}}
+",
+        RELAY_SCHEMA
+    ))
+    .expect("Failed to parse schema")
+    .into_static();
+
+    let errors = check_relay_compliance(&schema);
+
+    assert_eq!(
+        get_messages(&errors),
+        vec!["Edge type \"BrokenEdge\" must have a \"cursor\" field that returns a cursor."]
+    );
+}
@@ -0,0 +1,145 @@
+use graphql_parser::Pos;
+
+use super::SchemaLintRule;
+use crate::ast::{visit_schema_document, SchemaVisitor, SchemaVisitorContext, VisitorControl};
+use crate::static_graphql::schema::{Directive, EnumValue, Field, InputValue, Value};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Require deprecation reason
+///
+/// Usages of the `@deprecated` directive must have a non-empty `reason` argument.
+pub struct RequireDeprecationReason;
+
+impl RequireDeprecationReason {
+    pub fn new() -> Self {
+        RequireDeprecationReason
+    }
+}
+
+impl RequireDeprecationReason {
+    /// Reports a `@deprecated` directive without a reason, at the position of the deprecated
+    /// element.
+    fn check_directives(
+        &self,
+        user_context: &mut ValidationErrorContext,
+        directives: &[Directive],
+        position: Pos,
+    ) {
+        let missing_reason = directives
+            .iter()
+            .filter(|directive| directive.name == "deprecated")
+            .any(|directive| {
+                !directive.arguments.iter().any(|(name, value)| {
+                    name == "reason"
+                        && matches!(value, Value::String(reason) if !reason.trim().is_empty())
+                })
+            });
+
+        if missing_reason {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: "Directive \"@deprecated\" should have a reason.".to_string(),
+                locations: vec![position],
+            });
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for RequireDeprecationReason {
+    fn enter_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        self.check_directives(user_context, &field.directives, field.position);
+
+        VisitorControl::Continue
+    }
+
+    fn enter_argument(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        argument: &'a InputValue,
+    ) -> VisitorControl {
+        self.check_directives(user_context, &argument.directives, argument.position);
+
+        VisitorControl::SkipChildren
+    }
+
+    fn enter_input_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        input_field: &'a InputValue,
+    ) -> VisitorControl {
+        self.check_directives(user_context, &input_field.directives, input_field.position);
+
+        VisitorControl::SkipChildren
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        enum_value: &'a EnumValue,
+    ) -> VisitorControl {
+        self.check_directives(user_context, &enum_value.directives, enum_value.position);
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for RequireDeprecationReason {
+    fn error_code<'a>(&self) -> &'a str {
+        "RequireDeprecationReason"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(
+            &mut RequireDeprecationReason::new(),
+            ctx.schema,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn deprecation_reasons() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+type User {
+  name: String @deprecated(reason: \"Use fullName.\")
+  firstName: String @deprecated
+  lastName: String @deprecated(reason: \"\")
+  friends(first: Int @deprecated): [User]
+}
+
+enum Role {
+  ADMIN @deprecated
+}
+",
+        Box::new(RequireDeprecationReason::new()),
+    );
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(
+        errors[0].locations,
+        vec![graphql_parser::Pos { line: 4, column: 3 }]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![graphql_parser::Pos {
+            line: 6,
+            column: 11
+        }]
+    );
+}
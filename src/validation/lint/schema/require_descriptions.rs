@@ -0,0 +1,146 @@
+use super::{type_definition_description, type_definition_position, SchemaLintRule};
+use crate::ast::{
+    visit_schema_document, SchemaVisitor, SchemaVisitorContext, TypeDefinitionExtension,
+    VisitorControl,
+};
+use crate::static_graphql::schema::{Field, InputValue, TypeDefinition};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Require descriptions
+///
+/// Every type, field and input field must have a non-empty description.
+pub struct RequireDescriptions;
+
+impl RequireDescriptions {
+    pub fn new() -> Self {
+        RequireDescriptions
+    }
+}
+
+fn is_missing(description: Option<&String>) -> bool {
+    description.filter(|d| !d.trim().is_empty()).is_none()
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for RequireDescriptions {
+    fn enter_type_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        type_definition: &'a TypeDefinition,
+    ) -> VisitorControl {
+        if type_definition.name().starts_with("__") {
+            return VisitorControl::SkipChildren;
+        }
+
+        if is_missing(type_definition_description(type_definition)) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Type \"{}\" should have a description.",
+                    type_definition.name()
+                ),
+                locations: vec![type_definition_position(type_definition)],
+            });
+        }
+
+        VisitorControl::Continue
+    }
+
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        if is_missing(field.description.as_ref()) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Field \"{}.{}\" should have a description.",
+                    visitor_context.current_type_name().unwrap_or_default(),
+                    field.name
+                ),
+                locations: vec![field.position],
+            });
+        }
+
+        VisitorControl::SkipChildren
+    }
+
+    fn enter_input_field(
+        &mut self,
+        visitor_context: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        input_field: &'a InputValue,
+    ) -> VisitorControl {
+        if is_missing(input_field.description.as_ref()) {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "Input field \"{}.{}\" should have a description.",
+                    visitor_context.current_type_name().unwrap_or_default(),
+                    input_field.name
+                ),
+                locations: vec![input_field.position],
+            });
+        }
+
+        VisitorControl::SkipChildren
+    }
+}
+
+impl SchemaLintRule for RequireDescriptions {
+    fn error_code<'a>(&self) -> &'a str {
+        "RequireDescriptions"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(
+            &mut RequireDescriptions::new(),
+            ctx.schema,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn missing_descriptions() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+\"The root query type\"
+type Query {
+  \"The current user\"
+  me: User
+}
+
+type User {
+  name: String
+}
+
+\"\"\"
+Filter of users
+\"\"\"
+input UserFilter {
+  \"\"
+  name: String
+}
+",
+        Box::new(RequireDescriptions::new()),
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Type \"User\" should have a description.",
+            "Field \"User.name\" should have a description.",
+            "Input field \"UserFilter.name\" should have a description."
+        ]
+    );
+}
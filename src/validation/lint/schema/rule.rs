@@ -0,0 +1,11 @@
+use crate::{ast::SchemaVisitorContext, validation::utils::ValidationErrorContext};
+
+pub trait SchemaLintRule: Send + Sync {
+    fn lint<'a>(
+        &self,
+        _ctx: &mut SchemaVisitorContext<'a>,
+        _error_collector: &mut ValidationErrorContext,
+    ) -> ();
+
+    fn error_code<'a>(&self) -> &'a str;
+}
@@ -0,0 +1,228 @@
+use super::{type_definition_position, SchemaLintRule};
+use crate::ast::{
+    visit_schema_document, SchemaVisitor, SchemaVisitorContext, TypeDefinitionExtension,
+    VisitorControl,
+};
+use crate::static_graphql::schema::{EnumValue, Field, InputValue, TypeDefinition};
+use crate::validation::lint::NamingConvention;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Schema naming convention
+///
+/// Names of types, fields (including arguments and input fields) and enum values must follow the
+/// configured naming conventions. By default, types are `PascalCase`, fields are `camelCase` and
+/// enum values are `UPPER_CASE`. Names starting with `__` are reserved for introspection and are
+/// not checked.
+#[derive(Clone)]
+pub struct SchemaNamingConvention {
+    pub types: Option<NamingConvention>,
+    pub fields: Option<NamingConvention>,
+    pub enum_values: Option<NamingConvention>,
+}
+
+impl SchemaNamingConvention {
+    pub fn new() -> Self {
+        SchemaNamingConvention {
+            types: Some(NamingConvention::PascalCase),
+            fields: Some(NamingConvention::CamelCase),
+            enum_values: Some(NamingConvention::UpperCase),
+        }
+    }
+
+    fn check(
+        &self,
+        user_context: &mut ValidationErrorContext,
+        convention: Option<NamingConvention>,
+        kind: &str,
+        name: &str,
+        position: graphql_parser::Pos,
+    ) {
+        if name.starts_with("__") {
+            return;
+        }
+
+        if let Some(convention) = convention {
+            if !convention.is_match(name) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    message: format!(
+                        "{} \"{}\" should be in {} format.",
+                        kind,
+                        name,
+                        convention.as_str()
+                    ),
+                    locations: vec![position],
+                });
+            }
+        }
+    }
+}
+
+impl<'a> SchemaVisitor<'a, ValidationErrorContext> for SchemaNamingConvention {
+    fn enter_type_definition(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        type_definition: &'a TypeDefinition,
+    ) -> VisitorControl {
+        self.check(
+            user_context,
+            self.types,
+            "Type",
+            type_definition.name(),
+            type_definition_position(type_definition),
+        );
+
+        VisitorControl::Continue
+    }
+
+    fn enter_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &'a Field,
+    ) -> VisitorControl {
+        self.check(
+            user_context,
+            self.fields,
+            "Field",
+            &field.name,
+            field.position,
+        );
+
+        VisitorControl::Continue
+    }
+
+    fn enter_argument(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        argument: &'a InputValue,
+    ) -> VisitorControl {
+        self.check(
+            user_context,
+            self.fields,
+            "Argument",
+            &argument.name,
+            argument.position,
+        );
+
+        VisitorControl::Continue
+    }
+
+    fn enter_input_field(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        input_field: &'a InputValue,
+    ) -> VisitorControl {
+        self.check(
+            user_context,
+            self.fields,
+            "Input field",
+            &input_field.name,
+            input_field.position,
+        );
+
+        VisitorControl::Continue
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        _: &mut SchemaVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        enum_value: &'a EnumValue,
+    ) -> VisitorControl {
+        self.check(
+            user_context,
+            self.enum_values,
+            "Enum value",
+            &enum_value.name,
+            enum_value.position,
+        );
+
+        VisitorControl::Continue
+    }
+}
+
+impl SchemaLintRule for SchemaNamingConvention {
+    fn error_code<'a>(&self) -> &'a str {
+        "SchemaNamingConvention"
+    }
+
+    fn lint<'a>(
+        &self,
+        ctx: &mut SchemaVisitorContext<'a>,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_schema_document(&mut self.clone(), ctx.schema, ctx, error_collector);
+    }
+}
+
+#[test]
+fn valid_names() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+type Query {
+  user(userId: ID): User
+}
+
+type User {
+  firstName: String
+  role: Role
+}
+
+enum Role {
+  ADMIN
+  REGULAR_USER
+}
+",
+        Box::new(SchemaNamingConvention::new()),
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn invalid_names() {
+    use crate::validation::test_utils::*;
+
+    let errors = test_schema_lint(
+        "
+type Query {
+  User(user_id: ID): user
+}
+
+type user {
+  first_name: String
+}
+
+input UserFilter {
+  Name: String
+}
+
+enum Role {
+  admin
+}
+",
+        Box::new(SchemaNamingConvention::new()),
+    );
+
+    assert_eq!(
+        get_messages(&errors),
+        vec![
+            "Field \"User\" should be in camelCase format.",
+            "Argument \"user_id\" should be in camelCase format.",
+            "Type \"user\" should be in PascalCase format.",
+            "Field \"first_name\" should be in camelCase format.",
+            "Input field \"Name\" should be in camelCase format.",
+            "Enum value \"admin\" should be in UPPER_CASE format."
+        ]
+    );
+    assert_eq!(
+        errors[2].locations,
+        vec![graphql_parser::Pos { line: 6, column: 1 }]
+    );
+}
@@ -1,3 +1,8 @@
+pub mod defer_stream;
+pub mod field_set;
+pub mod incremental;
+pub mod lint;
+pub mod one_of;
 pub mod rules;
 pub mod utils;
 pub mod validate;
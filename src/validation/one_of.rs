@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+
+use crate::ast::{
+    merge_type_extensions, OneOfExtension, OperationDefinitionExtension, SchemaDocumentExtension,
+    TypeExtension,
+};
+use crate::static_graphql::query::{self, OperationDefinition};
+use crate::static_graphql::schema::{Definition, Document, TypeDefinition};
+use crate::validation::utils::ValidationError;
+
+/// Validates the `@oneOf` input objects of a schema: their fields must be nullable, and must not
+/// have a default value. Type extensions are taken into account.
+///
+/// See https://github.com/graphql/graphql-spec/pull/825
+pub fn validate_one_of_input_objects(schema: &Document) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    for definition in &merge_type_extensions(schema).definitions {
+        let input_object = match definition {
+            Definition::TypeDefinition(TypeDefinition::InputObject(input_object))
+                if input_object.is_one_of() =>
+            {
+                input_object
+            }
+            _ => continue,
+        };
+
+        for field in &input_object.fields {
+            if field.value_type.is_non_null() {
+                errors.push(ValidationError {
+                    error_code: "OneOfInputObjects",
+                    locations: vec![field.position],
+                    message: format!(
+                        "OneOf input field {}.{} must be nullable.",
+                        input_object.name, field.name
+                    ),
+                });
+            }
+
+            if field.default_value.is_some() {
+                errors.push(ValidationError {
+                    error_code: "OneOfInputObjects",
+                    locations: vec![field.position],
+                    message: format!(
+                        "OneOf input field {}.{} cannot have a default value.",
+                        input_object.name, field.name
+                    ),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Validates the values of the variables of an operation against the `@oneOf` input objects, as
+/// variable coercion does: exactly one field of a `@oneOf` input object must be provided, with a
+/// non-null value. The `@oneOf` input objects nested in input objects and lists are validated too.
+///
+/// The other checks of variable coercion are not done: this only complements the validation
+/// rules, which check the `@oneOf` input objects written in the operation.
+pub fn validate_one_of_variables(
+    schema: &Document,
+    operation: &OperationDefinition,
+    variables: &HashMap<String, JsonValue>,
+) -> Vec<ValidationError> {
+    let schema = merge_type_extensions(schema);
+    let mut errors = vec![];
+
+    for variable_definition in operation.variable_definitions() {
+        if let Some(value) = variables.get(&variable_definition.name) {
+            let mut messages = vec![];
+            validate_one_of_value(&schema, &variable_definition.var_type, value, &mut messages);
+
+            errors.extend(messages.into_iter().map(|message| ValidationError {
+                error_code: "OneOfVariableValues",
+                locations: vec![variable_definition.position],
+                message: format!(
+                    "Variable \"${}\" got invalid value: {}",
+                    variable_definition.name, message
+                ),
+            }));
+        }
+    }
+
+    errors
+}
+
+fn validate_one_of_value(
+    schema: &Document,
+    value_type: &query::Type,
+    value: &JsonValue,
+    messages: &mut Vec<String>,
+) {
+    match (value_type, value) {
+        (query::Type::NonNullType(inner), _) => {
+            validate_one_of_value(schema, inner, value, messages)
+        }
+        (query::Type::ListType(inner), JsonValue::Array(items)) => {
+            for item in items {
+                validate_one_of_value(schema, inner, item, messages);
+            }
+        }
+        // A single value is coerced to a list of one item
+        (query::Type::ListType(inner), _) => validate_one_of_value(schema, inner, value, messages),
+        (query::Type::NamedType(name), JsonValue::Object(fields)) => {
+            let input_object = match schema.type_by_name(name) {
+                Some(TypeDefinition::InputObject(input_object)) => input_object,
+                _ => return,
+            };
+
+            if input_object.is_one_of() {
+                match fields.iter().next() {
+                    Some((field_name, JsonValue::Null)) if fields.len() == 1 => {
+                        messages.push(format!(
+                            "Field \"{}.{}\" must be non-null.",
+                            input_object.name, field_name
+                        ))
+                    }
+                    Some(_) if fields.len() == 1 => {}
+                    _ => messages.push(format!(
+                        "OneOf Input Object \"{}\" must specify exactly one key.",
+                        input_object.name
+                    )),
+                }
+            }
+
+            for field in &input_object.fields {
+                if let Some(field_value) = fields.get(&field.name) {
+                    validate_one_of_value(schema, &field.value_type, field_value, messages);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::test_utils::get_messages;
+
+    fn validate(schema: &str) -> Vec<ValidationError> {
+        let schema = graphql_parser::parse_schema(schema)
+            .expect("Failed to parse schema")
+            .into_static();
+
+        validate_one_of_input_objects(&schema)
+    }
+
+    #[test]
+    fn valid_one_of_input_objects() {
+        assert_eq!(
+            validate(
+                "
+input PetInput @oneOf {
+  cat: String
+  dog: String
+}
+
+input Filter {
+  id: ID!
+  first: Int = 10
+}
+"
+            )
+            .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn invalid_one_of_input_objects() {
+        let errors = validate(
+            "
+input PetInput @oneOf {
+  cat: String!
+  dog: String = \"Rex\"
+}
+
+extend input PetInput {
+  fish: [String]!
+}
+",
+        );
+
+        assert_eq!(
+            get_messages(&errors),
+            vec![
+                "OneOf input field PetInput.cat must be nullable.",
+                "OneOf input field PetInput.dog cannot have a default value.",
+                "OneOf input field PetInput.fish must be nullable."
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_variables() {
+        use crate::static_graphql::query::Definition;
+
+        let schema = graphql_parser::parse_schema(
+            "
+input PetInput @oneOf {
+  cat: String
+  dog: String
+}
+
+input OwnerInput {
+  pets: [PetInput!]
+}
+",
+        )
+        .expect("Failed to parse schema")
+        .into_static();
+        let document = graphql_parser::parse_query(
+            "query ($pet: PetInput!, $owner: OwnerInput, $other: PetInput) { __typename }",
+        )
+        .expect("Failed to parse query")
+        .into_static();
+        let operation = match &document.definitions[0] {
+            Definition::Operation(operation) => operation,
+            _ => unreachable!(),
+        };
+
+        let errors = validate_one_of_variables(
+            &schema,
+            operation,
+            &HashMap::from([
+                ("pet".to_string(), serde_json::json!({ "cat": "Tom" })),
+                (
+                    "owner".to_string(),
+                    serde_json::json!({ "pets": [{ "cat": "Tom", "dog": "Rex" }, { "dog": null }] }),
+                ),
+                ("other".to_string(), serde_json::json!({})),
+            ]),
+        );
+
+        assert_eq!(
+            get_messages(&errors),
+            vec![
+                "Variable \"$owner\" got invalid value: OneOf Input Object \"PetInput\" must specify exactly one key.",
+                "Variable \"$owner\" got invalid value: Field \"PetInput.dog\" must be non-null.",
+                "Variable \"$other\" got invalid value: OneOf Input Object \"PetInput\" must specify exactly one key."
+            ]
+        );
+    }
+}
@@ -1,17 +1,18 @@
 use crate::validation::validate::ValidationPlan;
 
 use super::{
-    FieldsOnCorrectType, FragmentsOnCompositeTypes, KnownArgumentNames, KnownDirectives,
-    KnownFragmentNames, KnownTypeNames, LeafFieldSelections, LoneAnonymousOperation,
-    NoFragmentsCycle, NoUndefinedVariables, NoUnusedFragments, NoUnusedVariables,
-    OverlappingFieldsCanBeMerged, PossibleFragmentSpreads, ProvidedRequiredArguments,
-    SingleFieldSubscriptions, UniqueArgumentNames, UniqueDirectivesPerLocation,
-    UniqueFragmentNames, UniqueOperationNames, UniqueVariableNames, ValuesOfCorrectType,
-    VariablesAreInputTypes, VariablesInAllowedPosition,
+    DeferStreamDirectiveLabel, DeferStreamDirectiveOnRootField,
+    DeferStreamDirectiveOnValidOperations, FieldsOnCorrectType, FragmentsOnCompositeTypes,
+    KnownArgumentNames, KnownDirectives, KnownFragmentNames, KnownTypeNames, LeafFieldSelections,
+    LoneAnonymousOperation, NoFragmentsCycle, NoUndefinedVariables, NoUnusedFragments,
+    NoUnusedVariables, OverlappingFieldsCanBeMerged, PossibleFragmentSpreads,
+    ProvidedRequiredArguments, SingleFieldSubscriptions, StreamDirectiveOnListField,
+    UniqueArgumentNames, UniqueDirectivesPerLocation, UniqueFragmentNames, UniqueOperationNames,
+    UniqueVariableNames, ValuesOfCorrectType, VariablesAreInputTypes, VariablesInAllowedPosition,
 };
 
 pub fn default_rules_validation_plan() -> ValidationPlan {
-    let mut plan = ValidationPlan { rules: vec![] };
+    let mut plan = ValidationPlan::new();
 
     plan.add_rule(Box::new(UniqueOperationNames::new()));
     plan.add_rule(Box::new(LoneAnonymousOperation::new()));
@@ -37,6 +38,10 @@ pub fn default_rules_validation_plan() -> ValidationPlan {
     plan.add_rule(Box::new(VariablesInAllowedPosition::new()));
     plan.add_rule(Box::new(ValuesOfCorrectType::new()));
     plan.add_rule(Box::new(UniqueDirectivesPerLocation::new()));
+    plan.add_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    plan.add_rule(Box::new(DeferStreamDirectiveOnValidOperations::new()));
+    plan.add_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    plan.add_rule(Box::new(StreamDirectiveOnListField::new()));
 
     plan
 }
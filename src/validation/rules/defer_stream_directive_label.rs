@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use super::ValidationRule;
+use crate::ast::{visit_document, OperationVisitor, OperationVisitorContext};
+use crate::static_graphql::query::{Directive, Value};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use graphql_parser::Pos;
+
+/// Defer and stream directive labels are unique
+///
+/// A GraphQL document is only valid if the `label` arguments of the defer and stream directives
+/// are static strings, and are unique across the document.
+///
+/// See https://github.com/graphql/graphql-spec/pull/742
+pub struct DeferStreamDirectiveLabel {
+    known_labels: HashMap<String, Pos>,
+}
+
+impl DeferStreamDirectiveLabel {
+    pub fn new() -> Self {
+        DeferStreamDirectiveLabel {
+            known_labels: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for DeferStreamDirectiveLabel {
+    fn enter_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        if directive.name != "defer" && directive.name != "stream" {
+            return;
+        }
+
+        let label = match directive.arguments.iter().find(|(name, _)| name == "label") {
+            Some((_, label)) => label,
+            None => return,
+        };
+
+        match label {
+            Value::String(label) => match self.known_labels.get(label) {
+                Some(known_position) => user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: vec![*known_position, directive.position],
+                    message: "Defer/Stream directive label argument must be unique.".to_string(),
+                }),
+                None => {
+                    self.known_labels.insert(label.clone(), directive.position);
+                }
+            },
+            _ => user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                locations: vec![directive.position],
+                message: format!(
+                    "Argument \"@{}(label:)\" must be a static string.",
+                    directive.name
+                ),
+            }),
+        }
+    }
+}
+
+impl ValidationRule for DeferStreamDirectiveLabel {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveLabel"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut DeferStreamDirectiveLabel::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn unique_static_labels() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    let errors = test_operation_with_schema(
+        "{
+          human {
+            ... @defer(label: \"name\") {
+              name
+            }
+            relatives @stream(label: \"relatives\") {
+              name
+            }
+            pets @stream {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn duplicate_and_dynamic_labels() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveLabel::new()));
+    let errors = test_operation_with_schema(
+        "query ($label: String) {
+          human {
+            ...HumanFields @defer(label: \"human\")
+            relatives @stream(label: \"human\") {
+              name
+            }
+            pets @stream(label: $label) {
+              name
+            }
+          }
+        }
+
+        fragment HumanFields on Human {
+          name
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Defer/Stream directive label argument must be unique.",
+            "Argument \"@stream(label:)\" must be a static string."
+        ]
+    );
+    assert_eq!(errors[0].locations.len(), 2);
+}
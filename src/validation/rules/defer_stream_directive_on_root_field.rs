@@ -0,0 +1,143 @@
+use super::ValidationRule;
+use crate::ast::{
+    visit_document, OperationVisitor, OperationVisitorContext, SchemaDocumentExtension,
+    TypeDefinitionExtension,
+};
+use crate::static_graphql::query::Directive;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Defer and stream directives are used on valid root field
+///
+/// A GraphQL document is only valid if defer directives are not used on root mutation or
+/// subscription types, and stream directives are not used on root mutation or subscription
+/// fields.
+///
+/// See https://github.com/graphql/graphql-spec/pull/742
+pub struct DeferStreamDirectiveOnRootField;
+
+impl DeferStreamDirectiveOnRootField {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for DeferStreamDirectiveOnRootField {
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        let directive_name = match directive.name.as_str() {
+            "defer" => "Defer",
+            "stream" => "Stream",
+            _ => return,
+        };
+
+        let parent_type_name = match visitor_context.current_parent_type() {
+            Some(parent_type) => parent_type.name(),
+            None => return,
+        };
+
+        let root_type = if visitor_context
+            .schema
+            .mutation_type()
+            .is_some_and(|t| t.name == parent_type_name)
+        {
+            "mutation"
+        } else if visitor_context
+            .schema
+            .subscription_type()
+            .is_some_and(|t| t.name == parent_type_name)
+        {
+            "subscription"
+        } else {
+            return;
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code(),
+            locations: vec![directive.position],
+            message: format!(
+                "{} directive cannot be used on root {} type \"{}\".",
+                directive_name, root_type, parent_type_name
+            ),
+        });
+    }
+}
+
+impl ValidationRule for DeferStreamDirectiveOnRootField {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveOnRootField"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut DeferStreamDirectiveOnRootField::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn defer_and_stream_on_query_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          ... @defer {
+            human {
+              relatives @stream {
+                name
+              }
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn defer_and_stream_on_mutation_and_subscription_root() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnRootField::new()));
+    let errors = test_operation_with_schema(
+        "mutation {
+          ... @defer {
+            fieldB
+          }
+          fieldB @stream
+        }
+
+        subscription {
+          ...SubscriptionFields @defer
+        }
+
+        fragment SubscriptionFields on SubscriptionRoot {
+          fieldB
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Defer directive cannot be used on root mutation type \"MutationRoot\".",
+            "Stream directive cannot be used on root mutation type \"MutationRoot\".",
+            "Defer directive cannot be used on root subscription type \"SubscriptionRoot\"."
+        ]
+    );
+}
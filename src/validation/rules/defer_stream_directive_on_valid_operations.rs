@@ -0,0 +1,184 @@
+use super::ValidationRule;
+use crate::ast::{
+    collect_fragments_in_use, visit_document, OperationVisitor, OperationVisitorContext,
+};
+use crate::static_graphql::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Value,
+};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Defer and stream directives are used on valid operations
+///
+/// A GraphQL document is only valid if defer and stream directives are not used in subscription
+/// operations (or in the fragments they use), unless they are disabled with their `if`
+/// argument.
+///
+/// See https://github.com/graphql/graphql-spec/pull/742
+pub struct DeferStreamDirectiveOnValidOperations<'a> {
+    subscription_fragments: Vec<&'a str>,
+    in_subscription: bool,
+}
+
+impl<'a> DeferStreamDirectiveOnValidOperations<'a> {
+    pub fn new() -> Self {
+        DeferStreamDirectiveOnValidOperations {
+            subscription_fragments: vec![],
+            in_subscription: false,
+        }
+    }
+}
+
+fn if_argument_can_be_false(directive: &Directive) -> bool {
+    directive.arguments.iter().any(|(name, value)| {
+        name == "if" && matches!(value, Value::Boolean(false) | Value::Variable(_))
+    })
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext>
+    for DeferStreamDirectiveOnValidOperations<'a>
+{
+    fn enter_document(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        document: &'a Document,
+    ) {
+        for definition in &document.definitions {
+            if let Definition::Operation(OperationDefinition::Subscription(subscription)) =
+                definition
+            {
+                collect_fragments_in_use(
+                    &subscription.selection_set,
+                    &visitor_context.known_fragments,
+                    &mut self.subscription_fragments,
+                );
+            }
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        operation: &OperationDefinition,
+    ) {
+        self.in_subscription = matches!(operation, OperationDefinition::Subscription(_));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        fragment: &FragmentDefinition,
+    ) {
+        self.in_subscription = self
+            .subscription_fragments
+            .contains(&fragment.name.as_str());
+    }
+
+    fn enter_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        if !self.in_subscription || if_argument_can_be_false(directive) {
+            return;
+        }
+
+        let directive_name = match directive.name.as_str() {
+            "defer" => "Defer",
+            "stream" => "Stream",
+            _ => return,
+        };
+
+        user_context.report_error(ValidationError {
+            error_code: self.error_code(),
+            locations: vec![directive.position],
+            message: format!(
+                "{} directive not supported on subscription operations. Disable `@{}` by setting the `if` argument to `false`.",
+                directive_name, directive.name
+            ),
+        });
+    }
+}
+
+impl<'v> ValidationRule for DeferStreamDirectiveOnValidOperations<'v> {
+    fn error_code<'a>(&self) -> &'a str {
+        "DeferStreamDirectiveOnValidOperations"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut DeferStreamDirectiveOnValidOperations::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn defer_and_stream_in_queries() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnValidOperations::new()));
+    let errors = test_operation_with_schema(
+        "{
+          human {
+            ...HumanFields @defer
+          }
+        }
+
+        subscription {
+          fieldB
+        }
+
+        fragment HumanFields on Human {
+          relatives @stream {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn defer_and_stream_in_subscriptions() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(DeferStreamDirectiveOnValidOperations::new()));
+    let errors = test_operation_with_schema(
+        "subscription ($enabled: Boolean!) {
+          ... @defer(if: $enabled) {
+            fieldB
+          }
+          ...SubscriptionFields @defer(if: true)
+        }
+
+        fragment SubscriptionFields on SubscriptionRoot {
+          fieldB @stream
+          ... @defer(if: false) {
+            fieldB
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Defer directive not supported on subscription operations. Disable `@defer` by setting the `if` argument to `false`.",
+            "Stream directive not supported on subscription operations. Disable `@stream` by setting the `if` argument to `false`."
+        ]
+    );
+}
@@ -39,12 +39,15 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for KnownArgumentNames<'a>
         _: &mut ValidationErrorContext,
         directive: &Directive,
     ) {
-        if let Some(directive_def) = visitor_context.schema.directive_by_name(&directive.name) {
-            self.current_known_arguments = Some((
-                ArgumentParent::Directive(&directive_def.name),
-                &directive_def.arguments,
-            ));
-        }
+        self.current_known_arguments = visitor_context
+            .schema
+            .directive_by_name(&directive.name)
+            .map(|directive_def| {
+                (
+                    ArgumentParent::Directive(&directive_def.name),
+                    &directive_def.arguments,
+                )
+            });
     }
 
     fn leave_directive(
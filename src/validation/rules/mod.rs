@@ -1,6 +1,9 @@
 pub mod defaults;
 pub mod rule;
 
+pub mod defer_stream_directive_label;
+pub mod defer_stream_directive_on_root_field;
+pub mod defer_stream_directive_on_valid_operations;
 pub mod fields_on_correct_type;
 pub mod fragments_on_composite_types;
 pub mod known_argument_names;
@@ -9,6 +12,7 @@ pub mod known_fragment_names;
 pub mod known_type_names;
 pub mod leaf_field_selections;
 pub mod lone_anonymous_operation;
+pub mod no_deprecated;
 pub mod no_fragments_cycle;
 pub mod no_undefined_variables;
 pub mod no_unused_fragments;
@@ -17,6 +21,7 @@ pub mod overlapping_fields_can_be_merged;
 pub mod possible_fragment_spreads;
 pub mod provided_required_arguments;
 pub mod single_field_subscriptions;
+pub mod stream_directive_on_list_field;
 pub mod unique_argument_names;
 pub mod unique_directives_per_location;
 pub mod unique_fragment_names;
@@ -29,6 +34,9 @@ pub mod variables_in_allowed_position;
 pub use self::defaults::*;
 pub use self::rule::*;
 
+pub use self::defer_stream_directive_label::*;
+pub use self::defer_stream_directive_on_root_field::*;
+pub use self::defer_stream_directive_on_valid_operations::*;
 pub use self::fields_on_correct_type::*;
 pub use self::fragments_on_composite_types::*;
 pub use self::known_argument_names::*;
@@ -37,6 +45,7 @@ pub use self::known_fragment_names::*;
 pub use self::known_type_names::*;
 pub use self::leaf_field_selections::*;
 pub use self::lone_anonymous_operation::*;
+pub use self::no_deprecated::*;
 pub use self::no_fragments_cycle::*;
 pub use self::no_undefined_variables::*;
 pub use self::no_unused_fragments::*;
@@ -45,6 +54,7 @@ pub use self::overlapping_fields_can_be_merged::*;
 pub use self::possible_fragment_spreads::*;
 pub use self::provided_required_arguments::*;
 pub use self::single_field_subscriptions::*;
+pub use self::stream_directive_on_list_field::*;
 pub use self::unique_argument_names::*;
 pub use self::unique_directives_per_location::*;
 pub use self::unique_fragment_names::*;
@@ -0,0 +1,366 @@
+use graphql_parser::Pos;
+
+use super::ValidationRule;
+use crate::ast::{
+    visit_document, DeprecationExtension, FieldByNameExtension, OperationVisitor,
+    OperationVisitorContext, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+};
+use crate::static_graphql::query::{Directive, Field, Value};
+use crate::static_graphql::schema::{DirectiveDefinition, TypeDefinition};
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+use std::collections::BTreeMap;
+
+/// No deprecated
+///
+/// A GraphQL document is only valid if all selected fields and all used enum values have not been
+/// deprecated.
+///
+/// Note: This rule is optional and is not part of the Validation section of the GraphQL
+/// Specification. The main purpose of this rule is detection of deprecated usages and not
+/// necessarily to forbid their use when querying a service.
+pub struct NoDeprecated<'a> {
+    current_directive: Option<&'a DirectiveDefinition>,
+    locations: Vec<Pos>,
+    input_objects: Vec<Option<&'a TypeDefinition>>,
+}
+
+impl<'a> NoDeprecated<'a> {
+    pub fn new() -> Self {
+        NoDeprecated {
+            current_directive: None,
+            locations: vec![],
+            input_objects: vec![],
+        }
+    }
+
+    fn current_locations(&self) -> Vec<Pos> {
+        self.locations.last().into_iter().cloned().collect()
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for NoDeprecated<'a> {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        self.locations.push(field.position);
+
+        if let Some(parent_type) = visitor_context.current_parent_type() {
+            if let Some(field_def) = parent_type.field_by_name(&field.name) {
+                if let Some(reason) = field_def.deprecation_reason() {
+                    user_context.report_error(ValidationError {
+                        error_code: self.error_code(),
+                        locations: vec![field.position],
+                        message: format!(
+                            "The field {}.{} is deprecated. {}",
+                            parent_type.name(),
+                            field_def.name,
+                            reason
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    fn leave_field(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &Field,
+    ) {
+        self.locations.pop();
+    }
+
+    fn enter_directive(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        directive: &Directive,
+    ) {
+        self.locations.push(directive.position);
+        self.current_directive = visitor_context.schema.directive_by_name(&directive.name);
+    }
+
+    fn leave_directive(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &Directive,
+    ) {
+        self.locations.pop();
+        self.current_directive = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        (argument_name, _): &'a (String, Value),
+    ) {
+        if let Some(directive_def) = self.current_directive {
+            if let Some(reason) = directive_def
+                .arguments
+                .iter()
+                .find(|arg| arg.name.eq(argument_name))
+                .and_then(|arg| arg.deprecation_reason())
+            {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: self.current_locations(),
+                    message: format!(
+                        "Directive \"@{}\" argument \"{}\" is deprecated. {}",
+                        directive_def.name, argument_name, reason
+                    ),
+                });
+            }
+        } else if let (Some(parent_type), Some(field_def)) = (
+            visitor_context.current_parent_type(),
+            visitor_context.current_field(),
+        ) {
+            if let Some(reason) = field_def
+                .arguments
+                .iter()
+                .find(|arg| arg.name.eq(argument_name))
+                .and_then(|arg| arg.deprecation_reason())
+            {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: self.current_locations(),
+                    message: format!(
+                        "Field \"{}.{}\" argument \"{}\" is deprecated. {}",
+                        parent_type.name(),
+                        field_def.name,
+                        argument_name,
+                        reason
+                    ),
+                });
+            }
+        }
+    }
+
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        let input_object = visitor_context
+            .current_input_type_literal()
+            .and_then(|t| visitor_context.schema.type_by_name(t.inner_type()));
+
+        self.input_objects.push(input_object);
+    }
+
+    fn leave_object_value(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        _: &BTreeMap<String, Value>,
+    ) {
+        self.input_objects.pop();
+    }
+
+    fn enter_object_field(
+        &mut self,
+        _: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        (field_name, _): &(String, Value),
+    ) {
+        if let Some(Some(input_object)) = self.input_objects.last() {
+            if let Some(reason) = input_object
+                .input_field_by_name(field_name)
+                .and_then(|input_field| input_field.deprecation_reason())
+            {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: self.current_locations(),
+                    message: format!(
+                        "The input field {}.{} is deprecated. {}",
+                        input_object.name(),
+                        field_name,
+                        reason
+                    ),
+                });
+            }
+        }
+    }
+
+    fn enter_enum_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        value: &String,
+    ) {
+        if let Some(TypeDefinition::Enum(enum_type)) = visitor_context.current_input_type() {
+            if let Some(reason) = enum_type
+                .values
+                .iter()
+                .find(|enum_value| enum_value.name.eq(value))
+                .and_then(|enum_value| enum_value.deprecation_reason())
+            {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: self.current_locations(),
+                    message: format!(
+                        "The enum value \"{}.{}\" is deprecated. {}",
+                        enum_type.name, value, reason
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl<'n> ValidationRule for NoDeprecated<'n> {
+    fn error_code<'a>(&self) -> &'a str {
+        "NoDeprecated"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut NoDeprecated::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[cfg(test)]
+static DEPRECATED_SCHEMA: &str = "
+directive @someDirective(
+  normalArg: String
+  deprecatedArg: String @deprecated(reason: \"Some arg reason.\")
+) on FIELD
+
+enum EnumType {
+  NORMAL_VALUE
+  DEPRECATED_VALUE @deprecated(reason: \"Some enum reason.\")
+}
+
+input InputType {
+  normalField: String
+  deprecatedField: String @deprecated(reason: \"Some input field reason.\")
+}
+
+type Query {
+  normalField(enumArg: [EnumType], inputArg: InputType): String
+  deprecatedField: String @deprecated(reason: \"Some field reason.\")
+  fieldWithDefaultReason: String @deprecated
+  someField(
+    normalArg: String
+    deprecatedArg: String @deprecated(reason: \"Some arg reason.\")
+  ): String
+}
+";
+
+#[test]
+fn ignores_fields_and_enum_values_that_are_not_deprecated() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "{
+          normalField(enumArg: [NORMAL_VALUE], inputArg: { normalField: \"\" })
+          someField(normalArg: \"\") @someDirective(normalArg: \"\")
+        }",
+        DEPRECATED_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn reports_usage_of_deprecated_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "{
+          deprecatedField
+          fieldWithDefaultReason
+          ...QueryFields
+        }
+
+        fragment QueryFields on Query {
+          deprecatedField
+        }",
+        DEPRECATED_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "The field Query.deprecatedField is deprecated. Some field reason.",
+            "The field Query.fieldWithDefaultReason is deprecated. No longer supported",
+            "The field Query.deprecatedField is deprecated. Some field reason."
+        ]
+    );
+    assert_eq!(
+        errors[0].locations,
+        vec![Pos {
+            line: 2,
+            column: 11
+        }]
+    );
+}
+
+#[test]
+fn reports_usage_of_deprecated_arguments() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "{
+          someField(deprecatedArg: \"\") @someDirective(deprecatedArg: \"\")
+        }",
+        DEPRECATED_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Field \"Query.someField\" argument \"deprecatedArg\" is deprecated. Some arg reason.",
+            "Directive \"@someDirective\" argument \"deprecatedArg\" is deprecated. Some arg reason."
+        ]
+    );
+}
+
+#[test]
+fn reports_usage_of_deprecated_input_fields_and_enum_values() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(NoDeprecated::new()));
+    let errors = test_operation_with_schema(
+        "query ($enum: EnumType = DEPRECATED_VALUE) {
+          normalField(
+            enumArg: [NORMAL_VALUE, DEPRECATED_VALUE]
+            inputArg: { normalField: \"\", deprecatedField: \"\" }
+          )
+        }",
+        DEPRECATED_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "The enum value \"EnumType.DEPRECATED_VALUE\" is deprecated. Some enum reason.",
+            "The enum value \"EnumType.DEPRECATED_VALUE\" is deprecated. Some enum reason.",
+            "The input field InputType.deprecatedField is deprecated. Some input field reason."
+        ]
+    );
+}
@@ -315,6 +315,21 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
         })
     }
 
+    // Two fields can only be merged if they are both streamed with the same arguments, or both
+    // not streamed.
+    fn is_same_stream(&self, f1_directives: &[Directive], f2_directives: &[Directive]) -> bool {
+        let stream1 = f1_directives.iter().find(|d| d.name == "stream");
+        let stream2 = f2_directives.iter().find(|d| d.name == "stream");
+
+        match (stream1, stream2) {
+            (Some(stream1), Some(stream2)) => {
+                self.is_same_arguments(&stream1.arguments, &stream2.arguments)
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
     // Two types conflict if both types could not apply to a value simultaneously.
     // Composite types are ignored as their individual field types will be compared
     // later recursively. However List and Non-Null types must match.
@@ -412,6 +427,19 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
             }
         }
 
+        if !self.is_same_stream(&field1.directives, &field2.directives) {
+            return Some(Conflict(
+                ConflictReason(
+                    out_field_name.to_string(),
+                    ConflictReasonMessage::Message(
+                        "they have differing stream directives".to_string(),
+                    ),
+                ),
+                vec![field1.position],
+                vec![field2.position],
+            ));
+        }
+
         let t1 = field1_def.as_ref().map(|def| &def.field_type);
         let t2 = field2_def.as_ref().map(|def| &def.field_type);
 
@@ -1062,6 +1090,61 @@ fn different_skip_include_directives() {
     assert_eq!(get_messages(&errors).len(), 0);
 }
 
+#[test]
+fn same_stream_directives() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(OverlappingFieldsCanBeMerged::new()));
+    let errors = test_operation_with_schema(
+        "fragment sameStreams on Human {
+          relatives @stream(label: \"relatives\", initialCount: 1) {
+            name
+          }
+          relatives @stream(initialCount: 1, label: \"relatives\") {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn different_stream_directives() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(OverlappingFieldsCanBeMerged::new()));
+    let errors = test_operation_with_schema(
+        "fragment differentStreams on Human {
+          relatives @stream(initialCount: 1) {
+            name
+          }
+          relatives @stream(initialCount: 2) {
+            name
+          }
+          pets @stream {
+            name
+          }
+          pets {
+            name
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "Fields \"relatives\" conflict because they have differing stream directives. Use different aliases on the fields to fetch both if this was intentional.",
+            "Fields \"pets\" conflict because they have differing stream directives. Use different aliases on the fields to fetch both if this was intentional."
+        ]
+    );
+}
+
 #[test]
 fn same_alias_different_field_target() {
     use crate::validation::test_utils::*;
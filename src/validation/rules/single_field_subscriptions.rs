@@ -1,7 +1,7 @@
 use super::ValidationRule;
 use crate::ast::{
-    collect_fields, visit_document, OperationVisitor, OperationVisitorContext,
-    SchemaDocumentExtension,
+    collect_grouped_fields, visit_document, CollectFieldsOptions, OperationVisitor,
+    OperationVisitorContext, SchemaDocumentExtension,
 };
 use crate::static_graphql::query::OperationDefinition;
 use crate::static_graphql::schema::TypeDefinition;
@@ -32,12 +32,14 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
                 if let Some(subscription_type) = visitor_context.schema.subscription_type() {
                     let operation_name = subscription.name.as_ref();
 
-                    let selection_set_fields = collect_fields(
+                    let subscription_type = TypeDefinition::Object(subscription_type.clone());
+                    let selection_set_fields = collect_grouped_fields(
                         &subscription.selection_set,
-                        &TypeDefinition::Object(subscription_type.clone()),
-                        &visitor_context.known_fragments,
+                        &subscription_type,
                         visitor_context,
-                    );
+                        CollectFieldsOptions::default(),
+                    )
+                    .fields;
 
                     if selection_set_fields.len() > 1 {
                         let error_message = match operation_name {
@@ -57,9 +59,9 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for SingleFieldSubscriptio
 
                     selection_set_fields
                   .into_iter()
-                  .filter_map(|(field_name, fields_records)| {
-                      if field_name.starts_with("__") {
-                          return Some((field_name, fields_records));
+                  .filter_map(|(response_key, fields_records)| {
+                      if fields_records[0].name.starts_with("__") {
+                          return Some((response_key, fields_records));
                       }
 
                       None
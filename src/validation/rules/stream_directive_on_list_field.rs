@@ -0,0 +1,126 @@
+use super::ValidationRule;
+use crate::ast::{
+    visit_document, FieldByNameExtension, OperationVisitor, OperationVisitorContext,
+    TypeDefinitionExtension,
+};
+use crate::static_graphql::query::Field;
+use crate::static_graphql::schema::Type;
+use crate::validation::utils::{ValidationError, ValidationErrorContext};
+
+/// Stream directives are used on list fields
+///
+/// A GraphQL document is only valid if stream directives are used on list fields.
+///
+/// See https://github.com/graphql/graphql-spec/pull/742
+pub struct StreamDirectiveOnListField;
+
+impl StreamDirectiveOnListField {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<'a> OperationVisitor<'a, ValidationErrorContext> for StreamDirectiveOnListField {
+    fn enter_field(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        user_context: &mut ValidationErrorContext,
+        field: &Field,
+    ) {
+        let stream = match field.directives.iter().find(|d| d.name == "stream") {
+            Some(stream) => stream,
+            None => return,
+        };
+
+        let parent_type = match visitor_context.current_parent_type() {
+            Some(parent_type) => parent_type,
+            None => return,
+        };
+
+        if let Some(field_def) = parent_type.field_by_name(&field.name) {
+            let nullable_type = match &field_def.field_type {
+                Type::NonNullType(inner_type) => inner_type.as_ref(),
+                field_type => field_type,
+            };
+
+            if !matches!(nullable_type, Type::ListType(_)) {
+                user_context.report_error(ValidationError {
+                    error_code: self.error_code(),
+                    locations: vec![stream.position],
+                    message: format!(
+                        "Directive \"@stream\" cannot be used on non-list field \"{}.{}\".",
+                        parent_type.name(),
+                        field_def.name
+                    ),
+                });
+            }
+        }
+    }
+}
+
+impl ValidationRule for StreamDirectiveOnListField {
+    fn error_code<'a>(&self) -> &'a str {
+        "StreamDirectiveOnListField"
+    }
+
+    fn validate<'a>(
+        &self,
+        ctx: &'a mut OperationVisitorContext,
+        error_collector: &mut ValidationErrorContext,
+    ) {
+        visit_document(
+            &mut StreamDirectiveOnListField::new(),
+            &ctx.operation,
+            ctx,
+            error_collector,
+        );
+    }
+}
+
+#[test]
+fn stream_on_list_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(StreamDirectiveOnListField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          human {
+            pets @stream(initialCount: 1) {
+              name
+            }
+            relatives @stream {
+              name
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn stream_on_non_list_fields() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(StreamDirectiveOnListField::new()));
+    let errors = test_operation_with_schema(
+        "{
+          dog {
+            name @stream
+            ... @stream {
+              nickname
+            }
+          }
+        }",
+        TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec!["Directive \"@stream\" cannot be used on non-list field \"Dog.name\"."]
+    );
+}
@@ -3,7 +3,8 @@ use std::collections::BTreeMap;
 use graphql_parser::schema::TypeDefinition;
 
 use crate::ast::{
-    InputValueHelpers, SchemaDocumentExtension, TypeDefinitionExtension, TypeExtension,
+    InputValueHelpers, OneOfExtension, SchemaDocumentExtension, TypeDefinitionExtension,
+    TypeExtension,
 };
 use crate::static_graphql::query::Value;
 use crate::validation::utils::ValidationError;
@@ -103,6 +104,36 @@ impl ValuesOfCorrectType {
             }
         }
     }
+
+    /// A value of a `@oneOf` input object must have exactly one field, with a non-null value.
+    /// Variables used as the field are checked by `VariablesInAllowedPosition`.
+    fn validate_one_of_value(
+        &self,
+        user_context: &mut ValidationErrorContext,
+        type_name: &str,
+        object_value: &BTreeMap<String, Value>,
+    ) {
+        if object_value.len() != 1 {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!(
+                    "OneOf Input Object \"{}\" must specify exactly one key.",
+                    type_name
+                ),
+                locations: vec![],
+            });
+
+            return;
+        }
+
+        if let Some((field_name, Value::Null)) = object_value.iter().next() {
+            user_context.report_error(ValidationError {
+                error_code: self.error_code(),
+                message: format!("Field \"{}.{}\" must be non-null.", type_name, field_name),
+                locations: vec![],
+            })
+        }
+    }
 }
 
 impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
@@ -159,6 +190,10 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for ValuesOfCorrectType {
                     })
                 }
             });
+
+            if input_object_def.is_one_of() {
+                self.validate_one_of_value(user_context, &input_object_def.name, object_value);
+            }
         }
     }
 
@@ -1959,3 +1994,49 @@ fn list_variables_with_invalid_item() {
         vec!["Expected value of type \"String\", found 2."]
     );
 }
+
+#[test]
+fn valid_one_of_values() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "
+        query ($b: Int!) {
+          a: oneOfArgField(oneOfArg: { a: \"abc\" })
+          b: oneOfArgField(oneOfArg: { b: $b })
+        }",
+        ONE_OF_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    assert_eq!(get_messages(&errors).len(), 0);
+}
+
+#[test]
+fn invalid_one_of_values() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(ValuesOfCorrectType::new()));
+    let errors = test_operation_with_schema(
+        "
+        query ($b: Int) {
+          none: oneOfArgField(oneOfArg: {})
+          both: oneOfArgField(oneOfArg: { a: \"abc\", b: 123 })
+          null: oneOfArgField(oneOfArg: { a: null })
+          variable: oneOfArgField(oneOfArg: { b: $b })
+        }",
+        ONE_OF_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(
+        messages,
+        vec![
+            "OneOf Input Object \"OneOfInput\" must specify exactly one key.",
+            "OneOf Input Object \"OneOfInput\" must specify exactly one key.",
+            "Field \"OneOfInput.a\" must be non-null."
+        ]
+    );
+}
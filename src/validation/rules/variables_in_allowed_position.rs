@@ -1,11 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::{
     ast::{
-        visit_document, AstNodeWithName, OperationVisitor, OperationVisitorContext,
-        SchemaDocumentExtension,
+        visit_document, AstNodeWithName, OneOfExtension, OperationVisitor, OperationVisitorContext,
+        SchemaDocumentExtension, TypeExtension,
     },
     static_graphql::query::{Type, Value, VariableDefinition},
+    static_graphql::schema::TypeDefinition,
     validation::utils::{ValidationError, ValidationErrorContext},
 };
 
@@ -19,6 +20,8 @@ use super::ValidationRule;
 pub struct VariablesInAllowedPosition<'a> {
     spreads: HashMap<Scope<'a>, HashSet<&'a str>>,
     variable_usages: HashMap<Scope<'a>, Vec<(&'a str, &'a Type)>>,
+    /// The variables used as the field of a `@oneOf` input object, with the name of the type.
+    one_of_usages: HashMap<Scope<'a>, Vec<(String, String)>>,
     variable_defs: HashMap<Scope<'a>, Vec<&'a VariableDefinition>>,
     current_scope: Option<Scope<'a>>,
 }
@@ -28,6 +31,7 @@ impl<'a> VariablesInAllowedPosition<'a> {
         VariablesInAllowedPosition {
             spreads: HashMap::new(),
             variable_usages: HashMap::new(),
+            one_of_usages: HashMap::new(),
             variable_defs: HashMap::new(),
             current_scope: None,
         }
@@ -78,6 +82,23 @@ impl<'a> VariablesInAllowedPosition<'a> {
             }
         }
 
+        if let Some(usages) = self.one_of_usages.get(from) {
+            for (var_name, type_name) in usages {
+                if let Some(var_def) = var_defs.iter().find(|var_def| var_def.name == *var_name) {
+                    if !var_def.var_type.is_non_null() {
+                        user_context.report_error(ValidationError {
+                            error_code: self.error_code(),
+                            message: format!(
+                                "Variable \"${}\" is of type \"{}\" but must be non-nullable to be used for OneOf Input Object \"{}\".",
+                                var_name, var_def.var_type, type_name,
+                            ),
+                            locations: vec![var_def.position],
+                        });
+                    }
+                }
+            }
+        }
+
         if let Some(spreads) = self.spreads.get(from) {
             for spread in spreads {
                 self.collect_incorrect_usages(
@@ -162,6 +183,30 @@ impl<'a> OperationVisitor<'a, ValidationErrorContext> for VariablesInAllowedPosi
         }
     }
 
+    fn enter_object_value(
+        &mut self,
+        visitor_context: &mut OperationVisitorContext<'a>,
+        _: &mut ValidationErrorContext,
+        object_value: &BTreeMap<String, Value>,
+    ) {
+        if let (Some(scope), Some(TypeDefinition::InputObject(input_object))) =
+            (&self.current_scope, visitor_context.current_input_type())
+        {
+            if !input_object.is_one_of() {
+                return;
+            }
+
+            for value in object_value.values() {
+                if let Value::Variable(var_name) = value {
+                    self.one_of_usages
+                        .entry(scope.clone())
+                        .or_insert_with(Vec::new)
+                        .push((var_name.clone(), input_object.name.clone()));
+                }
+            }
+        }
+    }
+
     fn enter_variable_value(
         &mut self,
         visitor_context: &mut OperationVisitorContext<'a>,
@@ -681,3 +726,54 @@ fn boolean_to_boolean_non_null_with_default_value() {
     let messages = get_messages(&errors);
     assert_eq!(messages.len(), 0);
 }
+
+#[test]
+fn non_null_variables_in_one_of_input_objects() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(VariablesInAllowedPosition::new()));
+    let errors = test_operation_with_schema(
+        "query Query($a: String!, $b: Int!) {
+          oneOfArgField(oneOfArg: { a: $a })
+          ...OneOfFields
+        }
+
+        fragment OneOfFields on QueryRoot {
+          b: oneOfArgField(oneOfArg: { b: $b })
+        }",
+        ONE_OF_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let messages = get_messages(&errors);
+    assert_eq!(messages.len(), 0);
+}
+
+#[test]
+fn nullable_variables_in_one_of_input_objects() {
+    use crate::validation::test_utils::*;
+
+    let mut plan = create_plan_from_rule(Box::new(VariablesInAllowedPosition::new()));
+    let errors = test_operation_with_schema(
+        "query Query($a: String, $b: Int = 1) {
+          oneOfArgField(oneOfArg: { a: $a })
+          ...OneOfFields
+        }
+
+        fragment OneOfFields on QueryRoot {
+          b: oneOfArgField(oneOfArg: { b: $b })
+        }",
+        ONE_OF_TEST_SCHEMA,
+        &mut plan,
+    );
+
+    let mut messages = get_messages(&errors);
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "Variable \"$a\" is of type \"String\" but must be non-nullable to be used for OneOf Input Object \"OneOfInput\".",
+            "Variable \"$b\" is of type \"Int\" but must be non-nullable to be used for OneOf Input Object \"OneOfInput\"."
+        ]
+    );
+}
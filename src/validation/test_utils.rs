@@ -1,3 +1,4 @@
+use super::lint::schema::{lint_schema, SchemaLintPlan, SchemaLintRule};
 use super::rules::ValidationRule;
 use super::utils::ValidationError;
 use super::validate::validate;
@@ -228,12 +229,26 @@ directive @testDirective on FIELD | FRAGMENT_DEFINITION
 directive @repeatable repeatable on FIELD | FRAGMENT_DEFINITION
 ";
 
+#[cfg(test)]
+pub static ONE_OF_TEST_SCHEMA: &str = "
+input OneOfInput @oneOf {
+  a: String
+  b: Int
+}
+type QueryRoot {
+  oneOfArgField(oneOfArg: OneOfInput): String
+}
+schema {
+  query: QueryRoot
+}
+";
+
 #[cfg(test)]
 pub fn create_plan_from_rule(rule: Box<dyn ValidationRule>) -> ValidationPlan {
     let mut rules = Vec::new();
     rules.push(rule);
 
-    let plan = ValidationPlan { rules };
+    let plan = ValidationPlan::from(rules);
 
     plan
 }
@@ -287,3 +302,13 @@ pub fn test_operation_with_schema<'a>(
 
     validate(&schema_ast, &operation_ast, &plan)
 }
+
+#[cfg(test)]
+pub fn test_schema_lint(schema: &str, rule: Box<dyn SchemaLintRule>) -> Vec<ValidationError> {
+    let schema_ast = graphql_parser::parse_schema(schema)
+        .expect("Failed to parse schema")
+        .into_static();
+    let plan = SchemaLintPlan::from(vec![rule]);
+
+    lint_schema(&schema_ast, &plan).errors
+}
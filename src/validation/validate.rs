@@ -1,45 +1,170 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
 use super::{
     rules::ValidationRule,
     utils::{ValidationError, ValidationErrorContext},
 };
 
 use crate::{
-    ast::OperationVisitorContext,
+    ast::{with_builtin_definitions, OperationVisitorContext},
     static_graphql::{query, schema},
 };
 
+/// How the errors reported by a validation rule are treated.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    /// Errors reported by the rule are returned as errors, and make the operation invalid.
+    #[default]
+    Error,
+    /// Errors reported by the rule are returned as warnings, and do not make the operation invalid.
+    Warning,
+    /// The rule is not executed.
+    Off,
+}
+
+/// Severity of the rules of a plan, by error code. Rules that are not listed are treated as errors.
+#[derive(Clone, Debug, Default)]
+pub struct RuleSeverities(HashMap<String, ValidationSeverity>);
+
+impl RuleSeverities {
+    pub fn set(&mut self, error_code: &str, severity: ValidationSeverity) {
+        self.0.insert(error_code.to_string(), severity);
+    }
+
+    pub fn get(&self, error_code: &str) -> ValidationSeverity {
+        self.0.get(error_code).copied().unwrap_or_default()
+    }
+}
+
 pub struct ValidationPlan {
     pub rules: Vec<Box<dyn ValidationRule>>,
+    pub severities: RuleSeverities,
+    /// When enabled, the operations are validated against the schema with the definitions of the
+    /// specification it does not declare (see `with_builtin_definitions`).
+    ///
+    /// The schema is cloned and augmented on each validation: to validate many operations against
+    /// the same schema, augment it once with `with_builtin_definitions` and leave this disabled.
+    pub builtin_definitions: bool,
 }
 
 impl ValidationPlan {
     pub fn new() -> Self {
-        Self { rules: vec![] }
+        Self {
+            rules: vec![],
+            severities: RuleSeverities::default(),
+            builtin_definitions: false,
+        }
     }
 
     pub fn from(rules: Vec<Box<dyn ValidationRule>>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            severities: RuleSeverities::default(),
+            builtin_definitions: false,
+        }
     }
 
     pub fn add_rule(&mut self, rule: Box<dyn ValidationRule>) {
         self.rules.push(rule);
     }
+
+    pub fn add_rule_with_severity(
+        &mut self,
+        rule: Box<dyn ValidationRule>,
+        severity: ValidationSeverity,
+    ) {
+        self.set_severity(rule.error_code(), severity);
+        self.rules.push(rule);
+    }
+
+    /// Sets the severity of the rule with the given error code.
+    pub fn set_severity(&mut self, error_code: &str, severity: ValidationSeverity) {
+        self.severities.set(error_code, severity);
+    }
+
+    pub fn severity_of(&self, rule: &dyn ValidationRule) -> ValidationSeverity {
+        self.severities.get(rule.error_code())
+    }
 }
 
+/// The result of validating an operation, with the reported errors split by severity.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ValidationResult {
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationError>,
+}
+
+impl ValidationResult {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Adds the errors reported by a rule, as errors or warnings depending on its severity.
+    pub fn append(&mut self, severity: ValidationSeverity, errors: &mut Vec<ValidationError>) {
+        match severity {
+            ValidationSeverity::Error => self.errors.append(errors),
+            ValidationSeverity::Warning => self.warnings.append(errors),
+            ValidationSeverity::Off => {}
+        }
+    }
+}
+
+/// Validates the operation and returns the errors reported by the rules with the `Error`
+/// severity. Use `validate_with_severity` to also get the warnings.
 pub fn validate<'a>(
     schema: &'a schema::Document,
     operation: &'a query::Document,
     validation_plan: &'a ValidationPlan,
 ) -> Vec<ValidationError> {
-    let mut error_collector = ValidationErrorContext::new();
+    validate_with_severity(schema, operation, validation_plan).errors
+}
+
+/// Validates the operation and returns the errors and the warnings reported by the rules, depending
+/// on their severity. When `builtin_definitions` is enabled on the plan, the schema is cloned and
+/// augmented with `with_builtin_definitions` on every call.
+pub fn validate_with_severity<'a>(
+    schema: &'a schema::Document,
+    operation: &'a query::Document,
+    validation_plan: &'a ValidationPlan,
+) -> ValidationResult {
+    if validation_plan.builtin_definitions {
+        let schema = with_builtin_definitions(schema);
+
+        return run_rules(&schema, operation, validation_plan, |_| true);
+    }
+
+    run_rules(schema, operation, validation_plan, |_| true)
+}
+
+/// Runs the rules of the plan accepted by `filter` on the operation, with the schema as is.
+pub(crate) fn run_rules<F>(
+    schema: &schema::Document,
+    operation: &query::Document,
+    validation_plan: &ValidationPlan,
+    filter: F,
+) -> ValidationResult
+where
+    F: Fn(&dyn ValidationRule) -> bool,
+{
+    let mut result = ValidationResult::default();
     let mut validation_context = OperationVisitorContext::new(operation, schema);
 
-    validation_plan
-        .rules
-        .iter()
-        .for_each(|rule| rule.validate(&mut validation_context, &mut error_collector));
+    for rule in validation_plan.rules.iter() {
+        let severity = validation_plan.severity_of(rule.as_ref());
+
+        if severity == ValidationSeverity::Off || !filter(rule.as_ref()) {
+            continue;
+        }
+
+        let mut error_collector = ValidationErrorContext::new();
+        rule.validate(&mut validation_context, &mut error_collector);
+        result.append(severity, &mut error_collector.errors);
+    }
 
-    error_collector.errors
+    result
 }
 
 #[test]
@@ -163,3 +288,80 @@ fn fragment_loop_through_multiple_frags() {
     ]
     )
 }
+
+#[test]
+fn rules_with_warning_severity_do_not_produce_errors() {
+    use crate::validation::rules::{default_rules_validation_plan, NoDeprecated};
+    use crate::validation::test_utils::*;
+
+    let mut plan = default_rules_validation_plan();
+    plan.add_rule_with_severity(Box::new(NoDeprecated::new()), ValidationSeverity::Warning);
+    plan.set_severity("NoUnusedFragments", ValidationSeverity::Off);
+
+    let schema_ast = graphql_parser::parse_schema(
+        "
+type Query {
+  name: String
+  oldName: String @deprecated(reason: \"Use name.\")
+}
+",
+    )
+    .expect("Failed to parse schema");
+    let operation_ast = graphql_parser::parse_query(
+        "
+        { oldName }
+
+        fragment Unused on Query { name }
+    ",
+    )
+    .expect("Failed to parse query");
+
+    let result = validate_with_severity(&schema_ast, &operation_ast, &plan);
+
+    assert!(result.is_valid());
+    assert_eq!(
+        get_messages(&result.warnings),
+        vec!["The field Query.oldName is deprecated. Use name."]
+    );
+    assert_eq!(validate(&schema_ast, &operation_ast, &plan).len(), 0);
+}
+
+#[test]
+fn validate_with_builtin_definitions() {
+    use crate::validation::rules::default_rules_validation_plan;
+    use crate::validation::test_utils::*;
+
+    let schema_ast = graphql_parser::parse_schema(
+        "
+type Query {
+  name: String
+}
+",
+    )
+    .expect("Failed to parse schema");
+    let operation_ast = graphql_parser::parse_query(
+        "
+        query ($withName: Boolean!) {
+          name @include(if: $withName)
+          __schema {
+            queryType {
+              name
+            }
+          }
+        }
+    ",
+    )
+    .expect("Failed to parse query");
+
+    let mut plan = default_rules_validation_plan();
+    assert_eq!(
+        get_messages(&validate(&schema_ast, &operation_ast, &plan)),
+        vec![
+            "Unknown type \"Boolean\".",
+            "Unknown directive \"@include\"."
+        ]
+    );
+
+    plan.builtin_definitions = true;
+    assert_eq!(validate(&schema_ast, &operation_ast, &plan).len(), 0);
+}